@@ -4,7 +4,7 @@ use proc_macro2::Span;
 
 use std::ops::Deref;
 use syn::parse::{Parse, ParseStream, Result as ParseResult};
-use syn::{parse_macro_input, Ident, LitStr, Token};
+use syn::{parse_macro_input, Ident, LitStr, Macro, Token};
 
 /// Abigen proc macro definition and helper functions/types.
 
@@ -12,7 +12,17 @@ use syn::{parse_macro_input, Ident, LitStr, Token};
 pub fn abigen(input: TokenStream) -> TokenStream {
     let args = parse_macro_input!(input as Spanned<ContractArgs>);
 
-    let c = Abigen::new(&args.name, &args.abi).unwrap();
+    let mut c = match Abigen::new(&args.name, &args.abi) {
+        Ok(c) => c,
+        Err(e) => {
+            return syn::Error::new(args.span(), format!("failed to read the ABI: {:#}", e))
+                .to_compile_error()
+                .into()
+        }
+    };
+    if args.namespaced {
+        c = c.namespaced();
+    }
 
     c.expand().unwrap().into()
 }
@@ -76,6 +86,8 @@ impl<T> Deref for Spanned<T> {
 pub(crate) struct ContractArgs {
     name: String,
     abi: String,
+    /// Set by a trailing `namespaced` argument, see `Abigen::namespaced`.
+    namespaced: bool,
 }
 
 impl ParseInner for ContractArgs {
@@ -86,7 +98,11 @@ impl ParseInner for ContractArgs {
         // skip the comma
         input.parse::<Token![,]>()?;
 
-        let (span, abi) = {
+        // The ABI, or the path to it, is either a literal or `env!("VAR")`, read here
+        // as macros in the arguments of a procedural macro aren't expanded.
+        let (span, abi) = if input.peek(Ident) && input.peek2(Token![!]) {
+            parse_env_macro(input)?
+        } else {
             let literal = input.parse::<LitStr>()?;
             (literal.span(), literal.value())
         };
@@ -94,6 +110,50 @@ impl ParseInner for ContractArgs {
             input.parse::<Token![,]>()?;
         }
 
-        Ok((span, ContractArgs { name, abi }))
+        let mut namespaced = false;
+        if !input.is_empty() {
+            let option = input.parse::<Ident>()?;
+            if option != "namespaced" {
+                return Err(syn::Error::new(
+                    option.span(),
+                    format!("unknown option `{}`, expected `namespaced`", option),
+                ));
+            }
+            namespaced = true;
+
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        Ok((
+            span,
+            ContractArgs {
+                name,
+                abi,
+                namespaced,
+            },
+        ))
     }
 }
+
+/// Parses an `env!("VAR")` argument into the value of `VAR` at expansion time.
+fn parse_env_macro(input: ParseStream) -> ParseResult<(Span, String)> {
+    let mac = input.parse::<Macro>()?;
+    if !mac.path.is_ident("env") {
+        return Err(syn::Error::new_spanned(
+            &mac.path,
+            "expected a string literal or `env!(\"VAR\")`",
+        ));
+    }
+
+    let var = mac.parse_body::<LitStr>()?;
+    let value = std::env::var(var.value()).map_err(|_| {
+        syn::Error::new(
+            var.span(),
+            format!("environment variable `{}` isn't set", var.value()),
+        )
+    })?;
+
+    Ok((var.span(), value))
+}
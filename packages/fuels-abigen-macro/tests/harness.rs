@@ -1,9 +1,16 @@
-use fuel_tx::{AssetId, ContractId, Receipt, Salt};
+use async_trait::async_trait;
+use fuel_tx::{Address, AssetId, ContractId, Input, Output, Receipt, Salt, Transaction};
+use fuel_vm::consts::REG_ONE;
+use fuel_vm::prelude::Opcode;
 use fuels_abigen_macro::abigen;
 use fuels_rs::prelude::*;
+use fuels_rs::signers::signature::Signature;
+use fuels_rs::signers::wallet::WalletError;
 use rand::rngs::StdRng;
 use rand::{Rng, SeedableRng};
 use sha2::{Digest, Sha256};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 
 fn null_contract_id() -> String {
     // a null contract address ~[0u8;32]
@@ -46,6 +53,24 @@ async fn compile_bindings_from_contract_file() {
     assert_eq!("000000009593586c000000000000002a", encoded);
 }
 
+#[tokio::test]
+async fn compile_bindings_from_env_var() {
+    // `ABIGEN_TEST_ABI_PATH` is set in `.cargo/config.toml` to the absolute path of an ABI
+    abigen!(SimpleContract, env!("ABIGEN_TEST_ABI_PATH"));
+
+    let (provider, wallet) = setup_test_provider_and_wallet().await;
+    let contract_instance = SimpleContract::new(null_contract_id(), provider, wallet);
+
+    let contract_call = contract_instance.takes_u32_returns_bool(42);
+    let encoded = format!(
+        "{}{}",
+        hex::encode(contract_call.encoded_selector),
+        hex::encode(contract_call.encoded_args)
+    );
+
+    assert_eq!("000000006355e6ee000000000000002a", encoded);
+}
+
 #[tokio::test]
 async fn compile_bindings_from_inline_contract() {
     // Generates the bindings from the an ABI definition inline.
@@ -89,6 +114,388 @@ async fn compile_bindings_from_inline_contract() {
     assert_eq!("000000009593586c000000000000002a", encoded);
 }
 
+#[tokio::test]
+async fn encode_calldata_without_calling() {
+    abigen!(
+        SimpleContract,
+        r#"
+        [
+            {
+                "type": "function",
+                "inputs": [
+                    {
+                        "name": "arg",
+                        "type": "u32"
+                    }
+                ],
+                "name": "takes_u32_returns_bool",
+                "outputs": [
+                    {
+                        "name": "",
+                        "type": "bool"
+                    }
+                ]
+            }
+        ]
+        "#,
+    );
+
+    let (provider, wallet) = setup_test_provider_and_wallet().await;
+    let contract_instance = SimpleContract::new(null_contract_id(), provider, wallet);
+
+    let calldata = contract_instance
+        .takes_u32_returns_bool_calldata(7)
+        .unwrap();
+
+    // Selector, then the argument padded to a word
+    assert_eq!(calldata.len(), 16);
+    assert_eq!(hex::encode(&calldata[..8]), "000000006355e6ee");
+    assert_eq!(hex::encode(&calldata), "000000006355e6ee0000000000000007");
+
+    let contract_call = contract_instance.takes_u32_returns_bool(7);
+    assert_eq!(
+        calldata,
+        [
+            contract_call.encoded_selector.to_vec(),
+            contract_call.encoded_args
+        ]
+        .concat()
+    );
+}
+
+#[test]
+fn output_params_constant() {
+    use fuels_rs::core::abi_decoder::ABIDecoder;
+    use fuels_rs::core::ParamType;
+
+    abigen!(
+        SimpleContract,
+        r#"
+        [
+            {
+                "type": "function",
+                "inputs": [
+                    {
+                        "name": "arg",
+                        "type": "u32"
+                    }
+                ],
+                "name": "takes_u32_returns_bool",
+                "outputs": [
+                    {
+                        "name": "",
+                        "type": "bool"
+                    }
+                ]
+            }
+        ]
+        "#,
+    );
+
+    assert_eq!(output_types::TAKES_U32_RETURNS_BOOL, [ParamType::Bool]);
+    assert_eq!(
+        output_types::takes_u32_returns_bool(),
+        vec![ParamType::Bool]
+    );
+
+    // Decoding the output with the constant rather than the generated type
+    let decoded = ABIDecoder::new()
+        .decode(
+            output_types::TAKES_U32_RETURNS_BOOL,
+            &[0, 0, 0, 0, 0, 0, 0, 1],
+        )
+        .unwrap();
+    assert_eq!(decoded, vec![Token::Bool(true)]);
+}
+
+#[test]
+fn abi_hash_of_each_contract() {
+    // Both contracts' bindings are glob re-exported into this scope
+    abigen!(
+        FirstContract,
+        r#"
+        [
+            {
+                "type": "function",
+                "inputs": [{ "name": "arg", "type": "u32" }],
+                "name": "takes_u32",
+                "outputs": []
+            }
+        ]
+        "#,
+    );
+    abigen!(
+        SecondContract,
+        r#"
+        [
+            {
+                "type": "function",
+                "inputs": [{ "name": "arg", "type": "u64" }],
+                "name": "takes_u64",
+                "outputs": []
+            }
+        ]
+        "#,
+    );
+
+    assert_eq!(FirstContract::ABI_HASH.len(), 64);
+    assert_ne!(FirstContract::ABI_HASH, SecondContract::ABI_HASH);
+}
+
+#[tokio::test]
+async fn namespaced_bindings_with_colliding_types() {
+    // Both contracts declare an `Info` struct, with different fields
+    abigen!(
+        FirstContract,
+        r#"
+        [
+            {
+                "type": "function",
+                "inputs": [
+                    {
+                        "name": "info",
+                        "type": "struct Info",
+                        "components": [
+                            {
+                                "name": "id",
+                                "type": "u64"
+                            }
+                        ]
+                    }
+                ],
+                "name": "takes_info",
+                "outputs": []
+            }
+        ]
+        "#,
+        namespaced
+    );
+    abigen!(
+        SecondContract,
+        r#"
+        [
+            {
+                "type": "function",
+                "inputs": [
+                    {
+                        "name": "info",
+                        "type": "struct Info",
+                        "components": [
+                            {
+                                "name": "active",
+                                "type": "bool"
+                            },
+                            {
+                                "name": "level",
+                                "type": "u8"
+                            }
+                        ]
+                    }
+                ],
+                "name": "takes_info",
+                "outputs": []
+            }
+        ]
+        "#,
+        namespaced
+    );
+
+    let (provider, wallet) = setup_test_provider_and_wallet().await;
+    let first =
+        first_contract::FirstContract::new(null_contract_id(), provider.clone(), wallet.clone());
+    let second = second_contract::SecondContract::new(null_contract_id(), provider, wallet);
+
+    let first_call = first.takes_info(first_contract::Info { id: 42 });
+    let second_call = second.takes_info(second_contract::Info {
+        active: true,
+        level: 3,
+    });
+
+    assert_eq!(hex::encode(first_call.encoded_args), "000000000000002a");
+    assert_eq!(
+        hex::encode(second_call.encoded_args),
+        "00000000000000010000000000000003"
+    );
+}
+
+#[tokio::test]
+async fn calls_borrow_the_contract_instance() {
+    abigen!(
+        SimpleContract,
+        r#"
+        [
+            {
+                "type": "function",
+                "inputs": [
+                    {
+                        "name": "arg",
+                        "type": "u32"
+                    }
+                ],
+                "name": "takes_u32",
+                "outputs": []
+            },
+            {
+                "type": "function",
+                "inputs": [
+                    {
+                        "name": "arg",
+                        "type": "bool"
+                    }
+                ],
+                "name": "takes_bool",
+                "outputs": []
+            }
+        ]
+        "#,
+    );
+
+    let (provider, wallet) = setup_test_provider_and_wallet().await;
+    let contract_instance = SimpleContract::new(null_contract_id(), provider, wallet);
+
+    // Both calls are built from the same handle, which is only borrowed
+    let first_call = contract_instance.takes_u32(42);
+    let second_call = contract_instance.takes_bool(true);
+
+    // The calls own what they need, so they outlive the handle
+    drop(contract_instance);
+
+    assert_eq!("000000000000002a", hex::encode(first_call.encoded_args));
+    assert_eq!("0000000000000001", hex::encode(second_call.encoded_args));
+}
+
+#[test]
+fn set_configurable_constants() {
+    abigen!(
+        ConfigurableContract,
+        r#"
+        {
+            "types": [
+                { "typeId": 0, "type": "()", "components": [], "typeParameters": null },
+                { "typeId": 1, "type": "u64", "components": null, "typeParameters": null }
+            ],
+            "functions": [
+                {
+                    "inputs": [],
+                    "name": "noop",
+                    "output": { "name": "", "type": 0, "typeArguments": null }
+                }
+            ],
+            "loggedTypes": [],
+            "configurables": [
+                {
+                    "name": "MAX_SUPPLY",
+                    "configurableType": { "name": "", "type": 1, "typeArguments": null },
+                    "offset": 8
+                }
+            ]
+        }
+        "#,
+    );
+
+    let configurables = Configurables::new().set_max_supply(1_000);
+    assert_eq!(
+        configurables.overrides(),
+        &[(8, 1_000u64.to_be_bytes().to_vec())]
+    );
+
+    let mut bytecode = vec![0xff; 16];
+    configurables.apply(&mut bytecode).unwrap();
+    assert_eq!(bytecode[..8], [0xff; 8]);
+    assert_eq!(bytecode[8..], 1_000u64.to_be_bytes());
+
+    // The constant doesn't fit in a bytecode that's too short
+    let mut bytecode = vec![0; 12];
+    assert!(configurables.apply(&mut bytecode).is_err());
+}
+
+#[test]
+fn configurable_offset_past_the_address_space() {
+    abigen!(
+        ConfigurableContract,
+        r#"
+        {
+            "types": [
+                { "typeId": 0, "type": "()", "components": [], "typeParameters": null },
+                { "typeId": 1, "type": "u64", "components": null, "typeParameters": null }
+            ],
+            "functions": [
+                {
+                    "inputs": [],
+                    "name": "noop",
+                    "output": { "name": "", "type": 0, "typeArguments": null }
+                }
+            ],
+            "loggedTypes": [],
+            "configurables": [
+                {
+                    "name": "MAX_SUPPLY",
+                    "configurableType": { "name": "", "type": 1, "typeArguments": null },
+                    "offset": 18446744073709551612
+                }
+            ]
+        }
+        "#,
+    );
+
+    // The end of the constant overflows instead of wrapping around to the start
+    let configurables = Configurables::new().set_max_supply(1_000);
+    let mut bytecode = vec![0; 16];
+    assert!(configurables.apply(&mut bytecode).is_err());
+    assert_eq!(bytecode, vec![0; 16]);
+}
+
+#[tokio::test]
+async fn call_handler_options_are_in_the_transaction() {
+    abigen!(
+        SimpleContract,
+        "packages/fuels-abigen-macro/tests/takes_ints_returns_bool.json",
+    );
+
+    let (provider, wallet) = setup_test_provider_and_wallet().await;
+    let contract_instance = SimpleContract::new(null_contract_id(), provider, wallet);
+
+    let external_contract = ContractId::from([1u8; 32]);
+    let tx = contract_instance
+        .takes_ints_returns_bool(42)
+        .tx_params(TxParameters::new(Some(3), Some(500_000), Some(2), None))
+        .call_params(CallParameters::new(Some(100), None))
+        .append_variable_outputs(2)
+        .set_contracts(&[external_contract])
+        .build_transaction()
+        .await
+        .unwrap();
+
+    match tx {
+        Transaction::Script {
+            gas_price,
+            gas_limit,
+            byte_price,
+            script_data,
+            inputs,
+            outputs,
+            ..
+        } => {
+            assert_eq!((gas_price, gas_limit, byte_price), (3, 500_000, 2));
+
+            // The forwarded amount comes right after the forwarded asset id
+            assert_eq!(script_data[32..40], 100u64.to_be_bytes());
+
+            let variable_outputs = outputs
+                .iter()
+                .filter(|output| matches!(output, Output::Variable { .. }))
+                .count();
+            assert_eq!(variable_outputs, 2);
+
+            assert!(inputs.iter().any(|input| matches!(
+                input,
+                Input::Contract { contract_id, .. } if *contract_id == external_contract
+            )));
+        }
+        _ => panic!("a contract call is a script transaction"),
+    }
+}
+
 #[tokio::test]
 async fn compile_bindings_array_input() {
     // Generates the bindings from the an ABI definition inline.
@@ -250,18 +657,29 @@ async fn compile_bindings_string_input() {
     // `SimpleContract` is the name of the contract
     let contract_instance = SimpleContract::new(null_contract_id(), provider, wallet);
 
-    let contract_call = contract_instance.takes_string("This is a full sentence".into());
-
-    let encoded = format!(
-        "{}{}",
-        hex::encode(contract_call.encoded_selector),
-        hex::encode(contract_call.encoded_args)
-    );
-
-    assert_eq!(
-        "00000000d56e76515468697320697320612066756c6c2073656e74656e636500",
-        encoded
-    );
+    // Both borrowed and owned strings are accepted
+    let borrowed = contract_instance.takes_string("This is a full sentence");
+    let owned = contract_instance.takes_string(String::from("This is a full sentence"));
+
+    for contract_call in [borrowed, owned] {
+        let encoded = format!(
+            "{}{}",
+            hex::encode(contract_call.encoded_selector),
+            hex::encode(contract_call.encoded_args)
+        );
+
+        assert_eq!(
+            "00000000d56e76515468697320697320612066756c6c2073656e74656e636500",
+            encoded
+        );
+    }
+
+    // A string that isn't as long as its `str[23]` type is rejected, rather than panicking
+    let result = contract_instance.takes_string("Too short").call().await;
+    assert!(matches!(result, Err(Error::InvalidType(_))));
+
+    let result = contract_instance.takes_string_calldata("Too short");
+    assert!(matches!(result, Err(Error::InvalidType(_))));
 }
 
 #[tokio::test]
@@ -433,6 +851,47 @@ async fn compile_bindings_nested_struct_input() {
     assert_eq!("0000000088bf8a1b000000000000000a0000000000000001", encoded);
 }
 
+#[tokio::test]
+async fn read_only_functions_default_to_simulation() {
+    abigen!(
+        SimpleContract,
+        r#"
+        [
+            {
+                "type":"function",
+                "inputs":[],
+                "name":"get_counter",
+                "outputs":[
+                    {
+                        "name":"",
+                        "type":"u64"
+                    }
+                ],
+                "attributes":["storage(read)"]
+            },
+            {
+                "type":"function",
+                "inputs":[
+                    {
+                        "name":"value",
+                        "type":"u64"
+                    }
+                ],
+                "name":"set_counter",
+                "outputs":[],
+                "attributes":["storage(write)"]
+            }
+        ]
+        "#,
+    );
+
+    let (provider, wallet) = setup_test_provider_and_wallet().await;
+    let contract_instance = SimpleContract::new(null_contract_id(), provider, wallet);
+
+    assert!(contract_instance.get_counter().read_only);
+    assert!(!contract_instance.set_counter(42).read_only);
+}
+
 #[tokio::test]
 async fn compile_bindings_enum_input() {
     // Generates the bindings from the an ABI definition inline.
@@ -544,10 +1003,11 @@ async fn create_struct_from_decoded_tokens() {
     assert_eq!("00000000cb0b2f05000000000000000a0000000000000001", encoded);
 }
 
-#[tokio::test]
-async fn create_nested_struct_from_decoded_tokens() {
-    // Generates the bindings from the an ABI definition inline.
-    // The generated bindings can be accessed through `SimpleContract`.
+#[test]
+fn generated_types_are_hashable() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
     abigen!(
         SimpleContract,
         r#"
@@ -556,13 +1016,261 @@ async fn create_nested_struct_from_decoded_tokens() {
                 "type":"contract",
                 "inputs":[
                     {
-                        "name":"input",
-                        "type":"struct MyNestedStruct",
+                        "name":"my_val",
+                        "type":"struct MyStruct",
                         "components": [
                             {
-                                "name": "x",
-                                "type": "u16"
-                            },
+                                "name": "foo",
+                                "type": "u8"
+                            },
+                            {
+                                "name": "bar",
+                                "type": "bool"
+                            }
+                        ]
+                    },
+                    {
+                        "name":"my_enum",
+                        "type":"enum MyEnum",
+                        "components": [
+                            {
+                                "name": "x",
+                                "type": "u32"
+                            },
+                            {
+                                "name": "y",
+                                "type": "bool"
+                            }
+                        ]
+                    }
+                ],
+                "name":"takes_struct_and_enum",
+                "outputs":[]
+            }
+        ]
+        "#,
+    );
+
+    fn hash_of<T: Hash>(t: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        t.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    let first = MyStruct { foo: 10, bar: true };
+    let second = MyStruct { foo: 10, bar: true };
+
+    assert_eq!(first, second);
+    assert_eq!(hash_of(&first), hash_of(&second));
+
+    let first = MyEnum::X(42);
+    let second = MyEnum::X(42);
+
+    assert_eq!(first, second);
+    assert_eq!(hash_of(&first), hash_of(&second));
+}
+
+#[test]
+fn build_struct_with_generated_builder() {
+    abigen!(
+        SimpleContract,
+        r#"
+        [
+            {
+                "type":"contract",
+                "inputs":[
+                    {
+                        "name":"my_val",
+                        "type":"struct MyStruct",
+                        "components": [
+                            {
+                                "name": "foo",
+                                "type": "u8"
+                            },
+                            {
+                                "name": "bar",
+                                "type": "bool"
+                            }
+                        ]
+                    }
+                ],
+                "name":"takes_struct",
+                "outputs":[]
+            }
+        ]
+        "#,
+    );
+
+    let built = MyStruct::builder().foo(10).bar(true).build().unwrap();
+
+    assert_eq!(built, MyStruct { foo: 10, bar: true });
+
+    let err = MyStruct::builder().foo(10).build().unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "Missing data: Field `bar` of `MyStruct` wasn't set"
+    );
+}
+
+#[test]
+fn struct_try_from_token() {
+    abigen!(
+        SimpleContract,
+        r#"
+        [
+            {
+                "type":"contract",
+                "inputs":[
+                    {
+                        "name":"my_val",
+                        "type":"struct MyStruct",
+                        "components": [
+                            {
+                                "name": "foo",
+                                "type": "u8"
+                            },
+                            {
+                                "name": "bar",
+                                "type": "bool"
+                            }
+                        ]
+                    }
+                ],
+                "name":"takes_struct",
+                "outputs":[]
+            }
+        ]
+        "#,
+    );
+
+    let token = Token::Struct(vec![Token::U8(10), Token::Bool(true)]);
+    let my_struct = MyStruct::try_from(token).unwrap();
+
+    assert_eq!(my_struct, MyStruct { foo: 10, bar: true });
+
+    // Wrong arity
+    let token = Token::Struct(vec![Token::U8(10)]);
+    assert!(matches!(
+        MyStruct::try_from(token),
+        Err(Error::InvalidType(_))
+    ));
+
+    // Wrong inner type
+    let token = Token::Struct(vec![Token::U8(10), Token::U64(1)]);
+    assert!(matches!(
+        MyStruct::try_from(token),
+        Err(Error::InvalidType(_))
+    ));
+
+    // Not a struct
+    assert!(matches!(
+        MyStruct::try_from(Token::U8(10)),
+        Err(Error::InvalidType(_))
+    ));
+}
+
+#[test]
+fn enum_variant_index_by_name() {
+    abigen!(
+        SimpleContract,
+        r#"
+        [
+            {
+                "type":"contract",
+                "inputs":[
+                    {
+                        "name":"my_enum",
+                        "type":"enum Cocktail",
+                        "components": [
+                            {
+                                "name": "long_island",
+                                "type": "u32"
+                            },
+                            {
+                                "name": "mojito",
+                                "type": "bool"
+                            }
+                        ]
+                    }
+                ],
+                "name":"takes_enum",
+                "outputs":[]
+            }
+        ]
+        "#,
+    );
+
+    assert_eq!(Cocktail::variant_index("LongIsland"), Some(0));
+    assert_eq!(Cocktail::variant_index("Mojito"), Some(1));
+    assert_eq!(Cocktail::variant_index("Margarita"), None);
+
+    // The index is the discriminant of the variant's token
+    let token = Cocktail::Mojito(true).into_token();
+    assert!(matches!(token, Token::Enum(selector) if selector.0 == 1));
+}
+
+#[test]
+fn enum_from_discriminant_and_payload() {
+    abigen!(
+        SimpleContract,
+        r#"
+        [
+            {
+                "type":"contract",
+                "inputs":[
+                    {
+                        "name":"my_enum",
+                        "type":"enum Cocktail",
+                        "components": [
+                            {
+                                "name": "long_island",
+                                "type": "u32"
+                            },
+                            {
+                                "name": "mojito",
+                                "type": "bool"
+                            }
+                        ]
+                    }
+                ],
+                "name":"takes_enum",
+                "outputs":[]
+            }
+        ]
+        "#,
+    );
+
+    assert_eq!(Cocktail::LongIsland(7).discriminant(), 0);
+    assert_eq!(Cocktail::Mojito(true).discriminant(), 1);
+
+    let cocktail = Cocktail::from_discriminant(1, Token::Bool(true)).unwrap();
+    assert_eq!(cocktail, Cocktail::Mojito(true));
+
+    // The payload must match the variant's type
+    assert!(Cocktail::from_discriminant(1, Token::U32(7)).is_err());
+    assert!(Cocktail::from_discriminant(2, Token::Bool(true)).is_err());
+}
+
+#[tokio::test]
+async fn create_nested_struct_from_decoded_tokens() {
+    // Generates the bindings from the an ABI definition inline.
+    // The generated bindings can be accessed through `SimpleContract`.
+    abigen!(
+        SimpleContract,
+        r#"
+        [
+            {
+                "type":"contract",
+                "inputs":[
+                    {
+                        "name":"input",
+                        "type":"struct MyNestedStruct",
+                        "components": [
+                            {
+                                "name": "x",
+                                "type": "u16"
+                            },
                             {
                                 "name": "y",
                                 "type": "struct InnerStruct",
@@ -667,6 +1375,271 @@ async fn example_workflow() {
     assert_eq!(52, result.value);
 }
 
+#[tokio::test]
+async fn contract_handle_with_invalid_id() {
+    abigen!(
+        MyContract,
+        "packages/fuels-abigen-macro/tests/test_projects/contract_test/out/debug/contract_test-abi.json"
+    );
+
+    let (provider, wallet) = setup_test_provider_and_wallet().await;
+
+    let result = MyContract::try_new("0xnot_an_id".to_string(), provider.clone(), wallet.clone());
+    assert!(matches!(result, Err(Error::InvalidAddress(_))));
+
+    assert!(MyContract::try_new(null_contract_id(), provider, wallet).is_ok());
+}
+
+#[tokio::test]
+async fn deploy_with_test_helper() {
+    abigen!(
+        MyContract,
+        "packages/fuels-abigen-macro/tests/test_projects/contract_test/out/debug/contract_test-abi.json"
+    );
+
+    let contract_instance = launch_provider_and_deploy(
+        "tests/test_projects/contract_test/out/debug/contract_test.bin",
+        MyContract::new,
+    )
+    .await;
+
+    let result = contract_instance
+        .initialize_counter(42)
+        .call()
+        .await
+        .unwrap();
+
+    assert_eq!(42, result.value);
+}
+
+#[tokio::test]
+async fn call_with_logs_uses_the_abi_logged_types() {
+    use fuels_rs::core::ParamType;
+
+    // The ABI of `contract_test`'s `initialize_counter`, declaring a logged `u64`
+    abigen!(
+        MyContract,
+        r#"
+        {
+            "types": [
+                { "typeId": 0, "type": "u64", "components": null, "typeParameters": null }
+            ],
+            "functions": [
+                {
+                    "inputs": [{ "name": "value", "type": 0, "typeArguments": null }],
+                    "name": "initialize_counter",
+                    "output": { "name": "", "type": 0, "typeArguments": null }
+                }
+            ],
+            "loggedTypes": [
+                { "logId": 0, "loggedType": { "name": "", "type": 0, "typeArguments": null } }
+            ]
+        }
+        "#,
+    );
+    assert_eq!(MyContract::log_types(), vec![(0, ParamType::U64)]);
+
+    let contract_instance = launch_provider_and_deploy(
+        "tests/test_projects/contract_test/out/debug/contract_test.bin",
+        MyContract::new,
+    )
+    .await;
+
+    // The contract doesn't log anything
+    let (value, logs) = contract_instance
+        .initialize_counter(42)
+        .call_with_logs::<u64>(0)
+        .await
+        .unwrap();
+    assert_eq!(value, 42);
+    assert!(logs.is_empty());
+
+    let result = contract_instance
+        .initialize_counter(42)
+        .call_with_logs::<u64>(1)
+        .await;
+    assert!(matches!(result, Err(Error::MissingData(_))));
+}
+
+#[tokio::test]
+async fn contract_balance() {
+    abigen!(
+        MyContract,
+        "packages/fuels-abigen-macro/tests/test_projects/contract_test/out/debug/contract_test-abi.json"
+    );
+
+    let contract_instance = launch_provider_and_deploy(
+        "tests/test_projects/contract_test/out/debug/contract_test.bin",
+        MyContract::new,
+    )
+    .await;
+    assert_eq!(
+        contract_instance
+            .get_balance(NATIVE_ASSET_ID)
+            .await
+            .unwrap(),
+        0
+    );
+
+    // Forward coins of the native asset with the call
+    let tx_params = TxParameters::new(None, Some(1_000_000), None, None);
+    let call_params = CallParameters::new(Some(100), None);
+    contract_instance
+        .initialize_counter(42)
+        .tx_params(tx_params)
+        .call_params(call_params)
+        .call()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        contract_instance
+            .get_balance(NATIVE_ASSET_ID)
+            .await
+            .unwrap(),
+        100
+    );
+}
+
+/// Signer delegating to a wallet, standing in for e.g. a hardware wallet
+#[derive(Debug, Clone)]
+struct CountingSigner {
+    wallet: LocalWallet,
+    signatures: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl Signer for CountingSigner {
+    type Error = WalletError;
+
+    async fn sign_message<S: Send + Sync + AsRef<[u8]>>(
+        &self,
+        message: S,
+    ) -> Result<Signature, Self::Error> {
+        self.signatures.fetch_add(1, Ordering::SeqCst);
+        self.wallet.sign_message(message).await
+    }
+
+    async fn sign_transaction(&self, tx: &mut Transaction) -> Result<Signature, Self::Error> {
+        self.signatures.fetch_add(1, Ordering::SeqCst);
+        self.wallet.sign_transaction(tx).await
+    }
+
+    fn address(&self) -> Address {
+        self.wallet.address()
+    }
+}
+
+#[tokio::test]
+async fn call_with_custom_signer() {
+    abigen!(
+        MyContract,
+        "packages/fuels-abigen-macro/tests/test_projects/contract_test/out/debug/contract_test-abi.json"
+    );
+
+    let (contract_instance, signer) = launch_provider_and_deploy(
+        "tests/test_projects/contract_test/out/debug/contract_test.bin",
+        |contract_id, provider, wallet| {
+            let signer = CountingSigner {
+                wallet,
+                signatures: Arc::new(AtomicUsize::new(0)),
+            };
+            let contract_instance = MyContract::new(contract_id, provider, signer.clone());
+            (contract_instance, signer)
+        },
+    )
+    .await;
+
+    let result = contract_instance
+        .initialize_counter(42)
+        .call()
+        .await
+        .unwrap();
+
+    assert_eq!(42, result.value);
+    assert_eq!(signer.signatures.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn chain_calls_in_a_session() {
+    abigen!(
+        MyContract,
+        "packages/fuels-abigen-macro/tests/test_projects/contract_test/out/debug/contract_test-abi.json"
+    );
+
+    let (contract_instance, wallet) = launch_provider_and_deploy(
+        "tests/test_projects/contract_test/out/debug/contract_test.bin",
+        |contract_id, provider, wallet| {
+            (
+                MyContract::new(contract_id, provider, wallet.clone()),
+                wallet,
+            )
+        },
+    )
+    .await;
+    let mut session = ContractSession::new(wallet.clone());
+
+    let result = session
+        .call(contract_instance.initialize_counter(42))
+        .await
+        .unwrap();
+    assert_eq!(42, result.value);
+
+    let result = session
+        .call(contract_instance.increment_counter(10))
+        .await
+        .unwrap();
+    assert_eq!(52, result.value);
+
+    let result = session
+        .call(contract_instance.increment_counter(10))
+        .await
+        .unwrap();
+    assert_eq!(62, result.value);
+
+    // Only the first call had to look for coins
+    assert_eq!(session.coin_queries(), 1);
+
+    // Spending the chained coin outside of the session makes it query the coins again
+    wallet
+        .transfer(&wallet.address(), 1, Default::default())
+        .await
+        .unwrap();
+
+    let result = session
+        .call(contract_instance.increment_counter(10))
+        .await
+        .unwrap();
+    assert_eq!(72, result.value);
+    assert_eq!(session.coin_queries(), 2);
+}
+
+#[tokio::test]
+async fn spend_predicate_coins() {
+    // A predicate that always returns true
+    let predicate = Predicate::from_code(Opcode::RET(REG_ONE).to_bytes().to_vec());
+
+    let (provider, wallet) = setup_test_provider_and_wallet().await;
+
+    wallet
+        .transfer(&predicate.address(), 10, AssetId::default())
+        .await
+        .unwrap();
+    let balances = provider.get_balances(&predicate.address()).await.unwrap();
+    assert_eq!(balances.get(&AssetId::default()), Some(&10));
+
+    let receiver = Address::from([1u8; 32]);
+    predicate
+        .spend(&provider, vec![], &receiver, 4, AssetId::default())
+        .await
+        .unwrap();
+
+    let balances = provider.get_balances(&receiver).await.unwrap();
+    assert_eq!(balances.get(&AssetId::default()), Some(&4));
+    let balances = provider.get_balances(&predicate.address()).await.unwrap();
+    assert_eq!(balances.get(&AssetId::default()), Some(&6));
+}
+
 // TODO https://github.com/FuelLabs/fuels-rs/issues/201
 #[tokio::test]
 #[ignore]
@@ -802,11 +1775,14 @@ async fn call_with_empty_return() {
 
     let contract_instance = MyContract::new(contract_id.to_string(), provider, wallet);
 
-    let _result = contract_instance
+    let result = contract_instance
         .store_value(42) // Build the ABI call
         .call() // Perform the network call
         .await
         .unwrap();
+
+    // There's no return value to decode
+    let () = result.value;
 }
 
 #[tokio::test]
@@ -917,6 +1893,45 @@ async fn multiple_read_calls() {
     assert_eq!(stored.value, 42);
 }
 
+#[tokio::test]
+async fn simulate_at_height() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    abigen!(
+        MyContract,
+        "packages/fuels-abigen-macro/tests/test_projects/multiple_read_calls/out/debug/demo-abi.json"
+    );
+
+    let salt: [u8; 32] = rng.gen();
+    let salt = Salt::from(salt);
+    let compiled = Contract::load_sway_contract(
+        "tests/test_projects/multiple_read_calls/out/debug/demo.bin",
+        salt,
+    )
+    .unwrap();
+
+    let (provider, wallet) = setup_test_provider_and_wallet().await;
+    let contract_id = Contract::deploy(&compiled, &provider, &wallet, TxParameters::default())
+        .await
+        .unwrap();
+    let contract_instance = MyContract::new(contract_id.to_string(), provider.clone(), wallet);
+
+    contract_instance.store(42).call().await.unwrap();
+
+    // The node simulates calls as part of the block it produces next
+    let latest = provider.chain_info().await.unwrap().latest_block.height.0;
+    let stored = contract_instance
+        .read(0)
+        .at_height(latest + 1)
+        .simulate()
+        .await
+        .unwrap();
+    assert_eq!(stored.value, 42);
+
+    // It can't simulate them at past blocks, e.g. before the value was stored
+    let result = contract_instance.read(0).at_height(latest).simulate().await;
+    assert!(matches!(result, Err(Error::Unsupported(_))));
+}
+
 #[tokio::test]
 async fn test_methods_typeless_argument() {
     let rng = &mut StdRng::seed_from_u64(2322u64);
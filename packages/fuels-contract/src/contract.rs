@@ -4,7 +4,6 @@ use crate::parameters::{CallParameters, TxParameters};
 use crate::script::Script;
 use anyhow::Result;
 use fuel_asm::Opcode;
-use fuel_gql_client::client::FuelClient;
 use fuel_tx::{
     Address, AssetId, ContractId, Input, Output, Receipt, StorageSlot, Transaction, UtxoId,
 };
@@ -12,9 +11,9 @@ use fuel_types::{Bytes32, Salt, Word};
 use fuel_vm::consts::{REG_CGAS, REG_ONE};
 use fuel_vm::prelude::Contract as FuelContract;
 use fuel_vm::script_with_data_offset;
-use fuels_core::errors::Error;
+use fuels_core::errors::{decode_panic, Error};
 use fuels_core::{
-    constants::DEFAULT_COIN_AMOUNT, constants::WORD_SIZE, Detokenize, Selector, Token,
+    constants::DEFAULT_COIN_AMOUNT, constants::WORD_SIZE, Detokenize, Selector, Token, Tokenizable,
 };
 use fuels_core::{constants::NATIVE_ASSET_ID, ParamType};
 use fuels_signers::provider::Provider;
@@ -45,6 +44,22 @@ pub struct CallResponse<D> {
     pub receipts: Vec<Receipt>,
 }
 
+/// What a contract call did, as reported by its receipts, see `Contract::summarize`.
+/// Values are left undecoded: a word is a `Token::U64` and data is a `Token::Array` of
+/// `Token::Byte`s, as the receipts don't carry their types.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CallSummary {
+    /// The value returned by the first `Return` or `ReturnData` receipt.
+    pub return_value: Option<Token>,
+    /// The values logged with `Log` (its first register) or `LogData` receipts.
+    pub logs: Vec<Token>,
+    /// The recipient, amount and asset of each transfer. Contracts that received coins
+    /// are identified by their id, as an `Address`.
+    pub transferred: Vec<(Address, u64, AssetId)>,
+    /// Whether the call reverted or panicked.
+    pub reverted: bool,
+}
+
 impl Contract {
     pub fn new(compiled_contract: CompiledContract, wallet: LocalWallet) -> Self {
         Self {
@@ -157,22 +172,69 @@ impl Contract {
     }
 
     /// Calls a contract method with the given ABI function.
-    /// The wallet is here to pay for the transaction fees (even though they are 0 right now)
+    /// The wallet is here to pay for the transaction fees (even though they are 0 right now).
+    /// If `base_coins` is set, they're spent instead of the wallet's spendable coins of the
+    /// base asset. Returns the receipts along with the id of the transaction.
     #[allow(clippy::too_many_arguments)] // We need that many arguments for now
-    async fn call(
+    async fn call<S: Signer>(
         contract_id: ContractId,
         encoded_selector: Option<Selector>,
         encoded_args: Option<Vec<u8>>,
-        fuel_client: &FuelClient,
+        provider: &Provider,
         tx_parameters: TxParameters,
         call_parameters: CallParameters,
         variable_outputs: Option<Vec<Output>>,
         maturity: Word,
         compute_calldata_offset: bool,
         external_contracts: Option<Vec<ContractId>>,
-        wallet: LocalWallet,
+        wallet: S,
         simulate: bool,
-    ) -> Result<Vec<Receipt>, Error> {
+        simulated_height: Option<u64>,
+        base_coins: Option<Vec<Input>>,
+    ) -> Result<(Vec<Receipt>, Bytes32), Error> {
+        let tx = Self::build_call_tx(
+            contract_id,
+            encoded_selector,
+            encoded_args,
+            provider,
+            tx_parameters,
+            call_parameters,
+            variable_outputs,
+            maturity,
+            compute_calldata_offset,
+            external_contracts,
+            &wallet,
+            base_coins,
+        )
+        .await?;
+        let tx_id = tx.id();
+
+        let script = Script::new(tx);
+
+        let receipts = match (simulate, simulated_height) {
+            (true, Some(height)) => script.simulate_at_height(provider, height).await?,
+            (true, None) => script.simulate(provider).await?,
+            (false, _) => script.call(provider).await?,
+        };
+        Ok((receipts, tx_id))
+    }
+
+    /// Builds and signs the transaction calling a contract method, see `call`.
+    #[allow(clippy::too_many_arguments)] // We need that many arguments for now
+    async fn build_call_tx<S: Signer>(
+        contract_id: ContractId,
+        encoded_selector: Option<Selector>,
+        encoded_args: Option<Vec<u8>>,
+        provider: &Provider,
+        tx_parameters: TxParameters,
+        call_parameters: CallParameters,
+        variable_outputs: Option<Vec<Output>>,
+        maturity: Word,
+        compute_calldata_offset: bool,
+        external_contracts: Option<Vec<ContractId>>,
+        wallet: &S,
+        base_coins: Option<Vec<Input>>,
+    ) -> Result<Transaction, Error> {
         let (script, script_data) = Self::build_script(
             &contract_id,
             &encoded_selector,
@@ -191,22 +253,28 @@ impl Contract {
         );
         inputs.push(self_contract_input);
 
-        let mut spendables = wallet
-            .get_spendable_coins(&AssetId::default(), DEFAULT_COIN_AMOUNT as u64)
-            .await
-            .unwrap();
+        let base_coins = match base_coins {
+            Some(coins) => coins,
+            None => Self::get_base_coins(provider, wallet).await?,
+        };
 
         // add default asset change if any inputs are being spent
-        if !spendables.is_empty() {
+        if !base_coins.is_empty() {
             let change_output = Output::change(wallet.address(), 0, AssetId::default());
             outputs.push(change_output);
         }
+        inputs.extend(base_coins);
+
+        let mut spendables = vec![];
 
         if call_parameters.asset_id != AssetId::default() {
-            let alt_spendables = wallet
-                .get_spendable_coins(&call_parameters.asset_id, call_parameters.amount)
-                .await
-                .unwrap();
+            let alt_spendables = provider
+                .get_spendable_coins(
+                    &wallet.address(),
+                    call_parameters.asset_id,
+                    call_parameters.amount,
+                )
+                .await?;
 
             // add alt change if inputs are being spent
             if !alt_spendables.is_empty() {
@@ -277,14 +345,43 @@ impl Contract {
             outputs,
             vec![],
         );
-        wallet.sign_transaction(&mut tx).await?;
+        wallet
+            .sign_transaction(&mut tx)
+            .await
+            .map_err(|e| Error::WalletError(e.to_string()))?;
 
-        let script = Script::new(tx);
+        Ok(tx)
+    }
 
-        if simulate {
-            return script.simulate(fuel_client).await;
-        }
-        script.call(fuel_client).await
+    /// Returns inputs for the spendable coins of `wallet` in the base asset, used to pay for
+    /// contract calls.
+    pub(crate) async fn get_base_coins<S: Signer>(
+        provider: &Provider,
+        wallet: &S,
+    ) -> Result<Vec<Input>, Error> {
+        let coins = provider
+            .get_spendable_coins(
+                &wallet.address(),
+                AssetId::default(),
+                DEFAULT_COIN_AMOUNT as u64,
+            )
+            .await?;
+
+        Ok(coins
+            .into_iter()
+            .map(|coin| {
+                Input::coin(
+                    UtxoId::from(coin.utxo_id),
+                    coin.owner.into(),
+                    coin.amount.0,
+                    coin.asset_id.into(),
+                    0,
+                    0,
+                    vec![],
+                    vec![],
+                )
+            })
+            .collect())
     }
 
     /// Creates an ABI call based on a function selector and
@@ -296,19 +393,22 @@ impl Contract {
     /// quote! {
     ///     #doc
     ///     pub fn #name(&self #input) -> #result {
-    ///         Contract::method_hash(#tokenized_signature, #arg)
+    ///         Contract::method_call(#tokenized_signature, #arg)
     ///     }
     /// }
-    /// For more details see `code_gen/functions_gen.rs`.
-    /// Note that this needs a wallet because the contract instance needs a wallet for the calls
-    pub fn method_hash<D: Detokenize>(
+    /// where `method_call` calls this method. For more details see
+    /// `code_gen/functions_gen.rs`.
+    /// Note that this needs a wallet because the contract instance needs a wallet for the calls.
+    /// Any `Signer` can be used, e.g. a hardware wallet, as long as it can be cloned into
+    /// the call.
+    pub fn method_hash<D: Detokenize, S: Signer + Clone>(
         provider: &Provider,
         contract_id: ContractId,
-        wallet: &LocalWallet,
+        wallet: &S,
         signature: Selector,
         output_params: &[ParamType],
         args: &[Token],
-    ) -> Result<ContractCall<D>, Error> {
+    ) -> Result<ContractCall<D, S>, Error> {
         let mut encoder = ABIEncoder::new();
 
         let encoded_args = encoder.encode(args).unwrap();
@@ -327,22 +427,168 @@ impl Contract {
             call_parameters,
             maturity,
             encoded_selector,
-            fuel_client: provider.client.clone(),
+            provider: provider.clone(),
             datatype: PhantomData,
             output_params: output_params.to_vec(),
             variable_outputs: None,
             compute_calldata_offset,
             external_contracts: None,
             wallet: wallet.clone(),
+            read_only: false,
+            invalid_args: None,
+            log_types: vec![],
+            simulated_height: None,
         })
     }
 
+    /// Same as `method_hash`, with arguments that may have failed to tokenize, e.g. a
+    /// string that isn't as long as its `str[n]` type. The error is then returned when
+    /// the call is sent, so that generated methods don't panic.
+    pub fn method_call<D: Detokenize, S: Signer + Clone>(
+        provider: &Provider,
+        contract_id: ContractId,
+        wallet: &S,
+        signature: Selector,
+        output_params: &[ParamType],
+        args: Result<Vec<Token>, Error>,
+    ) -> ContractCall<D, S> {
+        let (args, invalid_args) = match args {
+            Ok(args) => (args, None),
+            Err(Error::InvalidType(reason)) => (vec![], Some(reason)),
+            Err(e) => (vec![], Some(e.to_string())),
+        };
+
+        let mut call = Self::method_hash(
+            provider,
+            contract_id,
+            wallet,
+            signature,
+            output_params,
+            &args,
+        )
+        .expect("method not found (this should never happen)");
+        call.invalid_args = invalid_args;
+        call
+    }
+
+    /// Encodes a call to a contract's method without building a transaction: its selector
+    /// followed by its encoded arguments, e.g. to compose it into another script.
+    /// Generated bindings expose it as a `<method>_calldata` function.
+    pub fn calldata(signature: Selector, args: &[Token]) -> Result<Vec<u8>, Error> {
+        let mut calldata = signature.to_vec();
+        calldata.extend(ABIEncoder::new().encode(args)?);
+        Ok(calldata)
+    }
+
+    /// Decodes the value returned by a contract's method from the receipts of its call,
+    /// e.g. after running a script directly. The value is read from the `ReturnData`
+    /// receipt, or from the `Return` receipt if it fits in a single word.
+    pub fn decode_return<D: Detokenize>(
+        receipts: &[Receipt],
+        output_params: &[ParamType],
+    ) -> Result<D, Error> {
+        let return_data = receipts.iter().find_map(|r| match r {
+            Receipt::ReturnData { data, .. } => Some(data.clone()),
+            _ => None,
+        });
+        let fits_in_word = output_params.len() == 1 && !output_params[0].bigger_than_word();
+
+        let encoded_value = match return_data {
+            Some(data) => data,
+            None if fits_in_word => match receipts.iter().find_map(Receipt::val) {
+                Some(val) => val.to_be_bytes().to_vec(),
+                None => return Err(Error::MissingData("no `Return` receipt".to_string())),
+            },
+            None => return Err(Error::MissingData("no `ReturnData` receipt".to_string())),
+        };
+
+        let tokens = ABIDecoder::new().decode(output_params, &encoded_value)?;
+        Ok(D::from_tokens(tokens)?)
+    }
+
+    /// Decodes the values logged by the contract `contract_id` under `log_id` during a
+    /// call as `log_type`, which the ABI's `loggedTypes` declare for `log_id`. The log id
+    /// is in the `rb` register of the receipts. Values that fit in a word are read from
+    /// `Log` receipts, bigger ones from `LogData` receipts. Fails if one of them isn't a
+    /// valid `log_type`.
+    pub fn decode_logs<L: Tokenizable>(
+        receipts: &[Receipt],
+        contract_id: &ContractId,
+        log_id: u64,
+        log_type: &ParamType,
+    ) -> Result<Vec<L>, Error> {
+        let log_types = [log_type.clone()];
+        receipts
+            .iter()
+            .filter_map(|receipt| match receipt {
+                Receipt::Log { id, ra, rb, .. }
+                    if id == contract_id && *rb == log_id && !log_type.bigger_than_word() =>
+                {
+                    Some(ra.to_be_bytes().to_vec())
+                }
+                Receipt::LogData { id, rb, data, .. }
+                    if id == contract_id && *rb == log_id && log_type.bigger_than_word() =>
+                {
+                    Some(data.clone())
+                }
+                _ => None,
+            })
+            .map(|data| {
+                let mut tokens = ABIDecoder::new().decode(&log_types, &data)?;
+                L::from_token(tokens.remove(0)).map_err(Error::from)
+            })
+            .collect()
+    }
+
+    /// Summarizes what a call did from its receipts: its return value, logs, transfers
+    /// and whether it reverted, without matching on every kind of receipt.
+    pub fn summarize(receipts: &[Receipt]) -> CallSummary {
+        let bytes = |data: &[u8]| Token::Array(data.iter().copied().map(Token::Byte).collect());
+
+        let mut summary = CallSummary::default();
+        for receipt in receipts {
+            match receipt {
+                Receipt::Return { val, .. } if summary.return_value.is_none() => {
+                    summary.return_value = Some(Token::U64(*val));
+                }
+                Receipt::ReturnData { data, .. } if summary.return_value.is_none() => {
+                    summary.return_value = Some(bytes(data));
+                }
+                Receipt::Log { ra, .. } => summary.logs.push(Token::U64(*ra)),
+                Receipt::LogData { data, .. } => summary.logs.push(bytes(data)),
+                Receipt::Transfer {
+                    to,
+                    amount,
+                    asset_id,
+                    ..
+                } => summary
+                    .transferred
+                    .push((Address::new(**to), *amount, *asset_id)),
+                Receipt::TransferOut {
+                    to,
+                    amount,
+                    asset_id,
+                    ..
+                } => summary.transferred.push((*to, *amount, *asset_id)),
+                Receipt::Revert { .. } | Receipt::Panic { .. } => summary.reverted = true,
+                _ => {}
+            }
+        }
+
+        summary
+    }
+
     // Returns true if the method call takes custom inputs or has more than one argument. This is used to determine whether we need to compute the `call_data_offset`.
     fn should_compute_call_data_offset(args: &[Token]) -> bool {
         match args.iter().any(|t| {
             matches!(
                 t,
-                Token::Struct(_) | Token::Enum(_) | Token::B256(_) | Token::Tuple(_)
+                Token::Struct(_)
+                    | Token::Enum(_)
+                    | Token::B256(_)
+                    | Token::Tuple(_)
+                    | Token::DynamicString(_)
+                    | Token::Vector(_)
             )
         }) {
             true => true,
@@ -363,7 +609,7 @@ impl Contract {
             Self::contract_deployment_transaction(compiled_contract, wallet, params).await?;
         wallet.sign_transaction(&mut tx).await?;
 
-        match provider.client.submit(&tx).await {
+        match provider.submit_transaction(&tx).await {
             Ok(_) => Ok(contract_id),
             Err(e) => Err(Error::TransactionError(e.to_string())),
         }
@@ -430,9 +676,10 @@ impl Contract {
 
 #[derive(Debug)]
 #[must_use = "contract calls do nothing unless you `call` them"]
-/// Helper for managing a transaction before submitting it to a node
-pub struct ContractCall<D> {
-    pub fuel_client: FuelClient,
+/// Helper for managing a transaction before submitting it to a node.
+/// The transaction is paid for and signed by `wallet`, a `LocalWallet` by default.
+pub struct ContractCall<D, S = LocalWallet> {
+    pub provider: Provider,
     pub encoded_args: Vec<u8>,
     pub encoded_selector: Selector,
     pub contract_id: ContractId,
@@ -442,14 +689,53 @@ pub struct ContractCall<D> {
     pub datatype: PhantomData<D>,
     pub output_params: Vec<ParamType>,
     pub compute_calldata_offset: bool,
-    pub wallet: LocalWallet,
+    pub wallet: S,
     pub variable_outputs: Option<Vec<Output>>,
     external_contracts: Option<Vec<ContractId>>,
+    /// Whether `run` simulates the call rather than submitting it.
+    pub read_only: bool,
+    /// Why the arguments couldn't be tokenized, see `Contract::method_call`.
+    invalid_args: Option<String>,
+    /// Types of the values the contract logs, by log id, see `with_log_types`.
+    pub log_types: Vec<(u64, ParamType)>,
+    /// Height of the block simulations of the call run at, see `at_height`.
+    pub simulated_height: Option<u64>,
 }
 
-impl<D> ContractCall<D>
+/// What the methods generated by `abigen!` return: a call to configure with chainable
+/// methods such as `tx_params`, `call_params` or `append_variable_outputs`, then send
+/// with `call`, `simulate` or `run`.
+pub type ContractCallHandler<D, S = LocalWallet> = ContractCall<D, S>;
+
+// Implemented by hand since deriving it would require `D: Clone`
+impl<D, S: Clone> Clone for ContractCall<D, S> {
+    fn clone(&self) -> Self {
+        Self {
+            provider: self.provider.clone(),
+            encoded_args: self.encoded_args.clone(),
+            encoded_selector: self.encoded_selector,
+            contract_id: self.contract_id,
+            tx_parameters: self.tx_parameters.clone(),
+            call_parameters: self.call_parameters.clone(),
+            maturity: self.maturity,
+            datatype: PhantomData,
+            output_params: self.output_params.clone(),
+            compute_calldata_offset: self.compute_calldata_offset,
+            wallet: self.wallet.clone(),
+            variable_outputs: self.variable_outputs.clone(),
+            external_contracts: self.external_contracts.clone(),
+            read_only: self.read_only,
+            invalid_args: self.invalid_args.clone(),
+            log_types: self.log_types.clone(),
+            simulated_height: self.simulated_height,
+        }
+    }
+}
+
+impl<D, S> ContractCall<D, S>
 where
     D: Detokenize,
+    S: Signer,
 {
     /// Sets external contracts as dependencies to this contract's call.
     /// Effectively, this will be used to create Input::Contract/Output::Contract
@@ -506,11 +792,24 @@ where
     /// `abigen!()`). The other field of CallResponse, `receipts`, contains the receipts of the
     /// transaction.
     async fn call_or_simulate(self, simulate: bool) -> Result<CallResponse<D>, Error> {
-        let receipts = Contract::call(
+        let (response, _) = self.execute(simulate, None).await?;
+        Ok(response)
+    }
+
+    /// Same as `call_or_simulate`, spending `base_coins` to pay for the call if they're set,
+    /// and also returning the id of the transaction.
+    pub(crate) async fn execute(
+        self,
+        simulate: bool,
+        base_coins: Option<Vec<Input>>,
+    ) -> Result<(CallResponse<D>, Bytes32), Error> {
+        self.check_args()?;
+
+        let (receipts, tx_id) = Contract::call(
             self.contract_id,
             Some(self.encoded_selector),
             Some(self.encoded_args),
-            &self.fuel_client,
+            &self.provider,
             self.tx_parameters,
             self.call_parameters,
             self.variable_outputs,
@@ -519,22 +818,96 @@ where
             self.external_contracts,
             self.wallet,
             simulate,
+            self.simulated_height,
+            base_coins,
         )
         .await?;
 
+        // Failed calls already report their panic, but dry-runs don't fail: their panic
+        // is only found in the receipts.
+        if let Some(reason) = decode_panic(&receipts) {
+            return Err(Error::Panic(reason));
+        }
+
         // If it's an ABI method without a return value, exit early.
         if self.output_params.is_empty() {
-            return Ok(CallResponse {
+            let response = CallResponse {
                 value: D::from_tokens(vec![])?,
                 receipts,
-            });
+            };
+            return Ok((response, tx_id));
         }
 
         let (decoded_value, receipts) = Self::get_decoded_output(receipts, &self.output_params)?;
-        Ok(CallResponse {
+        let response = CallResponse {
             value: D::from_tokens(decoded_value)?,
             receipts,
-        })
+        };
+        Ok((response, tx_id))
+    }
+
+    /// Marks the call as only reading the contract's storage, so that `run` simulates it.
+    /// Generated methods call it for functions annotated with `storage(read)`.
+    /// Note that this is a builder method, i.e. use it as a chain:
+    /// `my_contract_instance.my_method(...).read_only().run()`.
+    pub fn read_only(mut self) -> Self {
+        self.read_only = true;
+        self
+    }
+
+    /// Simulates the call as if it were included in the block at `height`, e.g. to check
+    /// behavior gated on the block height. Only the node's next block height is supported
+    /// for now, see provider's `dry_run_at_height`. Calls sent with `call` aren't affected.
+    /// Note that this is a builder method, i.e. use it as a chain:
+    /// `my_contract_instance.my_method(...).at_height(height).simulate()`.
+    pub fn at_height(mut self, height: u64) -> Self {
+        self.simulated_height = Some(height);
+        self
+    }
+
+    /// Sets the types of the values the contract logs, by log id, which `call_with_logs`
+    /// decodes them as. Generated methods set them from the ABI's `loggedTypes`.
+    pub fn with_log_types(mut self, log_types: Vec<(u64, ParamType)>) -> Self {
+        self.log_types = log_types;
+        self
+    }
+
+    /// Builds and signs the transaction `call` would submit, without sending it, e.g. to
+    /// check how the call is configured. The wallet's base asset coins are fetched to pay
+    /// for it, like when calling.
+    pub async fn build_transaction(&self) -> Result<Transaction, Error> {
+        self.check_args()?;
+
+        Contract::build_call_tx(
+            self.contract_id,
+            Some(self.encoded_selector),
+            Some(self.encoded_args.clone()),
+            &self.provider,
+            self.tx_parameters.clone(),
+            self.call_parameters.clone(),
+            self.variable_outputs.clone(),
+            self.maturity,
+            self.compute_calldata_offset,
+            self.external_contracts.clone(),
+            &self.wallet,
+            None,
+        )
+        .await
+    }
+
+    /// Fails with the error tokenizing the arguments of the call, if any.
+    fn check_args(&self) -> Result<(), Error> {
+        match &self.invalid_args {
+            Some(reason) => Err(Error::InvalidType(reason.clone())),
+            None => Ok(()),
+        }
+    }
+
+    /// Simulates the call if it's read-only, otherwise calls the contract's method.
+    /// Use `call` or `simulate` to pick one regardless of the method.
+    pub async fn run(self) -> Result<CallResponse<D>, Error> {
+        let simulate = self.read_only;
+        Self::call_or_simulate(self, simulate).await
     }
 
     /// Call a contract's method on the node, in a state-modifying manner.
@@ -542,6 +915,26 @@ where
         Ok(Self::call_or_simulate(self, false).await?)
     }
 
+    /// Same as `call`, also returning the values the contract logged under `log_id`
+    /// during the call, decoded as the type set for it by `with_log_types`, see
+    /// `Contract::decode_logs`. Fails if no type is set for `log_id`. Use `call` when the
+    /// logs aren't needed.
+    pub async fn call_with_logs<L: Tokenizable>(self, log_id: u64) -> Result<(D, Vec<L>), Error> {
+        let log_type = self
+            .log_types
+            .iter()
+            .find(|(id, _)| *id == log_id)
+            .map(|(_, log_type)| log_type.clone())
+            .ok_or_else(|| {
+                Error::MissingData(format!("no type is declared for the log id {}", log_id))
+            })?;
+        let contract_id = self.contract_id;
+
+        let response = Self::call_or_simulate(self, false).await?;
+        let logs = Contract::decode_logs(&response.receipts, &contract_id, log_id, &log_type)?;
+        Ok((response.value, logs))
+    }
+
     /// Call a contract's method on the node, in a simulated manner, meaning the state of the
     /// blockchain is *not* modified but simulated.
     /// It is the same as the `call` method because the API is more user-friendly this way.
@@ -585,3 +978,114 @@ where
         Ok((decoded_value, receipts))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_return_data_receipt() {
+        let receipts = vec![Receipt::ReturnData {
+            id: ContractId::default(),
+            ptr: 0,
+            len: 8,
+            digest: Bytes32::default(),
+            data: vec![0, 0, 0, 0, 0, 0, 0, 1],
+            pc: 0,
+            is: 0,
+        }];
+
+        let value: bool = Contract::decode_return(&receipts, &[ParamType::Bool]).unwrap();
+        assert!(value);
+
+        let result = Contract::decode_return::<bool>(&[], &[ParamType::Bool]);
+        assert!(matches!(result, Err(Error::MissingData(_))));
+    }
+
+    #[test]
+    fn summarize_call_receipts() {
+        let contract_id = ContractId::new([1u8; 32]);
+        let recipient = Address::new([2u8; 32]);
+        let asset_id = AssetId::new([3u8; 32]);
+
+        let receipts = vec![
+            Receipt::Log {
+                id: contract_id,
+                ra: 42,
+                rb: 0,
+                rc: 0,
+                rd: 0,
+                pc: 0,
+                is: 0,
+            },
+            Receipt::TransferOut {
+                id: contract_id,
+                to: recipient,
+                amount: 100,
+                asset_id,
+                pc: 0,
+                is: 0,
+            },
+            Receipt::Return {
+                id: contract_id,
+                val: 7,
+                pc: 0,
+                is: 0,
+            },
+            // The script's own return comes after the contract's
+            Receipt::Return {
+                id: ContractId::zeroed(),
+                val: 1,
+                pc: 0,
+                is: 0,
+            },
+        ];
+
+        let summary = Contract::summarize(&receipts);
+        assert_eq!(summary.return_value, Some(Token::U64(7)));
+        assert_eq!(summary.logs, vec![Token::U64(42)]);
+        assert_eq!(summary.transferred, vec![(recipient, 100, asset_id)]);
+        assert!(!summary.reverted);
+    }
+
+    #[test]
+    fn decode_logged_values() {
+        let contract_id = ContractId::new([1u8; 32]);
+        let other_contract_id = ContractId::new([2u8; 32]);
+        let log = |id, ra, rb| Receipt::Log {
+            id,
+            ra,
+            rb,
+            rc: 0,
+            rd: 0,
+            pc: 0,
+            is: 0,
+        };
+        let receipts = vec![
+            log(contract_id, 42, 0),
+            Receipt::Return {
+                id: contract_id,
+                val: 7,
+                pc: 0,
+                is: 0,
+            },
+            // Logged under another log id, or by another contract
+            log(contract_id, 1, 1),
+            log(other_contract_id, 2, 0),
+            log(contract_id, 43, 0),
+        ];
+
+        let logs: Vec<u64> =
+            Contract::decode_logs(&receipts, &contract_id, 0, &ParamType::U64).unwrap();
+        assert_eq!(logs, vec![42, 43]);
+
+        let logs: Vec<u64> =
+            Contract::decode_logs(&receipts, &contract_id, 1, &ParamType::U64).unwrap();
+        assert_eq!(logs, vec![1]);
+
+        // Bigger values are only logged with `LogData`
+        let logs: Vec<[u8; 32]> =
+            Contract::decode_logs(&receipts, &contract_id, 0, &ParamType::B256).unwrap();
+        assert!(logs.is_empty());
+    }
+}
@@ -1,6 +1,6 @@
 use core::str::Utf8Error;
 use fuels_core::errors::CodecError;
-use fuels_core::InvalidOutputType;
+use fuels_core::{InvalidOutputType, ParamType};
 use fuels_signers::wallet::WalletError;
 use thiserror::Error;
 pub type Result<T> = core::result::Result<T, Error>;
@@ -38,6 +38,12 @@ pub enum Error {
     ContractCallError(String),
     #[error("Wallet error: {0}")]
     WalletError(#[from] WalletError),
+    #[error("Failed to decode {param:?} at byte {offset}: {reason}")]
+    DecodeError {
+        param: ParamType,
+        offset: usize,
+        reason: String,
+    },
 }
 
 impl From<CodecError> for Error {
@@ -45,6 +51,15 @@ impl From<CodecError> for Error {
         match err {
             CodecError::InvalidData => Error::InvalidData,
             CodecError::Utf8Error(e) => Error::Utf8Error(e),
+            CodecError::Decode {
+                param,
+                offset,
+                reason,
+            } => Error::DecodeError {
+                param,
+                offset,
+                reason,
+            },
         }
     }
 }
@@ -1,6 +1,10 @@
 pub mod contract;
 pub mod parameters;
+pub mod predicate;
 pub mod script;
+pub mod session;
+#[cfg(feature = "test-helpers")]
+pub mod test_helpers;
 
 pub mod abi_encoder {
     pub use fuels_core::abi_encoder::*;
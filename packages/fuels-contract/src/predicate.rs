@@ -0,0 +1,78 @@
+use fuel_tx::{Address, AssetId, Input, Output, Receipt, Transaction, UtxoId, Witness};
+use fuel_vm::prelude::Contract as FuelContract;
+use fuels_core::errors::Error;
+use fuels_signers::provider::Provider;
+
+/// A predicate is a script guarding coins: the coins owned by its address can be spent by
+/// anyone, as long as the predicate evaluates to true for the data supplied with them.
+/// Coins are sent to a predicate like to any other address, e.g. with `Wallet::transfer`.
+#[derive(Debug, Clone)]
+pub struct Predicate {
+    code: Vec<u8>,
+    address: Address,
+}
+
+impl Predicate {
+    pub fn from_code(code: Vec<u8>) -> Self {
+        let root = FuelContract::root_from_code(&code);
+        Self {
+            address: Address::from(*root),
+            code,
+        }
+    }
+
+    pub fn code(&self) -> &[u8] {
+        &self.code
+    }
+
+    /// Address owning the coins guarded by this predicate, computed from the root
+    /// of its code.
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    /// Spends coins of `asset_id` owned by this predicate to send `amount` to `to`,
+    /// evaluating the predicate with `predicate_data`. The change goes back to the
+    /// predicate.
+    pub async fn spend(
+        &self,
+        provider: &Provider,
+        predicate_data: Vec<u8>,
+        to: &Address,
+        amount: u64,
+        asset_id: AssetId,
+    ) -> Result<Vec<Receipt>, Error> {
+        let coins = provider
+            .get_spendable_coins(&self.address, asset_id, amount)
+            .await?;
+
+        let inputs: Vec<Input> = coins
+            .into_iter()
+            .map(|coin| {
+                Input::coin(
+                    UtxoId::from(coin.utxo_id),
+                    self.address,
+                    coin.amount.0,
+                    asset_id,
+                    0,
+                    0,
+                    self.code.clone(),
+                    predicate_data.clone(),
+                )
+            })
+            .collect();
+        let outputs = vec![
+            Output::coin(*to, amount, asset_id),
+            Output::change(self.address, 0, asset_id),
+        ];
+
+        // Predicate coins aren't signed, but their witness index still has to point
+        // to an existing witness.
+        let mut tx = provider.build_transfer_tx(&inputs, &outputs);
+        if let Transaction::Script { witnesses, .. } = &mut tx {
+            witnesses.push(Witness::default());
+        }
+
+        Ok(provider.send_transaction(&tx).await?)
+    }
+}
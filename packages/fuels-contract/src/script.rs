@@ -1,9 +1,10 @@
 use anyhow::Result;
-use fuel_gql_client::client::{types::TransactionStatus, FuelClient};
+use fuel_gql_client::client::types::TransactionStatus;
 use fuel_tx::{Receipt, Transaction};
-use fuels_core::errors::Error;
+use fuels_core::errors::{decode_panic, Error};
+use fuels_signers::provider::Provider;
 
-/// Script is a very thin layer on top of fuel-client with some
+/// Script is a very thin layer on top of the provider with some
 /// extra functionalities needed and provided by the SDK.
 pub struct Script {
     pub tx: Transaction,
@@ -21,20 +22,40 @@ impl Script {
     }
 
     // Calling the contract executes the transaction, and is thus state-modifying
-    pub async fn call(self, fuel_client: &FuelClient) -> Result<Vec<Receipt>, Error> {
-        let tx_id = fuel_client.submit(&self.tx).await?.0.to_string();
-        let receipts = fuel_client.receipts(&tx_id).await?;
-        let status = fuel_client.transaction_status(&tx_id).await?;
+    pub async fn call(self, provider: &Provider) -> Result<Vec<Receipt>, Error> {
+        // The node rejecting the transaction, e.g. because one of its coins was already
+        // spent, isn't a failure of the call itself.
+        let tx_id = provider
+            .submit_transaction(&self.tx)
+            .await
+            .map_err(|e| Error::TransactionError(e.to_string()))?;
+        let receipts = provider.get_receipts(&tx_id).await?;
+        let status = provider.get_transaction_status(&tx_id).await?;
         match status {
-            TransactionStatus::Failure { reason, .. } => Err(Error::ContractCallError(reason)),
+            // A panic is reported with its reason, e.g. an arithmetic overflow, rather than
+            // the node's description of the failure.
+            TransactionStatus::Failure { reason, .. } => match decode_panic(&receipts) {
+                Some(panic) => Err(Error::Panic(panic)),
+                None => Err(Error::ContractCallError(reason)),
+            },
             _ => Ok(receipts),
         }
     }
 
     // Simulating a call to the contract means that the actual state of the blockchain is not
     // modified, it is only simulated using a "dry-run".
-    pub async fn simulate(self, fuel_client: &FuelClient) -> Result<Vec<Receipt>, Error> {
-        let receipts = fuel_client.dry_run(&self.tx).await?;
+    pub async fn simulate(self, provider: &Provider) -> Result<Vec<Receipt>, Error> {
+        let receipts = provider.dry_run(&self.tx).await?;
+        Ok(receipts)
+    }
+
+    // Same as `simulate`, as if the transaction were included in the block at `height`.
+    pub async fn simulate_at_height(
+        self,
+        provider: &Provider,
+        height: u64,
+    ) -> Result<Vec<Receipt>, Error> {
+        let receipts = provider.dry_run_at_height(&self.tx, height).await?;
         Ok(receipts)
     }
 }
@@ -0,0 +1,97 @@
+use crate::contract::{CallResponse, Contract, ContractCall};
+use fuel_tx::{AssetId, Input, UtxoId};
+use fuels_core::errors::Error;
+use fuels_core::Detokenize;
+use fuels_signers::{LocalWallet, Signer};
+
+/// Makes successive contract calls paid by the same wallet, spending the change of each
+/// call in the next one instead of asking the node for coins every time.
+///
+/// The change can only be chained when its amount is known upfront, i.e. when the calls
+/// have a gas price and byte price of 0. Otherwise, or if the chained coin turns out to be
+/// spent by someone else, the session falls back to querying the wallet's coins.
+pub struct ContractSession {
+    wallet: LocalWallet,
+    coins: Option<Vec<Input>>,
+    coin_queries: usize,
+}
+
+impl ContractSession {
+    pub fn new(wallet: LocalWallet) -> Self {
+        Self {
+            wallet,
+            coins: None,
+            coin_queries: 0,
+        }
+    }
+
+    /// Number of times the session had to ask the node for the wallet's coins.
+    pub fn coin_queries(&self) -> usize {
+        self.coin_queries
+    }
+
+    /// Submits `call`, signed and paid by the session's wallet rather than the one of the
+    /// contract instance that built it.
+    pub async fn call<D: Detokenize>(
+        &mut self,
+        mut call: ContractCall<D>,
+    ) -> Result<CallResponse<D>, Error> {
+        call.wallet = self.wallet.clone();
+
+        if let Some(coins) = self.coins.take() {
+            // The node rejects the transaction if the chained coins were spent in the meantime
+            match self.execute(call.clone(), coins).await {
+                Err(Error::TransactionError(_)) => {}
+                result => return result,
+            }
+        }
+
+        let coins = self.query_coins().await?;
+        self.execute(call, coins).await
+    }
+
+    async fn execute<D: Detokenize>(
+        &mut self,
+        call: ContractCall<D>,
+        coins: Vec<Input>,
+    ) -> Result<CallResponse<D>, Error> {
+        let fee_free = call.tx_parameters.gas_price == 0 && call.tx_parameters.byte_price == 0;
+        let forwarded = if call.call_parameters.asset_id == AssetId::default() {
+            call.call_parameters.amount
+        } else {
+            0
+        };
+        let total: u64 = coins.iter().filter_map(coin_amount).sum();
+
+        let (response, tx_id) = call.execute(false, Some(coins)).await?;
+
+        // The change of the base asset is the first output of the call
+        let change = total.saturating_sub(forwarded);
+        if fee_free && change > 0 {
+            self.coins = Some(vec![Input::coin(
+                UtxoId::new(tx_id, 0),
+                self.wallet.address(),
+                change,
+                AssetId::default(),
+                0,
+                0,
+                vec![],
+                vec![],
+            )]);
+        }
+
+        Ok(response)
+    }
+
+    async fn query_coins(&mut self) -> Result<Vec<Input>, Error> {
+        self.coin_queries += 1;
+        Contract::get_base_coins(&self.wallet.provider, &self.wallet).await
+    }
+}
+
+fn coin_amount(input: &Input) -> Option<u64> {
+    match input {
+        Input::Coin { amount, .. } => Some(*amount),
+        _ => None,
+    }
+}
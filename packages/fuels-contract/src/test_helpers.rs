@@ -0,0 +1,28 @@
+//! Testing utilities
+use crate::contract::Contract;
+use crate::parameters::TxParameters;
+use fuel_tx::Salt;
+use fuels_signers::provider::Provider;
+use fuels_signers::util::test_helpers::setup_test_provider_and_wallet;
+use fuels_signers::LocalWallet;
+use rand::Rng;
+
+/// Launches a local node with a funded wallet and deploys the contract at `binary_filepath`
+/// with a random salt. Returns what `new_contract` builds out of the contract's id, the
+/// provider and the wallet, e.g. the contract's bindings:
+/// `launch_provider_and_deploy(binary_filepath, MyContract::new).await`.
+pub async fn launch_provider_and_deploy<C>(
+    binary_filepath: &str,
+    new_contract: impl FnOnce(String, Provider, LocalWallet) -> C,
+) -> C {
+    let salt: [u8; 32] = rand::thread_rng().gen();
+    let compiled = Contract::load_sway_contract(binary_filepath, Salt::from(salt))
+        .expect("Failed to load the contract binary");
+
+    let (provider, wallet) = setup_test_provider_and_wallet().await;
+    let contract_id = Contract::deploy(&compiled, &provider, &wallet, TxParameters::default())
+        .await
+        .expect("Failed to deploy the contract");
+
+    new_contract(contract_id.to_string(), provider, wallet)
+}
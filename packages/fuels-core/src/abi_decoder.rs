@@ -1,4 +1,4 @@
-use crate::errors::CodecError;
+use crate::errors::{CodecError, Error};
 use crate::{constants::WORD_SIZE, Bits256, ByteArray, ParamType, Token};
 use core::convert::TryInto;
 use core::str;
@@ -10,12 +10,34 @@ struct DecodeResult {
     new_offset: usize,
 }
 
+/// Options of the `ABIDecoder`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct DecodeConfig {
+    /// Whether the null bytes padding a `str[N]` shorter than `N` are removed from the
+    /// decoded string. They are by default.
+    pub trim_trailing_nulls: bool,
+}
+
+impl Default for DecodeConfig {
+    fn default() -> Self {
+        Self {
+            trim_trailing_nulls: true,
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone)]
-pub struct ABIDecoder {}
+pub struct ABIDecoder {
+    config: DecodeConfig,
+}
 
 impl ABIDecoder {
     pub fn new() -> Self {
-        ABIDecoder {}
+        Self::with_config(DecodeConfig::default())
+    }
+
+    pub fn with_config(config: DecodeConfig) -> Self {
+        ABIDecoder { config }
     }
 
     /// Decode takes an array of `ParamType` and the encoded data as raw bytes
@@ -39,11 +61,56 @@ impl ABIDecoder {
         Ok(tokens)
     }
 
+    /// Same as `decode`, but takes the encoded data as a hex string, with or without
+    /// a `0x` prefix. Handy for decoding raw return data copied from logs.
+    pub fn decode_hex(&mut self, types: &[ParamType], encoded: &str) -> Result<Vec<Token>, Error> {
+        let encoded = encoded.trim();
+        let encoded = encoded.strip_prefix("0x").unwrap_or(encoded);
+        let data = hex::decode(encoded).map_err(|_| Error::InvalidData)?;
+
+        Ok(self.decode(types, &data)?)
+    }
+
+    /// Decodes the value of type `param` at `offset`. Errors are reported as
+    /// `CodecError::Decode`, with the type and offset of the innermost value that couldn't
+    /// be decoded.
     fn decode_param(
         self,
         param: &ParamType,
         data: &[u8],
         offset: usize,
+    ) -> Result<DecodeResult, CodecError> {
+        self.decode_value(param, data, offset).map_err(|e| {
+            if let CodecError::Decode { .. } = e {
+                return e;
+            }
+
+            let reason = match e {
+                CodecError::Utf8Error(e) => e.to_string(),
+                _ => {
+                    let remaining = data.len().saturating_sub(offset);
+                    match param.encoded_size() {
+                        needed if remaining < needed => {
+                            format!("expected {} bytes, only {} remain", needed, remaining)
+                        }
+                        _ => "invalid data".to_string(),
+                    }
+                }
+            };
+
+            CodecError::Decode {
+                param: param.clone(),
+                offset,
+                reason,
+            }
+        })
+    }
+
+    fn decode_value(
+        self,
+        param: &ParamType,
+        data: &[u8],
+        offset: usize,
     ) -> Result<DecodeResult, CodecError> {
         match &*param {
             ParamType::U8 => {
@@ -121,7 +188,10 @@ impl ABIDecoder {
             ParamType::String(length) => {
                 let encoded_str = peek(data, offset, *length)?;
 
-                let decoded = str::from_utf8(encoded_str)?;
+                let mut decoded = str::from_utf8(encoded_str)?;
+                if self.config.trim_trailing_nulls {
+                    decoded = decoded.trim_end_matches('\0');
+                }
 
                 let result = DecodeResult {
                     token: Token::String(decoded.to_string()),
@@ -130,6 +200,20 @@ impl ABIDecoder {
 
                 Ok(result)
             }
+            ParamType::DynamicString => {
+                let length = u64::from_be_bytes(peek_word(data, offset)?) as usize;
+
+                let encoded_str = peek(data, offset + WORD_SIZE, length)?;
+
+                let decoded = str::from_utf8(encoded_str)?;
+
+                let result = DecodeResult {
+                    token: Token::DynamicString(decoded.to_string()),
+                    new_offset: offset + WORD_SIZE + padded_len(encoded_str),
+                };
+
+                Ok(result)
+            }
             ParamType::Array(ref t, length) => {
                 let mut tokens = vec![];
                 let mut new_offset = offset;
@@ -147,6 +231,32 @@ impl ABIDecoder {
 
                 Ok(result)
             }
+            ParamType::Vector(ref t) => {
+                let length = u64::from_be_bytes(peek_word(data, offset)?) as usize;
+
+                // Every element takes at least a byte, so a length bigger than the
+                // remaining data is invalid, rather than something to allocate for.
+                let remaining = data.len().saturating_sub(offset + WORD_SIZE);
+                if length > remaining / t.encoded_size().max(1) {
+                    return Err(CodecError::InvalidData);
+                }
+
+                let mut tokens = Vec::with_capacity(length);
+                let mut new_offset = offset + WORD_SIZE;
+
+                for _ in 0..length {
+                    let res = self.decode_param(t, data, new_offset)?;
+                    new_offset = res.new_offset;
+                    tokens.push(res.token);
+                }
+
+                let result = DecodeResult {
+                    token: Token::Vector(tokens),
+                    new_offset,
+                };
+
+                Ok(result)
+            }
             ParamType::Struct(props) => {
                 let mut tokens = vec![];
 
@@ -165,20 +275,27 @@ impl ABIDecoder {
                 Ok(result)
             }
             ParamType::Enum(variations) => {
-                let discriminant = peek_word(data, offset).unwrap();
+                let discriminant = peek_word(data, offset)?;
 
                 let discriminant = u32::from_be_bytes(discriminant[4..8].try_into().unwrap());
+                let variant = variations
+                    .get(discriminant as usize)
+                    .ok_or(CodecError::InvalidData)?;
 
                 // Offset + 8 because of the discriminant that we just peeked
-                let res = self.decode_param(
-                    variations.get(discriminant as usize).unwrap(),
-                    data,
-                    offset + 8,
-                )?;
+                let res = self.decode_param(variant, data, offset + 8)?;
+
+                // An enum takes the size of its biggest variant, whichever variant it
+                // holds, so that the elements of an array of enums are evenly spaced.
+                let new_offset = res.new_offset.max(offset + param.encoded_size());
 
                 let result = DecodeResult {
-                    token: Token::Enum(Box::new((discriminant as u8, res.token))),
-                    new_offset: res.new_offset,
+                    token: Token::Enum(Box::new((
+                        discriminant as u8,
+                        res.token,
+                        variations.clone(),
+                    ))),
+                    new_offset,
                 };
 
                 Ok(result)
@@ -211,11 +328,9 @@ impl Default for ABIDecoder {
 }
 
 fn peek(data: &[u8], offset: usize, len: usize) -> Result<&[u8], CodecError> {
-    if offset + len > data.len() {
-        Err(CodecError::InvalidData)
-    } else {
-        Ok(&data[offset..(offset + len)])
-    }
+    // The length may come from the data itself, e.g. for dynamic strings
+    let end = offset.checked_add(len).ok_or(CodecError::InvalidData)?;
+    data.get(offset..end).ok_or(CodecError::InvalidData)
 }
 
 fn peek_word(data: &[u8], offset: usize) -> Result<ByteArray, CodecError> {
@@ -229,6 +344,7 @@ fn peek_word(data: &[u8], offset: usize) -> Result<ByteArray, CodecError> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::abi_encoder::ABIEncoder;
 
     #[test]
     fn decode_int() {
@@ -299,6 +415,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn decode_hex_string() {
+        let types = vec![ParamType::Bool, ParamType::U64];
+        let data = "0x0000000000000001000000000000002a";
+
+        let mut decoder = ABIDecoder::new();
+
+        let decoded = decoder.decode_hex(&types, data).unwrap();
+
+        let expected = vec![Token::Bool(true), Token::U64(42)];
+        assert_eq!(decoded, expected);
+
+        // Same payload without the prefix
+        let decoded = decoder.decode_hex(&types, &data[2..]).unwrap();
+        assert_eq!(decoded, expected);
+
+        let result = decoder.decode_hex(&types, "0xnothex");
+        assert!(matches!(result, Err(Error::InvalidData)));
+    }
+
     #[test]
     fn decode_b256() {
         let types = vec![ParamType::B256];
@@ -320,6 +456,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn decode_b256_array() {
+        let types = vec![ParamType::Array(Box::new(ParamType::B256), 4)];
+        let data: Vec<u8> = (1..=4u8).flat_map(|i| [i; 32]).collect();
+        let mut decoder = ABIDecoder::new();
+
+        let decoded = decoder.decode(&types, &data).unwrap();
+
+        let expected = vec![Token::Array(vec![
+            Token::B256([1; 32]),
+            Token::B256([2; 32]),
+            Token::B256([3; 32]),
+            Token::B256([4; 32]),
+        ])];
+        assert_eq!(decoded, expected);
+
+        // Missing the last word
+        assert!(decoder.decode(&types, &data[..96]).is_err());
+    }
+
     #[test]
     fn decode_string() {
         let types = vec![ParamType::String(23), ParamType::String(5)];
@@ -343,6 +499,101 @@ mod tests {
             data, types, decoded
         );
     }
+    #[test]
+    fn decode_string_trailing_nulls() {
+        let types = vec![ParamType::String(8)];
+        let data = [0x68, 0x69, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0];
+
+        let trimmed = ABIDecoder::new().decode(&types, &data).unwrap();
+        assert_eq!(trimmed, vec![Token::String("hi".into())]);
+
+        let config = DecodeConfig {
+            trim_trailing_nulls: false,
+        };
+        let raw = ABIDecoder::with_config(config)
+            .decode(&types, &data)
+            .unwrap();
+        assert_eq!(raw, vec![Token::String("hi\0\0\0\0\0\0".into())]);
+    }
+
+    #[test]
+    fn decode_dynamic_string() {
+        let types = vec![ParamType::DynamicString, ParamType::DynamicString];
+        let data = [
+            0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, // empty string length
+            0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x17, // sentence length
+            0x54, 0x68, 0x69, 0x73, 0x20, 0x69, 0x73, 0x20, 0x61, 0x20, 0x66, 0x75, 0x6c, 0x6c,
+            0x20, 0x73, 0x65, 0x6e, 0x74, 0x65, 0x6e, 0x63, 0x65, 0x00,
+        ];
+        let mut decoder = ABIDecoder::new();
+
+        let decoded = decoder.decode(&types, &data).unwrap();
+
+        let expected = vec![
+            Token::DynamicString("".into()),
+            Token::DynamicString("This is a full sentence".into()),
+        ];
+        assert_eq!(decoded, expected);
+    }
+
+    #[test]
+    fn dynamic_string_round_trip() {
+        let tokens = vec![
+            Token::DynamicString("".into()),
+            Token::DynamicString("This spans more than a single word".into()),
+            Token::U8(7),
+        ];
+        let types = vec![
+            ParamType::DynamicString,
+            ParamType::DynamicString,
+            ParamType::U8,
+        ];
+
+        let encoded = ABIEncoder::new().encode(&tokens).unwrap();
+        let decoded = ABIDecoder::new().decode(&types, &encoded).unwrap();
+
+        assert_eq!(decoded, tokens);
+    }
+
+    #[test]
+    fn vector_of_structs_round_trip() {
+        // Vec<Point>, where `struct Point { x: u64, y: bool }`
+        let point = ParamType::Struct(vec![ParamType::U64, ParamType::Bool]);
+        let types = vec![ParamType::Vector(Box::new(point)), ParamType::U8];
+        let tokens = vec![
+            Token::Vector(vec![
+                Token::Struct(vec![Token::U64(1), Token::Bool(true)]),
+                Token::Struct(vec![Token::U64(2), Token::Bool(false)]),
+            ]),
+            Token::U8(7),
+        ];
+
+        let encoded = ABIEncoder::new().encode(&tokens).unwrap();
+        // Length word, two 2-word structs, then the trailing u8
+        assert_eq!(encoded.len(), 6 * WORD_SIZE);
+        assert_eq!(encoded[..WORD_SIZE], [0, 0, 0, 0, 0, 0, 0, 2]);
+
+        let decoded = ABIDecoder::new().decode(&types, &encoded).unwrap();
+        assert_eq!(decoded, tokens);
+
+        // A length claiming more elements than the data holds is rejected
+        let mut truncated = encoded[..3 * WORD_SIZE].to_vec();
+        truncated[WORD_SIZE - 1] = 0xff;
+        assert!(ABIDecoder::new().decode(&types[..1], &truncated).is_err());
+    }
+
+    #[test]
+    fn decode_dynamic_string_with_missing_data() {
+        // Length says 16 bytes, only 8 follow
+        let data = [
+            0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x10, 0x48, 0x65, 0x6c, 0x6c, 0x6f, 0x0, 0x0, 0x0,
+        ];
+
+        let result = ABIDecoder::new().decode(&[ParamType::DynamicString], &data);
+
+        assert!(result.is_err());
+    }
+
     #[test]
     fn decode_array() {
         // Create a parameter type for u8[2].
@@ -406,7 +657,11 @@ mod tests {
 
         let decoded = decoder.decode(&types, &data).unwrap();
 
-        let expected = vec![Token::Enum(Box::new((0, Token::U32(42))))];
+        let expected = vec![Token::Enum(Box::new((
+            0,
+            Token::U32(42),
+            vec![ParamType::U32, ParamType::Bool],
+        )))];
         assert_eq!(decoded, expected);
 
         println!(
@@ -415,6 +670,125 @@ mod tests {
         );
     }
 
+    #[test]
+    fn decode_array_of_enums() {
+        // Sway enum:
+        // enum Shaker {
+        //     x: u64,
+        //     y: b256,
+        // }
+        let variants = vec![ParamType::U64, ParamType::B256];
+        let shaker = ParamType::Enum(variants.clone());
+        let types = vec![ParamType::Array(Box::new(shaker), 2), ParamType::Bool];
+
+        let mut data = vec![];
+        // `x` variant holding 42, padded to the size of `y`
+        data.extend([0, 0, 0, 0, 0, 0, 0, 0]);
+        data.extend([0, 0, 0, 0, 0, 0, 0, 42]);
+        data.extend([0; 24]);
+        // `y` variant
+        data.extend([0, 0, 0, 0, 0, 0, 0, 1]);
+        data.extend([0xff; 32]);
+        // Trailing bool
+        data.extend([0, 0, 0, 0, 0, 0, 0, 1]);
+
+        let decoded = ABIDecoder::new().decode(&types, &data).unwrap();
+
+        let expected = vec![
+            Token::Array(vec![
+                Token::Enum(Box::new((0, Token::U64(42), variants.clone()))),
+                Token::Enum(Box::new((1, Token::B256([0xff; 32]), variants))),
+            ]),
+            Token::Bool(true),
+        ];
+        assert_eq!(decoded, expected);
+    }
+
+    #[test]
+    fn decode_enum_holding_array() {
+        // Sway enum:
+        // enum MyEnum {
+        //     x: [u64; 3],
+        //     y: bool,
+        // }
+        let variants = vec![
+            ParamType::Array(Box::new(ParamType::U64), 3),
+            ParamType::Bool,
+        ];
+        let types = vec![ParamType::Enum(variants.clone())];
+
+        let mut data = vec![];
+        data.extend([0, 0, 0, 0, 0, 0, 0, 0]);
+        data.extend([0, 0, 0, 0, 0, 0, 0, 1]);
+        data.extend([0, 0, 0, 0, 0, 0, 0, 2]);
+        data.extend([0, 0, 0, 0, 0, 0, 0, 3]);
+
+        let decoded = ABIDecoder::new().decode(&types, &data).unwrap();
+
+        let expected = vec![Token::Enum(Box::new((
+            0,
+            Token::Array(vec![Token::U64(1), Token::U64(2), Token::U64(3)]),
+            variants,
+        )))];
+        assert_eq!(decoded, expected);
+    }
+
+    #[test]
+    fn decode_enum_with_unknown_discriminant() {
+        let types = vec![ParamType::Enum(vec![ParamType::U32, ParamType::Bool])];
+        let data = [0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 1];
+
+        let result = ABIDecoder::new().decode(&types, &data);
+        assert!(matches!(result, Err(CodecError::Decode { offset: 0, .. })));
+    }
+
+    #[test]
+    fn decode_dynamic_string_with_overflowing_length() {
+        let types = vec![ParamType::DynamicString];
+        let data = [
+            255, 255, 255, 255, 255, 255, 255, 255, b'a', 0, 0, 0, 0, 0, 0, 0,
+        ];
+
+        let result = ABIDecoder::new().decode(&types, &data);
+        assert!(matches!(result, Err(CodecError::Decode { offset: 0, .. })));
+    }
+
+    #[test]
+    fn decode_truncated_tuple() {
+        let types = vec![ParamType::Tuple(vec![ParamType::U64, ParamType::U64])];
+        // The second `u64` only has 4 of its 8 bytes
+        let data = [0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 2];
+
+        let err: Error = ABIDecoder::new().decode(&types, &data).unwrap_err().into();
+        match err {
+            Error::DecodeError {
+                param,
+                offset,
+                reason,
+            } => {
+                assert_eq!(param, ParamType::U64);
+                assert_eq!(offset, 8);
+                assert_eq!(reason, "expected 8 bytes, only 4 remain");
+            }
+            e => panic!("unexpected error: {}", e),
+        }
+    }
+
+    #[test]
+    fn decode_empty_array() {
+        let types = vec![
+            ParamType::Array(Box::new(ParamType::U64), 0),
+            ParamType::Bool,
+        ];
+        let data = [0, 0, 0, 0, 0, 0, 0, 1];
+
+        let decoded = ABIDecoder::new().decode(&types, &data).unwrap();
+        assert_eq!(decoded, vec![Token::Array(vec![]), Token::Bool(true)]);
+
+        let encoded = ABIEncoder::new().encode(&decoded).unwrap();
+        assert_eq!(encoded, data);
+    }
+
     #[test]
     fn decode_nested_struct() {
         // Sway nested struct:
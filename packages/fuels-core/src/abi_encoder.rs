@@ -1,10 +1,14 @@
-use crate::errors::CodecError;
-use crate::{pad_string, pad_u16, pad_u32, pad_u8, ByteArray, Token};
+use crate::constants::WORD_SIZE;
+use crate::errors::{CodecError, Error};
+use crate::{ByteArray, ParamType, Token};
 use sha2::{Digest, Sha256};
 
 pub struct ABIEncoder {
     pub function_selector: ByteArray,
     pub encoded_args: Vec<u8>,
+    /// Size, in bytes, that primitive values are padded to. Strings are padded to a
+    /// multiple of it. Defaults to the size of a FuelVM word, see `with_word_size`.
+    word_size: usize,
 }
 
 impl ABIEncoder {
@@ -12,14 +16,30 @@ impl ABIEncoder {
         Self {
             function_selector: [0; 8],
             encoded_args: Vec::new(),
+            word_size: WORD_SIZE,
         }
     }
 
     pub fn new_with_fn_selector(signature: &[u8]) -> Self {
         Self {
             function_selector: Self::encode_function_selector(signature),
-            encoded_args: Vec::new(),
+            ..Self::new()
+        }
+    }
+
+    /// Sets the size values are padded to, for VMs with a non-standard layout. Fails if
+    /// it's 0.
+    pub fn with_word_size(mut self, word_size: usize) -> Result<Self, CodecError> {
+        if word_size == 0 {
+            return Err(CodecError::InvalidData);
         }
+        self.word_size = word_size;
+        Ok(self)
+    }
+
+    /// Size, in bytes, that values are padded to.
+    pub fn word_size(&self) -> usize {
+        self.word_size
     }
 
     /// Encode takes an array of `Token`s, encodes these tokens, and returns the
@@ -29,31 +49,47 @@ impl ABIEncoder {
     pub fn encode(&mut self, args: &[Token]) -> Result<Vec<u8>, CodecError> {
         for arg in args {
             match arg {
-                Token::U8(arg_u8) => self.encoded_args.extend(pad_u8(arg_u8)),
-                Token::U16(arg_u16) => self.encoded_args.extend(pad_u16(arg_u16)),
-                Token::U32(arg_u32) => self.encoded_args.extend(pad_u32(arg_u32)),
-                Token::U64(arg_u64) => self.encoded_args.extend(arg_u64.to_be_bytes()),
-                Token::Byte(arg_byte) => self.encoded_args.extend(pad_u8(arg_byte)),
-                Token::Bool(arg_bool) => {
-                    self.encoded_args
-                        .extend(pad_u8(if *arg_bool { &1 } else { &0 }))
-                }
+                Token::U8(arg_u8) => self.encode_word(&arg_u8.to_be_bytes())?,
+                Token::U16(arg_u16) => self.encode_word(&arg_u16.to_be_bytes())?,
+                Token::U32(arg_u32) => self.encode_word(&arg_u32.to_be_bytes())?,
+                Token::U64(arg_u64) => self.encode_word(&arg_u64.to_be_bytes())?,
+                Token::Byte(arg_byte) => self.encode_word(&[*arg_byte])?,
+                Token::Bool(arg_bool) => self.encode_word(&[*arg_bool as u8])?,
                 Token::B256(arg_bits256) => self.encoded_args.extend(arg_bits256),
                 Token::Array(arg_array) => {
                     // Recursively encode the array of Tokens
                     self.encode(arg_array)?;
                 }
-                Token::String(arg_string) => self.encoded_args.extend(pad_string(arg_string)),
+                Token::Vector(arg_vector) => {
+                    // Length prefix, then each element
+                    self.encode_word(&(arg_vector.len() as u64).to_be_bytes())?;
+                    self.encode(arg_vector)?;
+                }
+                Token::String(arg_string) => self.encode_string(arg_string),
+                Token::DynamicString(arg_string) => {
+                    // Length prefix, then the string itself
+                    self.encode_word(&(arg_string.len() as u64).to_be_bytes())?;
+                    self.encode_string(arg_string);
+                }
                 Token::Struct(arg_struct) => {
                     for property in arg_struct.iter() {
                         self.encode(&[property.to_owned()])?;
                     }
                 }
                 Token::Enum(arg_enum) => {
+                    let (discriminant, token, variants) = arg_enum.as_ref();
+                    let start = self.encoded_args.len();
+
                     // Encode the discriminant of the enum
-                    self.encoded_args.extend(pad_u8(&arg_enum.0));
+                    self.encode_word(&[*discriminant])?;
                     // Encode the Token within the enum
-                    self.encode(&[arg_enum.1.to_owned()])?;
+                    self.encode(&[token.to_owned()])?;
+
+                    // An enum takes the size of its biggest variant, whichever variant it
+                    // holds, so that the elements of an array of enums are evenly spaced.
+                    let size = ParamType::Enum(variants.clone()).encoded_size();
+                    let padding = size.saturating_sub(self.encoded_args.len() - start);
+                    self.encoded_args.extend(vec![0; padding]);
                 }
                 Token::Tuple(arg_tuple) => {
                     self.encode(arg_tuple)?;
@@ -63,6 +99,61 @@ impl ABIEncoder {
         Ok(self.encoded_args.clone())
     }
 
+    /// Same as `encode`, but first checks that `args` has as many tokens as the function
+    /// takes `params`, so that a call with missing arguments isn't silently encoded.
+    pub fn encode_arguments(
+        &mut self,
+        params: &[ParamType],
+        args: &[Token],
+    ) -> Result<Vec<u8>, Error> {
+        if params.len() != args.len() {
+            return Err(Error::ArgumentCountMismatch {
+                expected: params.len(),
+                got: args.len(),
+            });
+        }
+
+        Ok(self.encode(args)?)
+    }
+
+    /// Encodes a call to the function with the given `signature`, e.g. `entry_one(u64)`:
+    /// its selector followed by the encoded `args`, leaving out what this encoder encoded
+    /// before.
+    pub fn encode_function(
+        &mut self,
+        signature: &[u8],
+        args: &[Token],
+    ) -> Result<Vec<u8>, CodecError> {
+        self.function_selector = Self::encode_function_selector(signature);
+        self.encoded_args.clear();
+
+        let mut encoded = self.function_selector.to_vec();
+        encoded.extend(self.encode(args)?);
+        Ok(encoded)
+    }
+
+    /// Right-aligns the big-endian bytes of a value in a word. Fails if the value
+    /// doesn't fit in a word.
+    fn encode_word(&mut self, be_bytes: &[u8]) -> Result<(), CodecError> {
+        let overflow = be_bytes.len().saturating_sub(self.word_size);
+        if be_bytes[..overflow].iter().any(|b| *b != 0) {
+            return Err(CodecError::InvalidData);
+        }
+
+        let value = &be_bytes[overflow..];
+        self.encoded_args
+            .extend(vec![0; self.word_size - value.len()]);
+        self.encoded_args.extend(value);
+        Ok(())
+    }
+
+    /// Pads a string with zeroes to a multiple of the word size.
+    fn encode_string(&mut self, s: &str) {
+        let padding = (self.word_size - s.len() % self.word_size) % self.word_size;
+        self.encoded_args.extend(s.as_bytes());
+        self.encoded_args.extend(vec![0; padding]);
+    }
+
     pub fn encode_function_selector(signature: &[u8]) -> ByteArray {
         let mut hasher = Sha256::new();
         hasher.update(signature);
@@ -425,6 +516,27 @@ mod tests {
         assert_eq!(abi_encoder.function_selector, expected_function_selector);
     }
 
+    #[test]
+    fn encode_dynamic_string() {
+        let args = vec![Token::DynamicString("This is a full sentence".into())];
+
+        let expected_encoded_abi = [
+            0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x17, 0x54, 0x68, 0x69, 0x73, 0x20, 0x69, 0x73,
+            0x20, 0x61, 0x20, 0x66, 0x75, 0x6c, 0x6c, 0x20, 0x73, 0x65, 0x6e, 0x74, 0x65, 0x6e,
+            0x63, 0x65, 0x00,
+        ];
+
+        let encoded = ABIEncoder::new().encode(&args).unwrap();
+
+        assert_eq!(hex::encode(expected_encoded_abi), hex::encode(encoded));
+
+        let encoded = ABIEncoder::new()
+            .encode(&[Token::DynamicString("".into())])
+            .unwrap();
+
+        assert_eq!(encoded, [0u8; 8]);
+    }
+
     #[test]
     fn encode_function_with_struct() {
         // let json_abi =
@@ -473,6 +585,88 @@ mod tests {
         assert_eq!(abi_encoder.function_selector, expected_function_selector);
     }
 
+    #[test]
+    fn encode_arguments_checks_count() {
+        let params = [ParamType::U32, ParamType::Bool];
+
+        let encoded = ABIEncoder::new()
+            .encode_arguments(&params, &[Token::U32(1), Token::Bool(true)])
+            .unwrap();
+        assert_eq!(encoded, [0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 1]);
+
+        let result = ABIEncoder::new().encode_arguments(&params, &[Token::U32(1)]);
+        assert!(matches!(
+            result,
+            Err(Error::ArgumentCountMismatch {
+                expected: 2,
+                got: 1
+            })
+        ));
+    }
+
+    #[test]
+    fn encode_struct_layout() {
+        // Sway struct:
+        // struct MyStruct {
+        //     foo: u8,
+        //     bar: bool,
+        //     baz: str[3],
+        // }
+        let my_struct = Token::Struct(vec![
+            Token::U8(7),
+            Token::Bool(true),
+            Token::String("abc".to_string()),
+        ]);
+
+        let encoded = ABIEncoder::default()
+            .encode_function(b"takes_my_struct(s(u8,bool,str[3]))", &[my_struct.clone()])
+            .unwrap();
+
+        let mut expected =
+            ABIEncoder::encode_function_selector("takes_my_struct(s(u8,bool,str[3]))".as_bytes())
+                .to_vec();
+        expected.extend([0, 0, 0, 0, 0, 0, 0, 7]);
+        expected.extend([0, 0, 0, 0, 0, 0, 0, 1]);
+        expected.extend([b'a', b'b', b'c', 0, 0, 0, 0, 0]);
+        assert_eq!(encoded, expected);
+
+        // Same struct on a VM with 4-byte words
+        let encoded = ABIEncoder::new()
+            .with_word_size(4)
+            .unwrap()
+            .encode(&[my_struct])
+            .unwrap();
+        assert_eq!(encoded, [0, 0, 0, 7, 0, 0, 0, 1, b'a', b'b', b'c', 0]);
+
+        // A u64 that doesn't fit in such a word
+        let result = ABIEncoder::new()
+            .with_word_size(4)
+            .unwrap()
+            .encode(&[Token::U64(u64::MAX)]);
+        assert!(matches!(result, Err(CodecError::InvalidData)));
+
+        assert_eq!(ABIEncoder::new().with_word_size(4).unwrap().word_size(), 4);
+        assert!(matches!(
+            ABIEncoder::new().with_word_size(0),
+            Err(CodecError::InvalidData)
+        ));
+    }
+
+    #[test]
+    fn encode_function_leaves_out_previous_calls() {
+        let mut encoder = ABIEncoder::new();
+        let first = encoder
+            .encode_function(b"entry_one(u64)", &[Token::U64(1)])
+            .unwrap();
+        let second = encoder
+            .encode_function(b"entry_one(u64)", &[Token::U64(2)])
+            .unwrap();
+
+        assert_eq!(first.len(), 16);
+        assert_eq!(second.len(), 16);
+        assert_eq!(&second[8..], &[0, 0, 0, 0, 0, 0, 0, 2]);
+    }
+
     #[test]
     fn encode_function_with_enum() {
         // let json_abi =
@@ -497,7 +691,7 @@ mod tests {
 
         // Create a tuple with the Enum discriminant (`0` in this case)
         // And the value matching the discriminant type.
-        let val = Box::new((0, Token::U32(42)));
+        let val = Box::new((0, Token::U32(42), vec![ParamType::U32, ParamType::Bool]));
 
         // Create the custom enum token using the array of the tuple above
         let arg = Token::Enum(val);
@@ -520,6 +714,40 @@ mod tests {
         assert_eq!(abi_encoder.function_selector, expected_function_selector);
     }
 
+    #[test]
+    fn array_of_enums_round_trip() {
+        // Sway enum:
+        // enum Shaker {
+        //     x: u64,
+        //     y: b256,
+        // }
+        let variants = vec![ParamType::U64, ParamType::B256];
+        let types = vec![
+            ParamType::Array(Box::new(ParamType::Enum(variants.clone())), 3),
+            ParamType::Bool,
+        ];
+        let tokens = vec![
+            Token::Array(vec![
+                Token::Enum(Box::new((0, Token::U64(42), variants.clone()))),
+                Token::Enum(Box::new((1, Token::B256([0xff; 32]), variants.clone()))),
+                Token::Enum(Box::new((0, Token::U64(7), variants))),
+            ]),
+            Token::Bool(true),
+        ];
+
+        let encoded = ABIEncoder::new().encode(&tokens).unwrap();
+
+        // Each enum takes a discriminant and the size of its biggest variant
+        assert_eq!(encoded.len(), 3 * (8 + 32) + 8);
+        assert_eq!(&encoded[8..16], &[0, 0, 0, 0, 0, 0, 0, 42]);
+        assert_eq!(&encoded[16..40], &[0; 24]);
+
+        let decoded = crate::abi_decoder::ABIDecoder::new()
+            .decode(&types, &encoded)
+            .unwrap();
+        assert_eq!(decoded, tokens);
+    }
+
     #[test]
     fn encode_function_with_nested_structs() {
         // let json_abi =
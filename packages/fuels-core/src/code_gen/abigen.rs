@@ -1,18 +1,24 @@
 use std::collections::HashMap;
 
 use crate::code_gen::bindings::ContractBindings;
+use crate::code_gen::configurables_gen::expand_configurables;
 use crate::code_gen::custom_types_gen::{
     expand_internal_enum, expand_internal_struct, extract_custom_type_name_from_abi_property,
 };
-use crate::code_gen::functions_gen::expand_function;
+use crate::code_gen::functions_gen::{expand_function, expand_output_struct, expand_output_types};
 use crate::errors::Error;
-use crate::json_abi::ABIParser;
+use crate::json_abi::{
+    parse_configurables, parse_json_abi, parse_log_types, parse_param, vector_element, ABIParser,
+    Configurable, LogType,
+};
 use crate::source::Source;
 use crate::utils::ident;
 use fuels_types::{JsonABI, Property};
+use inflector::Inflector;
 
 use proc_macro2::{Ident, TokenStream};
 use quote::quote;
+use sha2::{Digest, Sha256};
 
 use super::custom_types_gen::CustomType;
 
@@ -35,26 +41,47 @@ pub struct Abigen {
 
     /// Generate no-std safe code
     no_std: bool,
+
+    /// Name of the generated module, defaults to `{contract_name}_mod` lowercased.
+    module_name: Option<String>,
+
+    /// Keep the generated items in their module rather than re-exporting them
+    namespaced: bool,
+
+    /// Return a struct instead of a tuple from functions with several named outputs
+    named_outputs: bool,
+
+    /// Only generate the ABI's types, without the contract and its methods
+    types_only: bool,
+
+    /// Hex-encoded SHA-256 of the ABI source
+    abi_hash: String,
+
+    /// Constants set before deploying the contract, only declared by program ABIs
+    configurables: Vec<Configurable>,
+
+    /// Types of the values the contract logs, only declared by program ABIs
+    log_types: Vec<LogType>,
 }
 
 impl Abigen {
-    /// Creates a new contract with the given ABI JSON source.
+    /// Creates a new contract with the given ABI JSON source, in either the flat or the
+    /// program ABI format, see `parse_json_abi`.
     pub fn new<S: AsRef<str>>(contract_name: &str, abi_source: S) -> Result<Self, Error> {
-        let source = Source::parse(abi_source).unwrap();
-        let mut parsed_abi: JsonABI = serde_json::from_str(&source.get().unwrap())?;
-
-        // Filter out outputs with empty returns. These are
-        // generated by forc's json abi as `"name": ""` and `"type": "()"`
+        let abi_json = Source::parse(abi_source)
+            .and_then(|source| source.get())
+            .map_err(|e| Error::MissingData(format!("{:#}", e)))?;
+        let abi_hash = hex::encode(Sha256::digest(abi_json.as_bytes()));
+        let mut parsed_abi = parse_json_abi(&abi_json)?;
+        let configurables = parse_configurables(&abi_json)?;
+        let log_types = parse_log_types(&abi_json)?;
+
+        // Filter out `()` outputs, which forc declares for functions that return nothing.
+        // There's no value to decode for them, so such functions are expanded as returning
+        // `()`, and their calls don't look for a return value in the receipts.
         for f in &mut parsed_abi {
-            let index = f
-                .outputs
-                .iter()
-                .position(|p| p.name.is_empty() && p.type_field == "()");
-
-            match index {
-                Some(i) => f.outputs.remove(i),
-                None => continue,
-            };
+            f.outputs
+                .retain(|output| output.type_field.replace(' ', "") != "()");
         }
 
         Ok(Self {
@@ -65,6 +92,13 @@ impl Abigen {
             abi_parser: ABIParser::new(),
             rustfmt: true,
             no_std: false,
+            module_name: None,
+            namespaced: false,
+            named_outputs: false,
+            types_only: false,
+            abi_hash,
+            configurables,
+            log_types,
         })
     }
 
@@ -73,12 +107,55 @@ impl Abigen {
         self
     }
 
+    /// Overrides the name of the generated module, e.g. to avoid collisions between
+    /// contracts whose derived module names are the same.
+    pub fn module_name(mut self, name: &str) -> Self {
+        self.module_name = Some(name.to_string());
+        self
+    }
+
+    /// Keeps the generated contract and types inside a public module instead of
+    /// re-exporting them, so that contracts declaring types with the same name can be
+    /// used side by side, e.g. as `my_contract::Info`. The module is named after the
+    /// contract in snake case, unless `module_name` is set.
+    pub fn namespaced(mut self) -> Self {
+        self.namespaced = true;
+        self
+    }
+
+    /// Makes functions with several named outputs return a struct with a field per
+    /// output, named `{FunctionName}Output`, instead of a tuple.
+    pub fn named_outputs(mut self) -> Self {
+        self.named_outputs = true;
+        self
+    }
+
+    /// Only generates the ABI's custom types and the helpers to encode and decode them,
+    /// leaving out the contract struct and its methods, which need a `Provider` and a
+    /// wallet. Suits libraries encoding and decoding data without talking to a node.
+    pub fn types_only(mut self) -> Self {
+        self.types_only = true;
+        self
+    }
+
+    /// Returns the hex-encoded SHA-256 of the ABI source, which the generated contract
+    /// type exposes as `ABI_HASH`. Comparing it to the hash of the current ABI tells
+    /// whether the bindings are stale.
+    pub fn abi_hash(&self) -> &str {
+        &self.abi_hash
+    }
+
     /// Generates the contract bindings.
     pub fn generate(self) -> Result<ContractBindings, Error> {
         let rustfmt = self.rustfmt;
         let tokens = self.expand()?;
 
-        Ok(ContractBindings { tokens, rustfmt })
+        Ok(ContractBindings {
+            tokens,
+            rustfmt,
+            rustfmt_edition: crate::rustfmt::DEFAULT_EDITION.to_string(),
+            rustfmt_config: None,
+        })
     }
 
     /// Entry point of the Abigen's expansion logic.
@@ -92,14 +169,43 @@ impl Abigen {
     /// after it is called through a procedural macro (`abigen!()` in our case).
     pub fn expand(&self) -> Result<TokenStream, Error> {
         let name = &self.contract_name;
-        let name_mod = ident(&format!(
-            "{}_mod",
-            self.contract_name.to_string().to_lowercase()
-        ));
+        let name_mod = match &self.module_name {
+            Some(module_name) => ident(module_name),
+            None if self.namespaced => ident(&self.contract_name.to_string().to_snake_case()),
+            None => ident(&format!(
+                "{}_mod",
+                self.contract_name.to_string().to_lowercase()
+            )),
+        };
+
+        let (export, visibility) = match self.namespaced {
+            true => (quote! {}, quote! { pub }),
+            false => (quote! { pub use #name_mod::*; }, quote! {}),
+        };
 
         let contract_functions = self.functions()?;
+        let balance_method = self.balance_method();
+        let output_structs = self.output_structs()?;
+        let output_types = self.output_types()?;
         let abi_structs = self.abi_structs()?;
         let abi_enums = self.abi_enums()?;
+        let abi_hash = &self.abi_hash;
+        let configurables = expand_configurables(&self.configurables)?;
+        let log_types = self.log_types()?;
+
+        let helpers = quote! {
+            #output_structs
+            #configurables
+
+            /// The types of the values returned by the contract's functions,
+            /// e.g. to decode them from receipts with `Contract::decode_return`.
+            #[allow(non_snake_case)]
+            pub mod output_types {
+                use fuels_rs::core::ParamType;
+
+                #output_types
+            }
+        };
 
         let (includes, code) = if self.no_std {
             (
@@ -108,38 +214,62 @@ impl Abigen {
                 },
                 quote! {},
             )
+        } else if self.types_only {
+            (quote! {}, helpers)
         } else {
             (
                 quote! {
                     use fuel_tx::{ContractId, Address};
-                    use fuels_rs::contract::contract::{Contract, ContractCall};
-                    use fuels_rs::signers::{provider::Provider, LocalWallet};
+                    use fuels_rs::contract::contract::{Contract, ContractCallHandler};
+                    use fuels_rs::core::parse_contract_id;
+                    use fuels_rs::signers::{provider::Provider, LocalWallet, Signer};
                     use std::str::FromStr;
                 },
                 quote! {
-                    pub struct #name {
+                    pub struct #name<S = LocalWallet> {
                         contract_id: ContractId,
                         provider: Provider,
-                        wallet: LocalWallet
+                        wallet: S
                     }
 
-                    impl #name {
-                        pub fn new(contract_id: String, provider: Provider, wallet: LocalWallet)
+                    impl<S: Signer + Clone> #name<S> {
+                        /// Same as `try_new`, panicking if `contract_id` isn't a valid
+                        /// contract id.
+                        pub fn new(contract_id: String, provider: Provider, wallet: S)
                         -> Self {
-                            let contract_id = ContractId::from_str(&contract_id).unwrap();
-                            Self{ contract_id, provider, wallet }
+                            Self::try_new(contract_id, provider, wallet).unwrap()
+                        }
+
+                        /// Creates a handle to the contract `contract_id`, a hex string
+                        /// with or without the `0x` prefix. Fails if it isn't a valid
+                        /// contract id.
+                        pub fn try_new(contract_id: String, provider: Provider, wallet: S)
+                        -> Result<Self, fuels_rs::core::errors::Error> {
+                            let contract_id = parse_contract_id(&contract_id)?;
+                            Ok(Self{ contract_id, provider, wallet })
                         }
                         #contract_functions
+
+                        #balance_method
+                    }
+
+                    impl #name {
+                        /// Hex-encoded SHA-256 of the ABI these bindings were generated from.
+                        pub const ABI_HASH: &'static str = #abi_hash;
+
+                        #log_types
                     }
+
+                    #helpers
                 },
             )
         };
 
         Ok(quote! {
-            pub use #name_mod::*;
+            #export
 
             #[allow(clippy::too_many_arguments)]
-            mod #name_mod {
+            #visibility mod #name_mod {
                 #![allow(clippy::enum_variant_names)]
                 #![allow(dead_code)]
                 #![allow(unused_imports)]
@@ -155,22 +285,99 @@ impl Abigen {
         })
     }
 
+    /// Generates the methods calling the contract's functions. Errors are wrapped in
+    /// `Error::FunctionGeneration` with the name of the function that couldn't be generated.
     pub fn functions(&self) -> Result<TokenStream, Error> {
         let mut tokenized_functions = Vec::new();
 
         for function in &self.abi {
             let tokenized_fn = expand_function(
                 function,
+                &self.contract_name,
                 &self.abi_parser,
                 &self.custom_enums,
                 &self.custom_structs,
-            )?;
+                self.named_outputs,
+            )
+            .map_err(|e| Error::FunctionGeneration {
+                name: function.name.clone(),
+                source: Box::new(e),
+            })?;
             tokenized_functions.push(tokenized_fn);
         }
 
         Ok(quote! { #( #tokenized_functions )* })
     }
 
+    /// Generates the `get_balance` method, querying the contract's balance of an asset.
+    /// It's left out if the contract has a function of the same name.
+    fn balance_method(&self) -> TokenStream {
+        if self
+            .abi
+            .iter()
+            .any(|function| function.name == "get_balance")
+        {
+            return quote! {};
+        }
+
+        quote! {
+            /// Returns the contract's balance of `asset_id`
+            pub async fn get_balance(&self, asset_id: fuel_tx::AssetId) -> Result<u64, fuels_core::errors::Error> {
+                Ok(self.provider.get_contract_balance(&self.contract_id, &asset_id).await?)
+            }
+        }
+    }
+
+    /// Generates the contract type's `log_types` function, returning the types of the
+    /// values the contract logs by log id, which the generated methods pass on to
+    /// `call_with_logs`.
+    fn log_types(&self) -> Result<TokenStream, Error> {
+        let log_types = self
+            .log_types
+            .iter()
+            .map(|log_type| {
+                let log_id = log_type.log_id;
+                let param_type: TokenStream =
+                    format!("ParamType::{}", parse_param(&log_type.property)?)
+                        .parse()
+                        .map_err(|e| Error::InvalidType(format!("{:?}", e)))?;
+                Ok(quote! { (#log_id, #param_type) })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok(quote! {
+            /// Types of the values the contract logs, by log id, from its ABI's
+            /// `loggedTypes`. Empty for ABIs in the flat format, which don't declare them.
+            pub fn log_types() -> Vec<(u64, ParamType)> {
+                vec![#( #log_types ),*]
+            }
+        })
+    }
+
+    fn output_types(&self) -> Result<TokenStream, Error> {
+        let functions = self
+            .abi
+            .iter()
+            .map(expand_output_types)
+            .collect::<Result<Vec<_>, Error>>()?;
+        Ok(quote! { #( #functions )* })
+    }
+
+    fn output_structs(&self) -> Result<TokenStream, Error> {
+        let mut structs = TokenStream::new();
+        if !self.named_outputs {
+            return Ok(structs);
+        }
+
+        for function in &self.abi {
+            if let Some(output_struct) = expand_output_struct(function)? {
+                structs.extend(output_struct);
+            }
+        }
+
+        Ok(structs)
+    }
+
     fn abi_structs(&self) -> Result<TokenStream, Error> {
         let mut structs = TokenStream::new();
 
@@ -214,17 +421,15 @@ impl Abigen {
             CustomType::Struct => "struct",
         };
 
-        let mut all_properties: Vec<&Property> = vec![];
+        // The custom type of a `Vec<struct Point>` is that of its elements
+        let mut all_properties: Vec<Property> = vec![];
         for function in abi {
-            for prop in &function.inputs {
-                all_properties.push(prop);
-            }
-            for prop in &function.outputs {
-                all_properties.push(prop);
+            for prop in function.inputs.iter().chain(&function.outputs) {
+                all_properties.push(vector_element(prop).unwrap_or_else(|| prop.clone()));
             }
         }
 
-        for prop in all_properties {
+        for prop in &all_properties {
             if prop.type_field.contains(type_string) {
                 // Top level struct
                 let custom_type_name = extract_custom_type_name_from_abi_property(prop, ty)
@@ -259,6 +464,10 @@ impl Abigen {
     fn get_inner_custom_properties(prop: &Property, ty: &str) -> Vec<Property> {
         let mut props = Vec::new();
 
+        if let Some(element) = vector_element(prop) {
+            return Abigen::get_inner_custom_properties(&element, ty);
+        }
+
         if prop.type_field.contains(ty) {
             props.push(prop.clone());
 
@@ -302,6 +511,373 @@ mod tests {
         let _bindings = Abigen::new("test", contract).unwrap().generate().unwrap();
     }
 
+    #[test]
+    fn function_generation_error_names_the_function() {
+        let contract = r#"
+        [
+            {
+                "type": "function",
+                "inputs": [{ "name": "arg", "type": "u32" }],
+                "name": "takes_u32",
+                "outputs": []
+            },
+            {
+                "type": "function",
+                "inputs": [{ "name": "arg", "type": "uint64" }],
+                "name": "takes_garbage",
+                "outputs": []
+            }
+        ]
+        "#;
+
+        let abigen = Abigen::new("test", contract).unwrap();
+        let err = abigen.functions().unwrap_err();
+        match &err {
+            Error::FunctionGeneration { name, source } => {
+                assert_eq!(name, "takes_garbage");
+                assert!(matches!(**source, Error::InvalidType(_)));
+            }
+            _ => panic!("unexpected error: {}", err),
+        }
+        assert!(err.to_string().contains("`takes_garbage`"));
+    }
+
+    #[test]
+    fn missing_abi_file() {
+        let err = Abigen::new("test", "path/to/missing_abi.json").unwrap_err();
+
+        assert!(matches!(err, Error::MissingData(_)));
+        assert!(err.to_string().contains("missing_abi.json"));
+    }
+
+    #[test]
+    fn abi_hash_constant() {
+        let contract = r#"
+        [
+            {
+                "type": "function",
+                "inputs": [{ "name": "arg", "type": "u32" }],
+                "name": "takes_u32",
+                "outputs": []
+            }
+        ]
+        "#;
+
+        let abigen = Abigen::new("test", contract).unwrap();
+        let expanded = abigen.expand().unwrap().to_string();
+        let constant = format!(
+            "pub const ABI_HASH : & 'static str = \"{}\"",
+            abigen.abi_hash()
+        );
+        assert!(expanded.contains(&constant));
+        assert!(expanded.contains("impl test {"));
+        assert_eq!(abigen.abi_hash().len(), 64);
+
+        let regenerated = Abigen::new("test", contract).unwrap();
+        assert_eq!(regenerated.abi_hash(), abigen.abi_hash());
+
+        let modified = contract.replace("u32", "u64");
+        let modified = Abigen::new("test", modified).unwrap();
+        assert_ne!(modified.abi_hash(), abigen.abi_hash());
+    }
+
+    #[test]
+    fn custom_module_name() {
+        let contract = r#"
+        [
+            {
+                "type":"contract",
+                "inputs":[
+                    {
+                        "name":"arg",
+                        "type":"u32"
+                    }
+                ],
+                "name":"takes_u32_returns_bool",
+                "outputs":[
+                    {
+                        "name":"",
+                        "type":"bool"
+                    }
+                ]
+            }
+        ]
+        "#;
+
+        let expanded = Abigen::new("test", contract)
+            .unwrap()
+            .module_name("my_custom_mod")
+            .expand()
+            .unwrap()
+            .to_string();
+
+        assert!(expanded.contains("pub use my_custom_mod :: * ;"));
+        assert!(expanded.contains("mod my_custom_mod {"));
+        assert!(!expanded.contains("test_mod"));
+
+        let expanded = Abigen::new("test", contract)
+            .unwrap()
+            .expand()
+            .unwrap()
+            .to_string();
+
+        assert!(expanded.contains("pub use test_mod :: * ;"));
+    }
+
+    #[test]
+    fn namespaced_module() {
+        let contract = r#"
+        [
+            {
+                "type":"contract",
+                "inputs":[
+                    {
+                        "name":"arg",
+                        "type":"u32"
+                    }
+                ],
+                "name":"takes_u32_returns_bool",
+                "outputs":[
+                    {
+                        "name":"",
+                        "type":"bool"
+                    }
+                ]
+            }
+        ]
+        "#;
+
+        let expanded = Abigen::new("MyContract", contract)
+            .unwrap()
+            .namespaced()
+            .expand()
+            .unwrap()
+            .to_string();
+
+        assert!(expanded.contains("pub mod my_contract {"));
+        assert!(!expanded.contains("pub use"));
+    }
+
+    #[test]
+    fn named_outputs_struct() {
+        let contract = r#"
+        [
+            {
+                "type":"function",
+                "inputs":[],
+                "name":"get_pair",
+                "outputs":[
+                    {
+                        "name":"count",
+                        "type":"u64"
+                    },
+                    {
+                        "name":"is_set",
+                        "type":"bool"
+                    }
+                ]
+            }
+        ]
+        "#;
+
+        let expanded = Abigen::new("test", contract)
+            .unwrap()
+            .named_outputs()
+            .expand()
+            .unwrap()
+            .to_string();
+
+        assert!(
+            expanded.contains("pub struct GetPairOutput { pub count : u64 , pub is_set : bool }")
+        );
+        assert!(expanded.contains("-> ContractCallHandler < GetPairOutput , S >"));
+
+        let expanded = Abigen::new("test", contract)
+            .unwrap()
+            .expand()
+            .unwrap()
+            .to_string();
+
+        assert!(!expanded.contains("GetPairOutput"));
+        assert!(expanded.contains("-> ContractCallHandler < (u64 , bool) , S >"));
+    }
+
+    #[test]
+    fn output_types_module() {
+        let contract = r#"
+        [
+            {
+                "type":"function",
+                "inputs":[],
+                "name":"get_pair",
+                "outputs":[
+                    {
+                        "name":"count",
+                        "type":"u64"
+                    },
+                    {
+                        "name":"label",
+                        "type":"str[4]"
+                    }
+                ]
+            }
+        ]
+        "#;
+
+        let expanded = Abigen::new("test", contract)
+            .unwrap()
+            .expand()
+            .unwrap()
+            .to_string();
+
+        assert!(expanded.contains("pub mod output_types {"));
+        assert!(expanded.contains(
+            "pub fn get_pair () -> Vec < ParamType > { vec ! [ParamType :: U64 , ParamType :: String (4)] }"
+        ));
+        assert!(expanded.contains(
+            "pub const GET_PAIR : & [ParamType] = & [ParamType :: U64 , ParamType :: String (4)] ;"
+        ));
+    }
+
+    #[test]
+    fn unit_output_is_no_output() {
+        let contract = |outputs: &str| {
+            format!(
+                r#"[{{ "type": "function", "inputs": [], "name": "noop", "outputs": {} }}]"#,
+                outputs
+            )
+        };
+        let expand = |abi: &str| {
+            let abigen = Abigen::new("test", abi).unwrap();
+            let expanded = abigen.expand().unwrap().to_string();
+            expanded.replace(abigen.abi_hash(), "")
+        };
+
+        let without_outputs = expand(&contract("[]"));
+        assert_eq!(
+            expand(&contract(r#"[{ "name": "", "type": "()" }]"#)),
+            without_outputs
+        );
+        assert_eq!(
+            expand(&contract(r#"[{ "name": "", "type": "( )" }]"#)),
+            without_outputs
+        );
+    }
+
+    #[test]
+    fn types_only() {
+        let contract = r#"
+        [
+            {
+                "type":"contract",
+                "inputs":[
+                    {
+                        "name":"value",
+                        "type":"struct MyStruct",
+                        "components": [
+                            {
+                                "name": "foo",
+                                "type": "u8"
+                            },
+                            {
+                                "name": "bar",
+                                "type": "bool"
+                            }
+                        ]
+                    }
+                ],
+                "name":"takes_struct",
+                "outputs":[]
+            }
+        ]
+        "#;
+
+        let expanded = Abigen::new("test", contract)
+            .unwrap()
+            .types_only()
+            .expand()
+            .unwrap()
+            .to_string();
+
+        assert!(expanded.contains("pub struct MyStruct"));
+        assert!(!expanded.contains("Provider"));
+        assert!(!expanded.contains("pub struct test"));
+        assert!(!expanded.contains("fn takes_struct (& self"));
+    }
+
+    #[test]
+    fn program_abi_format() {
+        let program_abi = r#"
+        {
+            "types": [
+                { "typeId": 0, "type": "bool", "components": null, "typeParameters": null },
+                {
+                    "typeId": 1,
+                    "type": "struct MyStruct",
+                    "components": [
+                        { "name": "foo", "type": 2, "typeArguments": null },
+                        { "name": "bar", "type": 0, "typeArguments": null }
+                    ],
+                    "typeParameters": null
+                },
+                { "typeId": 2, "type": "u8", "components": null, "typeParameters": null },
+                {
+                    "typeId": 3,
+                    "type": "[_; 2]",
+                    "components": [
+                        { "name": "__array_element", "type": 4, "typeArguments": null }
+                    ],
+                    "typeParameters": null
+                },
+                { "typeId": 4, "type": "u64", "components": null, "typeParameters": null }
+            ],
+            "functions": [
+                {
+                    "inputs": [
+                        { "name": "value", "type": 1, "typeArguments": null },
+                        { "name": "values", "type": 3, "typeArguments": null }
+                    ],
+                    "name": "takes_struct",
+                    "output": { "name": "", "type": 0, "typeArguments": null },
+                    "attributes": [{ "name": "storage", "arguments": ["read"] }]
+                }
+            ],
+            "loggedTypes": []
+        }
+        "#;
+
+        let flat_abi = r#"
+        [
+            {
+                "type": "function",
+                "inputs": [
+                    {
+                        "name": "value",
+                        "type": "struct MyStruct",
+                        "components": [
+                            { "name": "foo", "type": "u8" },
+                            { "name": "bar", "type": "bool" }
+                        ]
+                    },
+                    { "name": "values", "type": "[u64; 2]" }
+                ],
+                "name": "takes_struct",
+                "outputs": [{ "name": "", "type": "bool" }],
+                "attributes": ["storage(read)"]
+            }
+        ]
+        "#;
+
+        // Only the hash of the sources differs
+        let expand = |abi: &str| {
+            let abigen = Abigen::new("test", abi).unwrap();
+            let expanded = abigen.expand().unwrap().to_string();
+            expanded.replace(abigen.abi_hash(), "")
+        };
+
+        assert_eq!(expand(program_abi), expand(flat_abi));
+    }
+
     #[test]
     fn generates_bindings_two_args() {
         let contract = r#"
@@ -372,6 +948,74 @@ mod tests {
         let _bindings = contract.generate().unwrap();
     }
 
+    #[test]
+    fn balance_method() {
+        let abi = r#"
+        [
+            {
+                "type":"contract",
+                "inputs":[],
+                "name":"get_balance",
+                "outputs":[]
+            }
+        ]
+        "#;
+
+        let generates = |abi: &str| {
+            Abigen::new("test", abi)
+                .unwrap()
+                .expand()
+                .unwrap()
+                .to_string()
+                .contains("get_contract_balance")
+        };
+        assert!(generates(&abi.replace("get_balance", "takes_nothing")));
+        // The contract's own `get_balance` function isn't shadowed
+        assert!(!generates(abi));
+    }
+
+    #[test]
+    fn vector_of_structs() {
+        let contract = r#"
+        [
+            {
+                "type":"contract",
+                "inputs":[
+                    {
+                        "name":"points",
+                        "type":"Vec<struct Point>",
+                        "components": [
+                            {
+                                "name": "x",
+                                "type": "u64"
+                            },
+                            {
+                                "name": "y",
+                                "type": "u64"
+                            }
+                        ]
+                    }
+                ],
+                "name":"takes_points",
+                "outputs":[]
+            }
+        ]
+        "#;
+
+        let contract = Abigen::new("custom", contract).unwrap();
+
+        assert_eq!(1, contract.custom_structs.len());
+        assert!(contract.custom_structs.contains_key("Point"));
+
+        let expanded = contract.expand().unwrap().to_string();
+        assert!(expanded.contains("pub struct Point"));
+        assert!(expanded
+            .contains("pub fn takes_points (& self , points : :: std :: vec :: Vec < Point >)"));
+        assert!(expanded.contains(
+            "Token :: Vector (points . into_iter () . map (Tokenizable :: into_token) . collect ())"
+        ));
+    }
+
     #[test]
     fn multiple_custom_types() {
         let contract = r#"
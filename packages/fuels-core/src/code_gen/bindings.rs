@@ -1,7 +1,11 @@
 use crate::errors::Error;
 use crate::rustfmt;
 use proc_macro2::TokenStream;
-use std::{fs::File, io::Write, path::Path};
+use std::{
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+};
 
 /// Type-safe contract bindings generated by a `Builder`. This type can be
 /// either written to file or into a token stream for use in a procedural macro.
@@ -10,9 +14,25 @@ pub struct ContractBindings {
     pub tokens: TokenStream,
     /// The output options used for serialization.
     pub rustfmt: bool,
+    /// The edition `rustfmt` formats the bindings with.
+    pub rustfmt_edition: String,
+    /// The `rustfmt` configuration file to use, rather than one found nearby.
+    pub rustfmt_config: Option<PathBuf>,
 }
 
 impl ContractBindings {
+    /// Formats the bindings with the given edition rather than the default one, 2021.
+    pub fn rustfmt_edition<S: Into<String>>(mut self, edition: S) -> Self {
+        self.rustfmt_edition = edition.into();
+        self
+    }
+
+    /// Formats the bindings with the `rustfmt` configuration file at `path`.
+    pub fn rustfmt_config<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.rustfmt_config = Some(path.into());
+        self
+    }
+
     /// Writes the bindings to a given `Write`.
     pub fn write<W>(&self, mut w: W) -> Result<(), Error>
     where
@@ -22,7 +42,12 @@ impl ContractBindings {
             let raw = self.tokens.to_string();
 
             if self.rustfmt {
-                rustfmt::format(&raw).unwrap_or(raw)
+                rustfmt::format_with_config(
+                    &raw,
+                    &self.rustfmt_edition,
+                    self.rustfmt_config.as_deref(),
+                )
+                .unwrap_or(raw)
             } else {
                 raw
             }
@@ -47,3 +72,32 @@ impl ContractBindings {
         self.tokens
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quote::quote;
+
+    #[test]
+    fn deterministic_formatting() {
+        let bindings = ContractBindings {
+            tokens: quote! { pub struct Foo { a: u64, b: bool } },
+            rustfmt: true,
+            rustfmt_edition: rustfmt::DEFAULT_EDITION.to_string(),
+            rustfmt_config: None,
+        }
+        .rustfmt_edition("2018");
+
+        let mut first = vec![];
+        bindings.write(&mut first).unwrap();
+        let mut second = vec![];
+        bindings.write(&mut second).unwrap();
+
+        // Falling back to the unformatted tokens would keep the struct on one line
+        assert_eq!(
+            String::from_utf8(first.clone()).unwrap(),
+            "pub struct Foo {\n    a: u64,\n    b: bool,\n}\n"
+        );
+        assert_eq!(first, second);
+    }
+}
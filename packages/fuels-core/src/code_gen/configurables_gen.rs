@@ -0,0 +1,128 @@
+use crate::code_gen::custom_types_gen::{extract_custom_type_name_from_abi_property, CustomType};
+use crate::code_gen::docs_gen::expand_doc;
+use crate::errors::Error;
+use crate::json_abi::{parse_param, Configurable};
+use crate::types::expand_type;
+use crate::utils::{ident, safe_ident};
+use inflector::Inflector;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Functions used by the Abigen to expand the configurable constants of an ABI.
+
+/// Expands the configurable constants of a program into a `Configurables` builder with a
+/// setter per constant. The setters encode the values, which are then written at the
+/// constants' offsets in the bytecode, before the program is deployed.
+/// Nothing is generated if the program has no configurable constants.
+pub fn expand_configurables(configurables: &[Configurable]) -> Result<TokenStream, Error> {
+    if configurables.is_empty() {
+        return Ok(quote! {});
+    }
+
+    let setters = configurables
+        .iter()
+        .map(expand_setter)
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok(quote! {
+        /// Values of the contract's configurable constants, to write into its bytecode
+        /// before deploying it.
+        #[derive(Debug, Clone, Default, PartialEq, Eq)]
+        pub struct Configurables {
+            overrides: Vec<(u64, Vec<u8>)>,
+        }
+
+        impl Configurables {
+            pub fn new() -> Self {
+                Self::default()
+            }
+
+            #( #setters )*
+
+            /// The encoded value of each constant that was set, with its offset in the
+            /// bytecode.
+            pub fn overrides(&self) -> &[(u64, Vec<u8>)] {
+                &self.overrides
+            }
+
+            /// Writes the values of the constants that were set into `bytecode`. Fails if
+            /// one of them doesn't fit in it.
+            pub fn apply(&self, bytecode: &mut [u8]) -> Result<(), fuels_core::errors::Error> {
+                for (offset, value) in &self.overrides {
+                    let target = usize::try_from(*offset)
+                        .ok()
+                        .and_then(|start| Some(start..start.checked_add(value.len())?))
+                        .and_then(|range| bytecode.get_mut(range))
+                        .ok_or(fuels_core::errors::Error::InvalidData)?;
+                    target.copy_from_slice(value);
+                }
+                Ok(())
+            }
+        }
+    })
+}
+
+/// Expands the setter of a configurable constant, e.g. `set_max_supply` for `MAX_SUPPLY`.
+fn expand_setter(configurable: &Configurable) -> Result<TokenStream, Error> {
+    let name = safe_ident(&format!("set_{}", configurable.name.to_snake_case()));
+    let ty = expand_configurable_type(configurable)?;
+    let offset = configurable.offset;
+    let doc = expand_doc(&format!(
+        "Sets the `{}` configurable constant",
+        configurable.name
+    ));
+
+    Ok(quote! {
+        #doc
+        pub fn #name(mut self, value: #ty) -> Self {
+            let encoded = fuels_core::abi_encoder::ABIEncoder::new()
+                .encode(&[value.into_token()])
+                .expect("configurable values should be encodable");
+            self.overrides.push((#offset, encoded));
+            self
+        }
+    })
+}
+
+fn expand_configurable_type(configurable: &Configurable) -> Result<TokenStream, Error> {
+    let property = &configurable.property;
+    let custom_type = match property.type_field.split_whitespace().next() {
+        Some("struct") => Some(CustomType::Struct),
+        Some("enum") => Some(CustomType::Enum),
+        _ => None,
+    };
+
+    match custom_type {
+        Some(custom_type) => {
+            let name = extract_custom_type_name_from_abi_property(property, &custom_type)?;
+            let ident = ident(&name.to_class_case());
+            Ok(quote! { #ident })
+        }
+        None => expand_type(&parse_param(property)?),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuels_types::Property;
+
+    #[test]
+    fn expand_u64_configurable() {
+        let configurables = vec![Configurable {
+            name: "MAX_SUPPLY".to_string(),
+            property: Property {
+                name: "".to_string(),
+                type_field: "u64".to_string(),
+                components: None,
+            },
+            offset: 48,
+        }];
+
+        let expanded = expand_configurables(&configurables).unwrap().to_string();
+        assert!(expanded.contains("pub fn set_max_supply (mut self , value : u64) -> Self"));
+        assert!(expanded.contains("self . overrides . push ((48u64 , encoded))"));
+
+        assert!(expand_configurables(&[]).unwrap().is_empty());
+    }
+}
@@ -22,6 +22,9 @@ pub enum CustomType {
 /// that represents that same type as a Rust-native struct.
 pub fn expand_internal_struct(prop: &Property) -> Result<TokenStream, Error> {
     let components = prop.components.as_ref().unwrap();
+    if components.is_empty() {
+        return Err(Error::EmptyCompositeType(prop.type_field.clone()));
+    }
     let mut fields = Vec::with_capacity(components.len());
 
     // Holds a TokenStream representing the process of
@@ -35,6 +38,14 @@ pub fn expand_internal_struct(prop: &Property) -> Result<TokenStream, Error> {
     // `MyStruct::new_from_tokens()`.
     let mut args = Vec::new();
 
+    // Same as `args`, but returning an error instead of panicking
+    // on a mismatched token. Used by `MyStruct::try_from(token)`.
+    let mut try_args = Vec::new();
+
+    // Holds the name and type of each field, used to
+    // generate the `MyStructBuilder` setters.
+    let mut builder_fields = Vec::new();
+
     // For each component, we create two TokenStreams:
     // 1. A struct field declaration like `pub #field_name: #component_name`
     // 2. The creation of a token and its insertion into a vector of Tokens.
@@ -51,7 +62,11 @@ pub fn expand_internal_struct(prop: &Property) -> Result<TokenStream, Error> {
                 );
 
                 fields.push(quote! {pub #field_name: #struct_name});
+                builder_fields.push((field_name.clone(), quote! {#struct_name}));
                 args.push(quote! {#field_name: #struct_name::new_from_tokens(&tokens[#idx..])});
+                try_args.push(quote! {
+                    #field_name: <#struct_name as std::convert::TryFrom<Token>>::try_from(tokens[#idx].clone())?
+                });
                 struct_fields_tokens.push(quote! { tokens.push(self.#field_name.into_token()) });
                 param_types
                     .push(quote! { types.push(ParamType::Struct(#struct_name::param_types())) });
@@ -81,11 +96,15 @@ pub fn expand_internal_struct(prop: &Property) -> Result<TokenStream, Error> {
 
                 // Field declaration
                 fields.push(quote! { pub #field_name: #ty});
+                builder_fields.push((field_name.clone(), ty.clone()));
 
                 // `new_from_token()` instantiations
                 args.push(quote! {
                     #field_name: <#ty>::from_token(tokens[#idx].clone()).expect("Failed to run `new_from_tokens()` for custom struct, make sure to pass tokens in the right order and right types" )
                 });
+                try_args.push(quote! {
+                    #field_name: <#ty>::from_token(tokens[#idx].clone()).map_err(|e| fuels_core::errors::Error::InvalidType(e.0))?
+                });
 
                 // Token creation and insertion
                 match param_type {
@@ -106,15 +125,25 @@ pub fn expand_internal_struct(prop: &Property) -> Result<TokenStream, Error> {
         }
     }
 
-    let name = ident(
-        &extract_custom_type_name_from_abi_property(prop, &CustomType::Struct)?.to_class_case(),
-    );
+    let name_str =
+        extract_custom_type_name_from_abi_property(prop, &CustomType::Struct)?.to_class_case();
+    let name = ident(&name_str);
+    let builder_name = ident(&format!("{}Builder", name_str));
+
+    let field_count = components.len();
+
+    let builder_field_names: Vec<_> = builder_fields.iter().map(|(n, _)| n).collect();
+    let builder_field_types: Vec<_> = builder_fields.iter().map(|(_, t)| t).collect();
+    let missing_field_messages: Vec<_> = builder_fields
+        .iter()
+        .map(|(n, _)| format!("Field `{}` of `{}` wasn't set", n, name_str))
+        .collect();
 
     // Actual creation of the struct, using the inner TokenStreams from above
     // to produce the TokenStream that represents the whole struct + methods
     // declaration.
     Ok(quote! {
-        #[derive(Clone, Debug, Default, Eq, PartialEq)]
+        #[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
         pub struct #name {
             #( #fields ),*
         }
@@ -139,6 +168,30 @@ pub fn expand_internal_struct(prop: &Property) -> Result<TokenStream, Error> {
                 }
             }
 
+            pub fn builder() -> #builder_name {
+                #builder_name::default()
+            }
+
+        }
+
+        #[derive(Clone, Debug, Default)]
+        pub struct #builder_name {
+            #( #builder_field_names: Option<#builder_field_types> ),*
+        }
+
+        impl #builder_name {
+            #(
+                pub fn #builder_field_names(mut self, #builder_field_names: #builder_field_types) -> Self {
+                    self.#builder_field_names = Some(#builder_field_names);
+                    self
+                }
+            )*
+
+            pub fn build(self) -> Result<#name, fuels_core::errors::Error> {
+                Ok(#name {
+                    #( #builder_field_names: self.#builder_field_names.ok_or_else(|| fuels_core::errors::Error::MissingData(#missing_field_messages.to_string()))? ),*
+                })
+            }
         }
 
         impl fuels_core::Detokenize for #name {
@@ -156,6 +209,24 @@ pub fn expand_internal_struct(prop: &Property) -> Result<TokenStream, Error> {
                 }
             }
         }
+
+        impl std::convert::TryFrom<Token> for #name {
+            type Error = fuels_core::errors::Error;
+
+            fn try_from(token: Token) -> Result<Self, Self::Error> {
+                let tokens = match token {
+                    Token::Struct(tokens) => tokens,
+                    other => return Err(fuels_core::errors::Error::InvalidType(format!("Expected a struct token for `{}`, got {:?}", #name_str, other))),
+                };
+                if tokens.len() != #field_count {
+                    return Err(fuels_core::errors::Error::InvalidType(format!("`{}` has {} fields, got {} tokens", #name_str, #field_count, tokens.len())));
+                }
+
+                Ok(Self {
+                    #( #try_args ),*
+                })
+            }
+        }
     })
 }
 
@@ -163,18 +234,41 @@ pub fn expand_internal_struct(prop: &Property) -> Result<TokenStream, Error> {
 /// that represents that same type as a Rust-native enum.
 pub fn expand_internal_enum(name: &str, prop: &Property) -> Result<TokenStream, Error> {
     let components = prop.components.as_ref().unwrap();
+    if components.is_empty() {
+        return Err(Error::EmptyCompositeType(prop.type_field.clone()));
+    }
     let mut fields = Vec::with_capacity(components.len());
 
     // Holds a TokenStream representing the process of
     // creating an enum [`Token`].
     let mut enum_selector_builder = Vec::new();
 
-    let name = ident(&name.to_class_case());
+    // Holds the ABI name of each variant, indexed by discriminant.
+    // Used by `MyEnum::variant_index("name")`.
+    let mut variant_names = Vec::new();
+
+    // Holds the TokenStreams mapping each variant to its discriminant and back.
+    // Used by `MyEnum::discriminant()` and `MyEnum::from_discriminant()`.
+    let mut discriminants = Vec::new();
+    let mut from_discriminants = Vec::new();
+
+    // Holds the `ParamType` of each variant, which the encoder needs to pad the enum
+    // to the size of its biggest variant.
+    let mut variant_types = Vec::new();
+
+    let name_str = name.to_class_case();
+    let name = ident(&name_str);
 
     for (discriminant, component) in components.iter().enumerate() {
         let field_name = ident(&component.name.to_class_case());
+        variant_names.push(component.name.to_class_case());
 
         let param_type = parse_param(component)?;
+        variant_types.push(
+            format!("ParamType::{}", param_type)
+                .parse::<TokenStream>()
+                .unwrap(),
+        );
         match param_type {
             // Case where an enum takes another enum
             ParamType::Enum(_params) => {
@@ -196,7 +290,13 @@ pub fn expand_internal_enum(name: &str, prop: &Property) -> Result<TokenStream,
                 // Token creation
                 enum_selector_builder.push(quote! {
                     #name::#field_name(value) => (#discriminant as u8, Token::#param_type_string(value))
-                })
+                });
+
+                let discriminant = discriminant as u64;
+                discriminants.push(quote! { #name::#field_name(_) => #discriminant });
+                from_discriminants.push(quote! {
+                    (#discriminant, Token::#param_type_string(value)) => Ok(#name::#field_name(value))
+                });
             }
         }
     }
@@ -205,7 +305,7 @@ pub fn expand_internal_enum(name: &str, prop: &Property) -> Result<TokenStream,
     // to produce the TokenStream that represents the whole enum + methods
     // declaration.
     Ok(quote! {
-        #[derive(Clone, Debug, Eq, PartialEq)]
+        #[derive(Clone, Debug, Eq, Hash, PartialEq)]
         pub enum #name {
             #( #fields ),*
         }
@@ -217,9 +317,26 @@ pub fn expand_internal_enum(name: &str, prop: &Property) -> Result<TokenStream,
                     #( #enum_selector_builder, )*
                 };
 
-                let selector = (dis, tok);
+                let selector = (dis, tok, vec![#( #variant_types ),*]);
                 Token::Enum(Box::new(selector))
             }
+
+            pub fn variant_index(name: &str) -> Option<u8> {
+                [#( #variant_names ),*].iter().position(|variant| *variant == name).map(|index| index as u8)
+            }
+
+            pub fn discriminant(&self) -> u64 {
+                match self {
+                    #( #discriminants, )*
+                }
+            }
+
+            pub fn from_discriminant(discriminant: u64, payload: Token) -> Result<Self, fuels_core::errors::Error> {
+                match (discriminant, payload) {
+                    #( #from_discriminants, )*
+                    (discriminant, payload) => Err(fuels_core::errors::Error::InvalidType(format!("`{}` has no variant {} holding {:?}", #name_str, discriminant, payload))),
+                }
+            }
         }
     })
 }
@@ -332,7 +449,7 @@ mod tests {
         let result = expand_internal_enum("matcha_tea", &p);
         let expected = TokenStream::from_str(
             r#"
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub enum MatchaTea {
     LongIsland(u64),
     MoscowMule(bool)
@@ -343,9 +460,25 @@ impl MatchaTea {
             MatchaTea::LongIsland(value) => (0usize as u8, Token::U64(value)),
             MatchaTea::MoscowMule(value) => (1usize as u8, Token::Bool(value)),
         };
-        let selector = (dis, tok);
+        let selector = (dis, tok, vec![ParamType::U64, ParamType::Bool]);
         Token::Enum(Box::new(selector))
     }
+    pub fn variant_index(name: &str) -> Option<u8> {
+        ["LongIsland", "MoscowMule"].iter().position(|variant| *variant == name).map(|index| index as u8)
+    }
+    pub fn discriminant(&self) -> u64 {
+        match self {
+            MatchaTea::LongIsland(_) => 0u64,
+            MatchaTea::MoscowMule(_) => 1u64,
+        }
+    }
+    pub fn from_discriminant(discriminant: u64, payload: Token) -> Result<Self, fuels_core::errors::Error> {
+        match (discriminant, payload) {
+            (0u64, Token::U64(value)) => Ok(MatchaTea::LongIsland(value)),
+            (1u64, Token::Bool(value)) => Ok(MatchaTea::MoscowMule(value)),
+            (discriminant, payload) => Err(fuels_core::errors::Error::InvalidType(format!("`{}` has no variant {} holding {:?}", "MatchaTea", discriminant, payload))),
+        }
+    }
 }
 "#,
         );
@@ -418,7 +551,7 @@ impl MatchaTea {
         };
         let expected = TokenStream::from_str(
             r#"
-#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
 pub struct Cocktail {
     pub long_island: bool,
     pub cosmopolitan: u64,
@@ -442,6 +575,36 @@ impl Cocktail {
     pub fn new_from_tokens(tokens: &[Token]) -> Self {
         Self { long_island : < bool > :: from_token (tokens [0usize] . clone ()) . expect ("Failed to run `new_from_tokens()` for custom struct, make sure to pass tokens in the right order and right types") , cosmopolitan : < u64 > :: from_token (tokens [1usize] . clone ()) . expect ("Failed to run `new_from_tokens()` for custom struct, make sure to pass tokens in the right order and right types") , mojito : < u32 > :: from_token (tokens [2usize] . clone ()) . expect ("Failed to run `new_from_tokens()` for custom struct, make sure to pass tokens in the right order and right types") }
     }
+    pub fn builder() -> CocktailBuilder {
+        CocktailBuilder::default()
+    }
+}
+#[derive(Clone, Debug, Default)]
+pub struct CocktailBuilder {
+    long_island: Option<bool>,
+    cosmopolitan: Option<u64>,
+    mojito: Option<u32>
+}
+impl CocktailBuilder {
+    pub fn long_island(mut self, long_island: bool) -> Self {
+        self.long_island = Some(long_island);
+        self
+    }
+    pub fn cosmopolitan(mut self, cosmopolitan: u64) -> Self {
+        self.cosmopolitan = Some(cosmopolitan);
+        self
+    }
+    pub fn mojito(mut self, mojito: u32) -> Self {
+        self.mojito = Some(mojito);
+        self
+    }
+    pub fn build(self) -> Result<Cocktail, fuels_core::errors::Error> {
+        Ok(Cocktail {
+            long_island: self.long_island.ok_or_else(|| fuels_core::errors::Error::MissingData("Field `long_island` of `Cocktail` wasn't set".to_string()))?,
+            cosmopolitan: self.cosmopolitan.ok_or_else(|| fuels_core::errors::Error::MissingData("Field `cosmopolitan` of `Cocktail` wasn't set".to_string()))?,
+            mojito: self.mojito.ok_or_else(|| fuels_core::errors::Error::MissingData("Field `mojito` of `Cocktail` wasn't set".to_string()))?
+        })
+    }
 }
 impl fuels_core::Detokenize for Cocktail {
     fn from_tokens(mut tokens: Vec<Token>) -> Result<Self, fuels_core::InvalidOutputType> {
@@ -456,6 +619,23 @@ impl fuels_core::Detokenize for Cocktail {
             Err(fuels_core::InvalidOutputType("Struct token doesn't contain inner tokens. This shouldn't happen.".to_string()))
         }
     }
+}
+impl std::convert::TryFrom<Token> for Cocktail {
+    type Error = fuels_core::errors::Error;
+    fn try_from(token: Token) -> Result<Self, Self::Error> {
+        let tokens = match token {
+            Token::Struct(tokens) => tokens,
+            other => return Err(fuels_core::errors::Error::InvalidType(format!("Expected a struct token for `{}`, got {:?}", "Cocktail", other))),
+        };
+        if tokens.len() != 3usize {
+            return Err(fuels_core::errors::Error::InvalidType(format!("`{}` has {} fields, got {} tokens", "Cocktail", 3usize, tokens.len())));
+        }
+        Ok(Self {
+            long_island: <bool>::from_token(tokens[0usize].clone()).map_err(|e| fuels_core::errors::Error::InvalidType(e.0))?,
+            cosmopolitan: <u64>::from_token(tokens[1usize].clone()).map_err(|e| fuels_core::errors::Error::InvalidType(e.0))?,
+            mojito: <u32>::from_token(tokens[2usize].clone()).map_err(|e| fuels_core::errors::Error::InvalidType(e.0))?
+        })
+    }
 }
         "#,
         );
@@ -495,7 +675,7 @@ impl fuels_core::Detokenize for Cocktail {
         };
         let expected = TokenStream::from_str(
             r#"
-#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
 pub struct Cocktail {
     pub long_island: Shaker,
     pub mojito: u32
@@ -516,6 +696,30 @@ impl Cocktail {
     pub fn new_from_tokens(tokens: &[Token]) -> Self {
         Self { long_island : Shaker :: new_from_tokens (& tokens [0usize ..]) , mojito : < u32 > :: from_token (tokens [1usize] . clone ()) . expect ("Failed to run `new_from_tokens()` for custom struct, make sure to pass tokens in the right order and right types") }
     }
+    pub fn builder() -> CocktailBuilder {
+        CocktailBuilder::default()
+    }
+}
+#[derive(Clone, Debug, Default)]
+pub struct CocktailBuilder {
+    long_island: Option<Shaker>,
+    mojito: Option<u32>
+}
+impl CocktailBuilder {
+    pub fn long_island(mut self, long_island: Shaker) -> Self {
+        self.long_island = Some(long_island);
+        self
+    }
+    pub fn mojito(mut self, mojito: u32) -> Self {
+        self.mojito = Some(mojito);
+        self
+    }
+    pub fn build(self) -> Result<Cocktail, fuels_core::errors::Error> {
+        Ok(Cocktail {
+            long_island: self.long_island.ok_or_else(|| fuels_core::errors::Error::MissingData("Field `long_island` of `Cocktail` wasn't set".to_string()))?,
+            mojito: self.mojito.ok_or_else(|| fuels_core::errors::Error::MissingData("Field `mojito` of `Cocktail` wasn't set".to_string()))?
+        })
+    }
 }
 impl fuels_core::Detokenize for Cocktail {
     fn from_tokens(mut tokens: Vec<Token>) -> Result<Self, fuels_core::InvalidOutputType> {
@@ -530,6 +734,22 @@ impl fuels_core::Detokenize for Cocktail {
             Err(fuels_core::InvalidOutputType("Struct token doesn't contain inner tokens. This shouldn't happen.".to_string()))
         }
     }
+}
+impl std::convert::TryFrom<Token> for Cocktail {
+    type Error = fuels_core::errors::Error;
+    fn try_from(token: Token) -> Result<Self, Self::Error> {
+        let tokens = match token {
+            Token::Struct(tokens) => tokens,
+            other => return Err(fuels_core::errors::Error::InvalidType(format!("Expected a struct token for `{}`, got {:?}", "Cocktail", other))),
+        };
+        if tokens.len() != 2usize {
+            return Err(fuels_core::errors::Error::InvalidType(format!("`{}` has {} fields, got {} tokens", "Cocktail", 2usize, tokens.len())));
+        }
+        Ok(Self {
+            long_island: <Shaker as std::convert::TryFrom<Token>>::try_from(tokens[0usize].clone())?,
+            mojito: <u32>::from_token(tokens[1usize].clone()).map_err(|e| fuels_core::errors::Error::InvalidType(e.0))?
+        })
+    }
 }
         "#,
         );
@@ -538,6 +758,49 @@ impl fuels_core::Detokenize for Cocktail {
         assert_eq!(result.unwrap().to_string(), expected);
     }
 
+    #[test]
+    fn test_expand_internal_struct_with_b256_array() {
+        let p = Property {
+            name: String::from("unused"),
+            type_field: String::from("struct Receipt"),
+            components: Some(vec![Property {
+                name: String::from("hashes"),
+                type_field: String::from("[b256; 4]"),
+                components: None,
+            }]),
+        };
+
+        let result = expand_internal_struct(&p).unwrap().to_string();
+
+        assert!(result.contains("pub hashes : [[u8 ; 32] ; 4]"));
+        assert!(result
+            .contains("types . push (ParamType :: Array (Box :: new (ParamType :: B256) , 4))"));
+    }
+
+    #[test]
+    fn test_expand_internal_struct_without_components() {
+        let p = Property {
+            name: String::from("unused"),
+            type_field: String::from("struct EmptyCocktail"),
+            components: Some(vec![]),
+        };
+
+        let err = expand_internal_struct(&p).unwrap_err();
+        assert!(
+            matches!(err, Error::EmptyCompositeType(ref name) if name == "struct EmptyCocktail")
+        );
+        assert!(err.to_string().contains("struct EmptyCocktail"));
+
+        let p = Property {
+            name: String::from("unused"),
+            type_field: String::from("enum EmptyMatchaTea"),
+            components: Some(vec![]),
+        };
+
+        let err = expand_internal_enum("EmptyMatchaTea", &p).unwrap_err();
+        assert!(err.to_string().contains("enum EmptyMatchaTea"));
+    }
+
     #[test]
     #[should_panic(expected = "not implemented")]
     fn test_expand_internal_struct_with_enum() {
@@ -2,14 +2,14 @@ use crate::abi_encoder::ABIEncoder;
 use crate::code_gen::custom_types_gen::{extract_custom_type_name_from_abi_property, CustomType};
 use crate::code_gen::docs_gen::expand_doc;
 use crate::errors::Error;
-use crate::json_abi::{parse_param, ABIParser};
+use crate::json_abi::{parse_param, vector_element, ABIParser};
 use crate::types::expand_type;
 use crate::utils::{ident, safe_ident};
 use crate::{ParamType, Selector};
 use fuels_types::{Function, Property};
 use inflector::Inflector;
-use proc_macro2::{Literal, TokenStream};
-use quote::quote;
+use proc_macro2::{Ident, Literal, TokenStream};
+use quote::{quote, ToTokens};
 use std::collections::HashMap;
 
 /// Functions used by the Abigen to expand functions defined in an ABI spec.
@@ -17,25 +17,33 @@ use std::collections::HashMap;
 /// Transforms a function defined in [`Function`] into a [`TokenStream`]
 /// that represents that same function signature as a Rust-native function
 /// declaration.
-/// The actual logic inside the function is the function `method_hash` under
+/// The actual logic inside the function is the function `method_call` under
 /// [`Contract`], which is responsible for encoding the function selector
 /// and the function parameters that will be used in the actual contract call.
+/// The call decodes logs with the `log_types` of `contract_name`, the generated
+/// contract type.
 ///
 /// [`Contract`]: crate::contract::Contract
 pub fn expand_function(
     function: &Function,
+    contract_name: &Ident,
     abi_parser: &ABIParser,
     custom_enums: &HashMap<String, Property>,
     custom_structs: &HashMap<String, Property>,
+    named_outputs: bool,
 ) -> Result<TokenStream, Error> {
     let name = safe_ident(&function.name);
+    let calldata_name = safe_ident(&format!("{}_calldata", function.name));
     let fn_signature = abi_parser.build_fn_selector(&function.name, &function.inputs);
 
     let encoded = ABIEncoder::encode_function_selector(fn_signature?.as_bytes());
 
     let tokenized_signature = expand_selector(encoded);
-    let tokenized_output = expand_fn_outputs(&function.outputs)?;
-    let result = quote! { ContractCall<#tokenized_output> };
+    let tokenized_output = match output_struct_name(function) {
+        Some(struct_name) if named_outputs => ident(&struct_name).into_token_stream(),
+        _ => expand_fn_outputs(&function.outputs)?,
+    };
+    let result = quote! { ContractCallHandler<#tokenized_output, S> };
 
     let (input, arg) = expand_function_arguments(function, custom_enums, custom_structs)?;
 
@@ -44,10 +52,42 @@ pub fn expand_function(
         function.name,
         hex::encode(encoded)
     ));
+    let calldata_doc = expand_doc(&format!(
+        "Encodes a call to the contract's `{}` function, without executing it",
+        function.name
+    ));
+
+    let output_params = expand_output_params(function);
+    let output_params_token = quote! { &[#( #output_params ),*] };
+
+    // Functions that only read storage are simulated by `run()`
+    let execution_mode = if is_read_only(function) {
+        quote! { .read_only() }
+    } else {
+        quote! {}
+    };
 
+    Ok(quote! {
+        #doc
+        pub fn #name(&self #input) -> #result {
+            Contract::method_call(&self.provider, self.contract_id, &self.wallet,
+                #tokenized_signature, #output_params_token, #arg)
+                .with_log_types(#contract_name::log_types())
+                #execution_mode
+        }
+
+        #calldata_doc
+        pub fn #calldata_name(&self #input) -> Result<Vec<u8>, fuels_rs::core::errors::Error> {
+            #arg.and_then(|args| Contract::calldata(#tokenized_signature, &args))
+        }
+    })
+}
+
+/// Expands the `ParamType`s of a function's outputs into the Rust code declaring them.
+fn expand_output_params(function: &Function) -> Vec<TokenStream> {
     // Here we turn `ParamType`s into a custom stringified version that's identical
     // to how we would declare a `ParamType` in Rust code. Which will then
-    // be used to be tokenized and passed onto `method_hash()`.
+    // be used to be tokenized and passed onto `method_call()`.
     let mut output_params = vec![];
     for output in &function.outputs {
         let mut param_type_str: String = "ParamType::".to_owned();
@@ -58,23 +98,126 @@ pub fn expand_function(
 
         output_params.push(tok);
     }
+    output_params
+}
 
-    let output_params_token = quote! { &[#( #output_params ),*] };
+/// Expands a function returning the `ParamType`s of `function`'s outputs, named after
+/// it, so that its outputs can be decoded at runtime without parsing the ABI again.
+/// When the outputs are all primitive types, they're also expanded as a constant named
+/// after the function in upper case, e.g. to pass them to `ABIDecoder::decode`.
+pub fn expand_output_types(function: &Function) -> Result<TokenStream, Error> {
+    let name = safe_ident(&function.name);
+    let output_params = expand_output_params(function);
+    let doc = expand_doc(&format!(
+        "Types of the values returned by the contract's `{}` function",
+        function.name
+    ));
+
+    // Composite types hold a `Vec` or a `Box`, which can't be built in a constant
+    let params = function
+        .outputs
+        .iter()
+        .map(parse_param)
+        .collect::<Result<Vec<_>, Error>>()?;
+    let is_primitive = |param: &ParamType| {
+        !matches!(
+            param,
+            ParamType::Array(..)
+                | ParamType::Vector(_)
+                | ParamType::Struct(_)
+                | ParamType::Enum(_)
+                | ParamType::Tuple(_)
+        )
+    };
+    let constant = if params.iter().all(is_primitive) {
+        let const_name = ident(&function.name.to_screaming_snake_case());
+        quote! {
+            #doc
+            pub const #const_name: &[ParamType] = &[#( #output_params ),*];
+        }
+    } else {
+        quote! {}
+    };
 
     Ok(quote! {
         #doc
-        pub fn #name(&self #input) -> #result {
-            Contract::method_hash(&self.provider, self.contract_id, &self.wallet,
-                #tokenized_signature, #output_params_token, #arg).expect("method not found (this should never happen)")
+        pub fn #name() -> Vec<ParamType> {
+            vec![#( #output_params ),*]
         }
+
+        #constant
     })
 }
 
+/// Whether the function is annotated as reading storage without writing it.
+fn is_read_only(function: &Function) -> bool {
+    let attributes: Vec<String> = function
+        .attributes
+        .iter()
+        .flatten()
+        .map(|a| a.replace(' ', ""))
+        .collect();
+
+    attributes.iter().any(|a| a == "storage(read)")
+        && !attributes.iter().any(|a| a.contains("write"))
+}
+
 fn expand_selector(selector: Selector) -> TokenStream {
     let bytes = selector.iter().copied().map(Literal::u8_unsuffixed);
     quote! { [#( #bytes ),*] }
 }
 
+/// Name of the struct holding the outputs of a function returning several named
+/// values, e.g. `GetPairOutput` for `get_pair`.
+pub fn output_struct_name(function: &Function) -> Option<String> {
+    if function.outputs.len() > 1 && function.outputs.iter().all(|o| !o.name.is_empty()) {
+        Some(format!("{}Output", function.name.to_class_case()))
+    } else {
+        None
+    }
+}
+
+/// Expands the struct returned instead of a tuple when a function has several named
+/// outputs, see `Abigen::named_outputs`. Each output becomes a field of the same name.
+pub fn expand_output_struct(function: &Function) -> Result<Option<TokenStream>, Error> {
+    let name = match output_struct_name(function) {
+        Some(name) => ident(&name),
+        None => return Ok(None),
+    };
+
+    let field_names: Vec<_> = function
+        .outputs
+        .iter()
+        .map(|o| safe_ident(&o.name.to_snake_case()))
+        .collect();
+    let field_types = function
+        .outputs
+        .iter()
+        .map(|o| expand_fn_outputs(std::slice::from_ref(o)))
+        .collect::<Result<Vec<_>, Error>>()?;
+    let field_count = field_names.len();
+
+    Ok(Some(quote! {
+        #[derive(Clone, Debug, PartialEq)]
+        pub struct #name {
+            #( pub #field_names: #field_types ),*
+        }
+
+        impl fuels_core::Detokenize for #name {
+            fn from_tokens(tokens: Vec<Token>) -> Result<Self, fuels_core::InvalidOutputType> {
+                if tokens.len() != #field_count {
+                    return Err(fuels_core::InvalidOutputType(format!("Expected {} outputs, got {}", #field_count, tokens.len())));
+                }
+                let mut tokens = tokens.into_iter();
+
+                Ok(Self {
+                    #( #field_names: <#field_types as fuels_core::Detokenize>::from_tokens(vec![tokens.next().unwrap()])? ),*
+                })
+            }
+        }
+    }))
+}
+
 /// Expands the output of a function, i.e. what comes after `->` in a function
 /// signature.
 fn expand_fn_outputs(outputs: &[Property]) -> Result<TokenStream, Error> {
@@ -84,30 +227,67 @@ fn expand_fn_outputs(outputs: &[Property]) -> Result<TokenStream, Error> {
             // If it's a struct as the type of a function's output, use its
             // tokenized name only. Otherwise, parse and expand.
             // The non-expansion should happen to enums as well
-            if outputs[0].type_field.contains("struct ") {
+            if let Some(element) = vector_element(&outputs[0]) {
+                if element.type_field.contains("struct ") {
+                    let name =
+                        extract_custom_type_name_from_abi_property(&element, &CustomType::Struct)?;
+                    let ident = ident(&name);
+                    return Ok(quote! { ::std::vec::Vec<#ident> });
+                }
+                expand_output_type(&outputs[0])
+            } else if outputs[0].type_field.contains("struct ") {
                 let tok: proc_macro2::TokenStream =
                     extract_custom_type_name_from_abi_property(&outputs[0], &CustomType::Struct)?
                         .parse()
                         .unwrap();
                 Ok(tok)
             } else {
-                expand_type(&parse_param(&outputs[0])?)
+                expand_output_type(&outputs[0])
             }
         }
         _ => {
             let types = outputs
                 .iter()
-                .map(|param| expand_type(&parse_param(param)?))
+                .map(expand_output_type)
                 .collect::<Result<Vec<_>, Error>>()?;
             Ok(quote! { (#( #types ),*) })
         }
     }
 }
 
+/// Expands the type of a single output, naming it in the error if it's an
+/// empty composite type.
+fn expand_output_type(output: &Property) -> Result<TokenStream, Error> {
+    expand_type(&parse_param(output)?).map_err(|e| name_empty_composite_type(e, output))
+}
+
+/// Replaces the generic name of an empty composite type in `e`, e.g. `struct`, with its
+/// type in the ABI, e.g. `struct Empty`, found in `property` or its components.
+fn name_empty_composite_type(e: Error, property: &Property) -> Error {
+    match e {
+        Error::EmptyCompositeType(_) => {
+            let empty = find_empty_composite(property).unwrap_or(property);
+            Error::EmptyCompositeType(empty.type_field.clone())
+        }
+        e => e,
+    }
+}
+
+fn find_empty_composite(property: &Property) -> Option<&Property> {
+    match property.components.as_deref() {
+        Some([]) => Some(property),
+        Some(components) => components.iter().find_map(find_empty_composite),
+        None => None,
+    }
+}
+
 /// Expands the arguments in a function declaration and the same arguments as input
 /// to a function call. For instance:
 /// 1. The `my_arg: u32` in `pub fn my_func(my_arg: u32) -> ()`
-/// 2. The `my_arg.into_token()` in `another_fn_call(my_arg.into_token())`
+/// 2. The `Ok(vec![my_arg.into_token()])` in `another_fn_call(Ok(vec![my_arg.into_token()]))`
+///
+/// The call arguments are a `Result` since tokenizing fixed-size strings fails if they
+/// don't have the length of their type.
 fn expand_function_arguments(
     fun: &Function,
     custom_enums: &HashMap<String, Property>,
@@ -115,6 +295,7 @@ fn expand_function_arguments(
 ) -> Result<(TokenStream, TokenStream), Error> {
     let mut args = vec![];
     let mut call_args = vec![];
+    let mut fixed_strings = vec![];
 
     for (i, param) in fun.inputs.iter().enumerate() {
         // For each [`Property`] in a function input we expand:
@@ -125,7 +306,15 @@ fn expand_function_arguments(
         // TokenStream representing the name of the argument
         let name = expand_input_name(i, &param.name);
 
-        let opt_custom_type = match param.type_field.split_whitespace().collect::<Vec<_>>()[0] {
+        // The custom type of a `Vec<struct Point>` argument is that of its elements
+        let element = vector_element(param);
+        let custom_param = element.as_ref().unwrap_or(param);
+
+        let opt_custom_type = match custom_param
+            .type_field
+            .split_whitespace()
+            .collect::<Vec<_>>()[0]
+        {
             "enum" => Some(&CustomType::Enum),
             "struct" => Some(&CustomType::Struct),
             _ => None,
@@ -134,15 +323,19 @@ fn expand_function_arguments(
         let rust_custom_name = if let Some(c) = opt_custom_type {
             match c {
                 CustomType::Enum => {
-                    let name =
-                        extract_custom_type_name_from_abi_property(param, opt_custom_type.unwrap())
-                            .unwrap();
+                    let name = extract_custom_type_name_from_abi_property(
+                        custom_param,
+                        opt_custom_type.unwrap(),
+                    )
+                    .unwrap();
                     custom_enums.get(&name)
                 }
                 CustomType::Struct => {
-                    let name =
-                        extract_custom_type_name_from_abi_property(param, opt_custom_type.unwrap())
-                            .unwrap();
+                    let name = extract_custom_type_name_from_abi_property(
+                        custom_param,
+                        opt_custom_type.unwrap(),
+                    )
+                    .unwrap();
                     custom_structs.get(&name)
                 }
             }
@@ -150,24 +343,57 @@ fn expand_function_arguments(
             None
         };
 
-        // TokenStream representing the type of the argument
-        let ty = expand_input_param(fun, &param.name, &parse_param(param)?, &rust_custom_name)?;
+        let param_type = parse_param(param)?;
+
+        // TokenStream representing the type of the argument.
+        // Fixed-size strings are borrowed, so that callers don't have to allocate.
+        let ty = match param_type {
+            ParamType::String(_) => quote! { impl AsRef<str> },
+            _ => expand_input_param(fun, &param.name, &param_type, &rust_custom_name)
+                .map_err(|e| name_empty_composite_type(e, param))?,
+        };
 
         // Add the TokenStream to argument declarations
         args.push(quote! { #name: #ty });
 
-        // This `name` TokenStream is also added to the call arguments
-        call_args.push(name);
+        // This `name` TokenStream is also added to the call arguments.
+        // A Rust `String` turns into a fixed-size `str[n]` token by default,
+        // so dynamic strings are tokenized explicitly, as are vectors, which
+        // would otherwise be fixed-size arrays. Fixed-size strings are checked
+        // against their length, which is the only way tokenizing can fail.
+        match param_type {
+            ParamType::String(len) => {
+                fixed_strings.push(call_args.len());
+                let len = Literal::usize_unsuffixed(len);
+                call_args.push(quote! { Token::fixed_string(#name.as_ref(), #len) })
+            }
+            ParamType::DynamicString => call_args.push(quote! { Token::DynamicString(#name) }),
+            ParamType::Vector(_) => call_args.push(quote! {
+                Token::Vector(#name.into_iter().map(Tokenizable::into_token).collect())
+            }),
+            _ => call_args.push(quote! { #name.into_token() }),
+        }
     }
 
     // The final TokenStream of the argument declaration in a function declaration
     let args = quote! { #( , #args )* };
 
     // The final TokenStream of the arguments being passed in a function call
-    // It'll look like `&[my_arg.into_token(), another_arg.into_token()]`
-    // as the [`Contract`] `method_hash` function expects a slice of Tokens
-    // in order to encode the call.
-    let call_args = quote! { &[ #(#call_args.into_token(), )* ] };
+    // It'll look like `Ok(vec![my_arg.into_token(), another_arg.into_token()])`
+    // as the [`Contract`] `method_call` function expects the Tokens, or the error
+    // tokenizing them, in order to encode the call.
+    let call_args = if fixed_strings.is_empty() {
+        quote! { Ok(vec![ #(#call_args, )* ]) }
+    } else {
+        let call_args = call_args.iter().enumerate().map(|(i, arg)| {
+            if fixed_strings.contains(&i) {
+                arg.clone()
+            } else {
+                quote! { Ok(#arg) }
+            }
+        });
+        quote! { vec![ #(#call_args, )* ].into_iter().collect::<Result<Vec<Token>, _>>() }
+    };
 
     Ok((args, call_args))
 }
@@ -196,13 +422,18 @@ fn expand_input_param(
     custom_struct_name: &Option<&Property>,
 ) -> Result<TokenStream, Error> {
     match kind {
-        ParamType::Array(ty, _) => {
+        // Arrays of `b256` keep their size, like in `expand_type`
+        ParamType::Array(ty, _) if **ty == ParamType::B256 => expand_type(kind),
+        ParamType::Array(ty, _) | ParamType::Vector(ty) => {
             let ty = expand_input_param(fun, param, ty, custom_struct_name)?;
             Ok(quote! {
                 ::std::vec::Vec<#ty>
             })
         }
-        ParamType::Enum(_) => {
+        ParamType::Enum(variants) => {
+            if variants.is_empty() {
+                return Err(Error::EmptyCompositeType("enum".to_string()));
+            }
             let ident = ident(
                 &extract_custom_type_name_from_abi_property(
                     custom_struct_name.unwrap(),
@@ -212,7 +443,10 @@ fn expand_input_param(
             );
             Ok(quote! { #ident })
         }
-        ParamType::Struct(_) => {
+        ParamType::Struct(members) => {
+            if members.is_empty() {
+                return Err(Error::EmptyCompositeType("struct".to_string()));
+            }
             let ident = ident(
                 &extract_custom_type_name_from_abi_property(
                     custom_struct_name.unwrap(),
@@ -236,6 +470,7 @@ mod tests {
     fn test_expand_function_simple() {
         let mut the_function = Function {
             type_field: "unused".to_string(),
+            attributes: None,
             inputs: vec![],
             name: "HelloWorld".to_string(),
             outputs: vec![],
@@ -247,23 +482,30 @@ mod tests {
         });
         let result = expand_function(
             &the_function,
+            &ident("MyContract"),
             &ABIParser::new(),
             &Default::default(),
             &Default::default(),
+            false,
         );
         let expected = TokenStream::from_str(
             r#"
 #[doc = "Calls the contract's `HelloWorld` (0x0000000097d4de45) function"]
-pub fn HelloWorld(&self, bimbam: bool) -> ContractCall<()> {
-    Contract::method_hash(
+pub fn HelloWorld(&self, bimbam: bool) -> ContractCallHandler<(), S> {
+    Contract::method_call(
         &self.provider,
         self.contract_id,
         &self.wallet,
         [0, 0, 0, 0, 151, 212, 222, 69],
         &[],
-        &[bimbam.into_token() ,]
+        Ok(vec![bimbam.into_token() ,])
     )
-    .expect("method not found (this should never happen)")
+    .with_log_types(MyContract::log_types())
+}
+#[doc = "Encodes a call to the contract's `HelloWorld` function, without executing it"]
+pub fn HelloWorld_calldata(&self, bimbam: bool) -> Result<Vec<u8>, fuels_rs::core::errors::Error> {
+    Ok(vec![bimbam.into_token() ,])
+        .and_then(|args| Contract::calldata([0, 0, 0, 0, 151, 212, 222, 69], &args))
 }
         "#,
         );
@@ -271,9 +513,55 @@ pub fn HelloWorld(&self, bimbam: bool) -> ContractCall<()> {
         assert_eq!(result.unwrap().to_string(), expected);
     }
     #[test]
+    fn test_expand_read_only_function() {
+        let the_function = Function {
+            type_field: "function".to_string(),
+            attributes: Some(vec!["storage(read)".to_string()]),
+            inputs: vec![],
+            name: "get_counter".to_string(),
+            outputs: vec![],
+        };
+        let result = expand_function(
+            &the_function,
+            &ident("MyContract"),
+            &ABIParser::new(),
+            &Default::default(),
+            &Default::default(),
+            false,
+        );
+        let expected = TokenStream::from_str(
+            r#"
+#[doc = "Calls the contract's `get_counter` (0x00000000a785fe65) function"]
+pub fn get_counter(&self) -> ContractCallHandler<(), S> {
+    Contract::method_call(
+        &self.provider,
+        self.contract_id,
+        &self.wallet,
+        [0, 0, 0, 0, 167, 133, 254, 101],
+        &[],
+        Ok(vec![])
+    )
+    .with_log_types(MyContract::log_types())
+    .read_only()
+}
+#[doc = "Encodes a call to the contract's `get_counter` function, without executing it"]
+pub fn get_counter_calldata(&self) -> Result<Vec<u8>, fuels_rs::core::errors::Error> {
+    Ok(vec![]).and_then(|args| Contract::calldata([0, 0, 0, 0, 167, 133, 254, 101], &args))
+}
+        "#,
+        );
+        let expected = expected.unwrap().to_string();
+        assert_eq!(result.unwrap().to_string(), expected);
+
+        let mut writing_function = the_function;
+        writing_function.attributes = Some(vec!["storage(read, write)".to_string()]);
+        assert!(!is_read_only(&writing_function));
+    }
+    #[test]
     fn test_expand_function_complex() {
         let mut the_function = Function {
             type_field: "function".to_string(),
+            attributes: None,
             name: "hello_world".to_string(),
             inputs: vec![],
             outputs: vec![
@@ -354,7 +642,14 @@ pub fn HelloWorld(&self, bimbam: bool) -> ContractCall<()> {
             },
         );
         let abi_parser = ABIParser::new();
-        let result = expand_function(&the_function, &abi_parser, &custom_enums, &custom_structs);
+        let result = expand_function(
+            &the_function,
+            &ident("MyContract"),
+            &abi_parser,
+            &custom_enums,
+            &custom_structs,
+            false,
+        );
         // Some more editing was required because it is not rustfmt-compatible (adding/removing parentheses or commas)
         let expected = TokenStream::from_str(
             r#"
@@ -362,8 +657,8 @@ pub fn HelloWorld(&self, bimbam: bool) -> ContractCall<()> {
 pub fn hello_world(
     &self,
     the_only_allowed_input: SomeWeirdFrenchCuisine
-) -> ContractCall<((bool , u64 ,) , (bool, u64 ,))> {
-    Contract::method_hash(
+) -> ContractCallHandler<((bool , u64 ,) , (bool, u64 ,)), S> {
+    Contract::method_call(
         &self.provider,
         self.contract_id,
         &self.wallet,
@@ -371,9 +666,19 @@ pub fn hello_world(
         &[
             ParamType::Struct(vec![ParamType::Bool, ParamType::U64]),
             ParamType::Enum([Bool , U64])] ,
-            &[the_only_allowed_input . into_token () ,]
+            Ok(vec![the_only_allowed_input . into_token () ,])
     )
-    .expect("method not found (this should never happen)")
+    .with_log_types(MyContract::log_types())
+}
+#[doc = "Encodes a call to the contract's `hello_world` function, without executing it"]
+pub fn hello_world_calldata(
+    &self,
+    the_only_allowed_input: SomeWeirdFrenchCuisine
+) -> Result<Vec<u8>, fuels_rs::core::errors::Error> {
+    Ok(vec![the_only_allowed_input . into_token () ,]).and_then(|args| Contract::calldata(
+        [0, 0, 0, 0, 118, 178, 90, 36],
+        &args
+    ))
 }
         "#,
         );
@@ -560,6 +865,7 @@ pub fn hello_world(
         // All arguments are here
         let mut the_function = Function {
             type_field: "".to_string(),
+            attributes: None,
             inputs: vec![],
             name: "".to_string(),
             outputs: vec![],
@@ -569,14 +875,70 @@ pub fn hello_world(
         let result = expand_function_arguments(&the_function, &hm, &hm);
         let (args, call_args) = result.unwrap();
         let result = format!("({},{})", args, call_args);
-        let expected = "(, some_argument : u32,& [some_argument . into_token () ,])";
+        let expected = "(, some_argument : u32,Ok (vec ! [some_argument . into_token () ,]))";
         assert_eq!(result, expected);
     }
     #[test]
+    fn test_expand_function_arguments_arities() {
+        let hm: HashMap<String, Property> = HashMap::new();
+        let mut the_function = Function {
+            type_field: "function".to_string(),
+            attributes: None,
+            inputs: vec![],
+            name: "pip_pop".to_string(),
+            outputs: vec![],
+        };
+
+        let (args, call_args) = expand_function_arguments(&the_function, &hm, &hm).unwrap();
+        assert_eq!(format!("({},{})", args, call_args), "(,Ok (vec ! []))");
+
+        the_function.inputs.push(Property {
+            name: "bim".to_string(),
+            type_field: String::from("u64"),
+            components: None,
+        });
+        let (args, call_args) = expand_function_arguments(&the_function, &hm, &hm).unwrap();
+        assert_eq!(
+            format!("({},{})", args, call_args),
+            "(, bim : u64,Ok (vec ! [bim . into_token () ,]))"
+        );
+
+        the_function.inputs.push(Property {
+            name: "bam".to_string(),
+            type_field: String::from("bool"),
+            components: None,
+        });
+        the_function.inputs.push(Property {
+            name: "boum".to_string(),
+            type_field: String::from("str[4]"),
+            components: None,
+        });
+        let (args, call_args) = expand_function_arguments(&the_function, &hm, &hm).unwrap();
+        assert_eq!(
+            args.to_string(),
+            ", bim : u64 , bam : bool , boum : impl AsRef < str >"
+        );
+        // Tokenizing fixed-size strings can fail, so the tokens are collected into a `Result`
+        let expected = TokenStream::from_str(
+            r#"
+vec![
+    Ok(bim.into_token()),
+    Ok(bam.into_token()),
+    Token::fixed_string(boum.as_ref(), 4),
+]
+.into_iter()
+.collect::<Result<Vec<Token>, _>>()
+        "#,
+        )
+        .unwrap();
+        assert_eq!(call_args.to_string(), expected.to_string());
+    }
+    #[test]
     fn test_expand_function_arguments_primitive() {
         let hm: HashMap<String, Property> = HashMap::new();
         let mut the_function = Function {
             type_field: "function".to_string(),
+            attributes: None,
             inputs: vec![],
             name: "pip_pop".to_string(),
             outputs: vec![],
@@ -590,18 +952,22 @@ pub fn hello_world(
         let result = expand_function_arguments(&the_function, &hm, &hm);
         let (args, call_args) = result.unwrap();
         let result = format!("({},{})", args, call_args);
-        assert_eq!(result, "(, bim_bam : u64,& [bim_bam . into_token () ,])");
+        assert_eq!(
+            result,
+            "(, bim_bam : u64,Ok (vec ! [bim_bam . into_token () ,]))"
+        );
 
         the_function.inputs[0].name = String::from("");
         let result = expand_function_arguments(&the_function, &hm, &hm);
         let (args, call_args) = result.unwrap();
         let result = format!("({},{})", args, call_args);
-        assert_eq!(result, "(, p0 : u64,& [p0 . into_token () ,])");
+        assert_eq!(result, "(, p0 : u64,Ok (vec ! [p0 . into_token () ,]))");
     }
     #[test]
     fn test_expand_function_arguments_composite() {
         let mut function = Function {
             type_field: "zig_zag".to_string(),
+            attributes: None,
             inputs: vec![],
             name: "PipPopFunction".to_string(),
             outputs: vec![],
@@ -609,8 +975,11 @@ pub fn hello_world(
         function.inputs.push(Property {
             name: "bim_bam".to_string(),
             type_field: String::from("struct CarMaker"),
-            // Not parsed, so can be empty but not None
-            components: Some(vec![]),
+            components: Some(vec![Property {
+                name: "wheels".to_string(),
+                type_field: "u64".to_string(),
+                components: None,
+            }]),
         });
         let mut custom_structs = HashMap::new();
         custom_structs.insert(
@@ -625,10 +994,34 @@ pub fn hello_world(
         let result = expand_function_arguments(&function, &custom_structs, &custom_structs);
         let (args, call_args) = result.unwrap();
         let result = format!("({},{})", args, call_args);
-        let expected = r#"(, bim_bam : CarMaker,& [bim_bam . into_token () ,])"#;
+        let expected = r#"(, bim_bam : CarMaker,Ok (vec ! [bim_bam . into_token () ,]))"#;
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_expand_function_arguments_empty_composite() {
+        let empty = Property {
+            name: "unused".to_string(),
+            type_field: "struct Empty".to_string(),
+            components: Some(vec![]),
+        };
+        let function = Function {
+            type_field: "function".to_string(),
+            attributes: None,
+            inputs: vec![Property {
+                name: "nothing".to_string(),
+                ..empty.clone()
+            }],
+            name: "takes_nothing".to_string(),
+            outputs: vec![],
+        };
+        let custom_structs = HashMap::from([("Empty".to_string(), empty)]);
+
+        let err =
+            expand_function_arguments(&function, &HashMap::new(), &custom_structs).unwrap_err();
+        assert!(matches!(err, Error::EmptyCompositeType(ref name) if name == "struct Empty"));
+    }
+
     // --- expand_input_name ---
     #[test]
     fn test_expand_input_name() {
@@ -658,6 +1051,10 @@ pub fn hello_world(
         let array_type = ParamType::Array(Box::new(ParamType::U64), 10);
         let result = expand_input_param(&Function::default(), "unused", &array_type, &None);
         assert_eq!(result.unwrap().to_string(), ":: std :: vec :: Vec < u64 >");
+
+        let b256_array = ParamType::Array(Box::new(ParamType::B256), 4);
+        let result = expand_input_param(&Function::default(), "unused", &b256_array, &None);
+        assert_eq!(result.unwrap().to_string(), "[[u8 ; 32] ; 4]");
     }
     #[test]
     fn test_expand_input_param_struct_name() {
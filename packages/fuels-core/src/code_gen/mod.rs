@@ -2,6 +2,7 @@
 //! from a fuel/sway ABI.
 pub mod abigen;
 pub mod bindings;
+pub mod configurables_gen;
 pub mod custom_types_gen;
 pub mod docs_gen;
 pub mod functions_gen;
@@ -5,6 +5,9 @@ pub const DEFAULT_GAS_LIMIT: u64 = 1_000_000;
 pub const DEFAULT_GAS_PRICE: u64 = 0;
 pub const DEFAULT_BYTE_PRICE: u64 = 0;
 pub const DEFAULT_MATURITY: u32 = 0;
+// Multiplier applied to the gas used by a dry-run when estimating a gas limit, in
+// basis points: 10_000 keeps the estimate as is, 11_000 adds 10% to it.
+pub const DEFAULT_GAS_MARGIN: u64 = 11_000;
 
 pub const WORD_SIZE: usize = core::mem::size_of::<Word>();
 
@@ -1,16 +1,25 @@
 use core::fmt;
 use core::str::Utf8Error;
 pub type Result<T> = core::result::Result<T, Error>;
+use fuel_asm::InstructionResult;
+pub use fuel_asm::PanicReason;
+use fuel_tx::Receipt;
 use std::net;
 use strum::ParseError;
 use thiserror::Error;
 
-use crate::InvalidOutputType;
+use crate::{InvalidOutputType, ParamType};
 
 #[derive(Debug)]
 pub enum CodecError {
     InvalidData,
     Utf8Error(Utf8Error),
+    /// Decoding the value of type `param` starting at byte `offset` failed.
+    Decode {
+        param: ParamType,
+        offset: usize,
+        reason: String,
+    },
 }
 
 impl fmt::Display for CodecError {
@@ -57,6 +66,45 @@ pub enum Error {
     ContractCallError(String),
     #[error("Wallet error: {0}")]
     WalletError(String),
+    #[error("Node error: {0}")]
+    Node(String),
+    #[error("Gas limit {0} is higher than the maximum of {1} per transaction")]
+    GasLimitTooHigh(u64, u64),
+    #[error("Invalid gas margin {0}, it must be at least 1")]
+    InvalidGasMargin(f64),
+    #[error("Type `{0}` has no members")]
+    EmptyCompositeType(String),
+    #[error("Conflicting ABI definitions: {0}")]
+    ABIConflict(String),
+    #[error("Signature verification failed: {0}")]
+    SignatureVerification(String),
+    #[error("Function takes {expected} arguments, got {got}")]
+    ArgumentCountMismatch { expected: usize, got: usize },
+    #[error("Invalid address: {0}")]
+    InvalidAddress(String),
+    #[error("Invalid private key: {0}")]
+    InvalidPrivateKey(String),
+    #[error("Not supported by the node: {0}")]
+    Unsupported(String),
+    #[error("Failed to generate function `{name}`: {source}")]
+    FunctionGeneration { name: String, source: Box<Error> },
+    #[error("The VM panicked: {0:?}")]
+    Panic(PanicReason),
+    #[error("Failed to decode {param:?} at byte {offset}: {reason}")]
+    DecodeError {
+        param: ParamType,
+        offset: usize,
+        reason: String,
+    },
+}
+
+/// Returns the reason of the first VM panic among `receipts`, if any. The `Panic`
+/// receipt's reason holds the VM's reason code along with the instruction that panicked.
+pub fn decode_panic(receipts: &[Receipt]) -> Option<PanicReason> {
+    receipts.iter().find_map(|receipt| match receipt {
+        Receipt::Panic { reason, .. } => Some(*InstructionResult::from(*reason).reason()),
+        _ => None,
+    })
 }
 
 impl From<CodecError> for Error {
@@ -64,6 +112,15 @@ impl From<CodecError> for Error {
         match err {
             CodecError::InvalidData => Error::InvalidData,
             CodecError::Utf8Error(e) => Error::Utf8Error(e),
+            CodecError::Decode {
+                param,
+                offset,
+                reason,
+            } => Error::DecodeError {
+                param,
+                offset,
+                reason,
+            },
         }
     }
 }
@@ -84,3 +141,23 @@ impl From<std::io::Error> for Error {
         Error::ContractCallError(err.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuel_tx::ContractId;
+
+    #[test]
+    fn decode_overflow_panic() {
+        let receipts = vec![
+            Receipt::panic(ContractId::zeroed(), 0x05 << 56, 16, 8),
+            Receipt::panic(ContractId::zeroed(), 0x02 << 56, 24, 8),
+        ];
+        assert_eq!(
+            decode_panic(&receipts),
+            Some(PanicReason::ArithmeticOverflow)
+        );
+
+        assert_eq!(decode_panic(&[]), None);
+    }
+}
@@ -0,0 +1,118 @@
+//! Entry points for fuzzing the SDK, meant to be wired into `cargo-fuzz` targets, e.g.:
+//!
+//! ```ignore
+//! fuzz_target!(|data: &[u8]| {
+//!     let _ = fuels_core::fuzz::decode_arbitrary(data);
+//! });
+//! ```
+use crate::abi_decoder::ABIDecoder;
+use crate::errors::Error;
+use crate::{ParamType, Token};
+
+/// Deepest nesting of the types generated from the fuzzer's data.
+const MAX_DEPTH: usize = 4;
+
+/// Largest number of types, array and string length, or number of members generated.
+const MAX_LEN: usize = 8;
+
+/// Interprets the start of `data` as a sequence of `ParamType`s and decodes the rest of it
+/// according to them, like the return data of a malformed node response would be.
+/// It never panics, data that can't be decoded is reported as an error.
+pub fn decode_arbitrary(data: &[u8]) -> Result<Vec<Token>, Error> {
+    let mut data = data;
+
+    let count = next_len(&mut data)? + 1;
+    let types = (0..count)
+        .map(|_| next_param_type(&mut data, 0))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(ABIDecoder::new().decode(&types, data)?)
+}
+
+fn next_byte(data: &mut &[u8]) -> Result<u8, Error> {
+    let (byte, rest) = data.split_first().ok_or(Error::InvalidData)?;
+    *data = rest;
+    Ok(*byte)
+}
+
+fn next_len(data: &mut &[u8]) -> Result<usize, Error> {
+    Ok(next_byte(data)? as usize % MAX_LEN)
+}
+
+fn next_param_type(data: &mut &[u8], depth: usize) -> Result<ParamType, Error> {
+    let tag = next_byte(data)? % 14;
+
+    // Past the maximum depth, composite types become words
+    if tag >= 9 && depth >= MAX_DEPTH {
+        return Ok(ParamType::U64);
+    }
+
+    let param_type = match tag {
+        0 => ParamType::U8,
+        1 => ParamType::U16,
+        2 => ParamType::U32,
+        3 => ParamType::U64,
+        4 => ParamType::Bool,
+        5 => ParamType::Byte,
+        6 => ParamType::B256,
+        7 => ParamType::String(next_len(data)?),
+        8 => ParamType::DynamicString,
+        9 => {
+            let len = next_len(data)?;
+            ParamType::Array(Box::new(next_param_type(data, depth + 1)?), len)
+        }
+        13 => ParamType::Vector(Box::new(next_param_type(data, depth + 1)?)),
+        _ => {
+            let len = next_len(data)?;
+            let members = (0..len)
+                .map(|_| next_param_type(data, depth + 1))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            match tag {
+                10 => ParamType::Struct(members),
+                11 => ParamType::Enum(members),
+                _ => ParamType::Tuple(members),
+            }
+        }
+    };
+
+    Ok(param_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_arbitrary_valid_data() {
+        // One `u64`, then its value
+        let data = [0, 3, 0, 0, 0, 0, 0, 0, 0, 42];
+
+        let tokens = decode_arbitrary(&data).unwrap();
+        assert_eq!(tokens, vec![Token::U64(42)]);
+    }
+
+    #[test]
+    fn decode_arbitrary_bad_data() {
+        let bad_inputs: Vec<Vec<u8>> = vec![
+            // Nothing to read the types from
+            vec![],
+            // Two types announced, only one given
+            vec![1, 3],
+            // A `u64` without its value
+            vec![0, 3, 0, 0, 0],
+            // A dynamic string claiming to be as long as possible
+            vec![0, 8, 255, 255, 255, 255, 255, 255, 255, 255],
+            // An enum without variants
+            vec![0, 11, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+            // A `str[3]` that isn't UTF-8
+            vec![0, 7, 3, 255, 255, 255, 0, 0, 0, 0, 0],
+            // Arrays nested deeper than the maximum depth, without their elements
+            [vec![0], [9, 7].repeat(10)].concat(),
+        ];
+
+        for data in bad_inputs {
+            assert!(decode_arbitrary(&data).is_err(), "{:?} decoded", data);
+        }
+    }
+}
@@ -1,9 +1,10 @@
-use crate::Token;
 use crate::{abi_decoder::ABIDecoder, abi_encoder::ABIEncoder, errors::Error, ParamType};
-use fuels_types::{JsonABI, Property};
+use crate::{Selector, Token};
+use fuels_types::{Function, JsonABI, ProgramABI, Property, TypeApplication, TypeDeclaration};
 use hex::FromHex;
 use itertools::Itertools;
 use serde_json;
+use std::collections::HashMap;
 use std::str;
 use std::str::FromStr;
 
@@ -11,6 +12,18 @@ pub struct ABIParser {
     fn_selector: Option<Vec<u8>>,
 }
 
+/// Summary of a function exposed by an ABI, see `ABIParser::list_functions`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionInfo {
+    pub name: String,
+    /// Canonical signature, as used to compute the function selector.
+    pub signature: String,
+    /// Name and type of each argument.
+    pub inputs: Vec<(String, String)>,
+    /// Type of each output.
+    pub outputs: Vec<String>,
+}
+
 impl Default for ABIParser {
     fn default() -> Self {
         Self::new()
@@ -64,7 +77,7 @@ impl ABIParser {
     ///     assert_eq!(encoded, expected_encode);
     /// ```
     pub fn encode(&mut self, abi: &str, fn_name: &str, values: &[String]) -> Result<String, Error> {
-        let parsed_abi: JsonABI = serde_json::from_str(abi)?;
+        let parsed_abi = parse_json_abi(abi)?;
 
         let entry = parsed_abi.iter().find(|e| e.name == fn_name);
 
@@ -77,6 +90,13 @@ impl ABIParser {
 
         let entry = entry.unwrap();
 
+        if entry.inputs.len() != values.len() {
+            return Err(Error::ArgumentCountMismatch {
+                expected: entry.inputs.len(),
+                got: values.len(),
+            });
+        }
+
         let mut encoder = ABIEncoder::new_with_fn_selector(
             self.build_fn_selector(fn_name, &entry.inputs)?.as_bytes(),
         );
@@ -153,6 +173,35 @@ impl ABIParser {
         Ok(format!("{}{}", encoded_fn_selector, encoded_params))
     }
 
+    /// Encodes a call to `fn_name` with the given arguments, already tokenized,
+    /// i.e. the function selector followed by the encoded arguments.
+    /// This is what the node expects as calldata when crafting a raw call.
+    pub fn encode_function_call(
+        &self,
+        abi: &JsonABI,
+        fn_name: &str,
+        tokens: &[Token],
+    ) -> Result<Vec<u8>, Error> {
+        let entry = abi.iter().find(|e| e.name == fn_name).ok_or_else(|| {
+            Error::InvalidName(format!("couldn't find function name: {}", fn_name))
+        })?;
+
+        let params = entry
+            .inputs
+            .iter()
+            .map(parse_param)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut encoder = ABIEncoder::new_with_fn_selector(
+            self.build_fn_selector(fn_name, &entry.inputs)?.as_bytes(),
+        );
+
+        let mut encoded_call = encoder.function_selector.to_vec();
+        encoded_call.extend(encoder.encode_arguments(&params, tokens)?);
+
+        Ok(encoded_call)
+    }
+
     /// Helper function to return the encoded function selector.
     /// It must already be encoded.
     pub fn get_encoded_function_selector(&self) -> String {
@@ -222,7 +271,12 @@ impl ABIParser {
                 Ok(Token::B256(s))
             }
             ParamType::Array(t, _) => Ok(self.tokenize_array(trimmed_value, &*t)?),
+            ParamType::Vector(t) => match self.tokenize_array(trimmed_value, &*t)? {
+                Token::Array(tokens) => Ok(Token::Vector(tokens)),
+                other => Ok(other),
+            },
             ParamType::String(_) => Ok(Token::String(trimmed_value.to_string())),
+            ParamType::DynamicString => Ok(Token::DynamicString(trimmed_value.to_string())),
             ParamType::Struct(struct_params) => {
                 Ok(self.tokenize_struct(trimmed_value, struct_params)?)
             }
@@ -232,7 +286,11 @@ impl ABIParser {
 
                 let token = self.tokenize(&s[discriminant], value)?;
 
-                Ok(Token::Enum(Box::new((discriminant as u8, token))))
+                Ok(Token::Enum(Box::new((
+                    discriminant as u8,
+                    token,
+                    s.clone(),
+                ))))
             }
             ParamType::Tuple(_tuple_params) => {
                 todo!("Tuple tokenization for the ABI CLI tool not implemented yet")
@@ -240,6 +298,48 @@ impl ABIParser {
         }
     }
 
+    /// Parses a literal value of a primitive type, e.g. from a CLI argument, into a
+    /// `Token`. Integers are read as decimal and `b256` values as 32 bytes of hex,
+    /// with or without the `0x` prefix. Composite types go through `tokenize`.
+    pub fn token_from_str(param: &ParamType, s: &str) -> Result<Token, Error> {
+        let value = s.trim();
+        let invalid = |e: &dyn std::fmt::Display| {
+            Error::InvalidType(format!("Couldn't parse `{}` as `{}`: {}", value, param, e))
+        };
+
+        match param {
+            ParamType::U8 => value.parse().map(Token::U8).map_err(|e| invalid(&e)),
+            ParamType::U16 => value.parse().map(Token::U16).map_err(|e| invalid(&e)),
+            ParamType::U32 => value.parse().map(Token::U32).map_err(|e| invalid(&e)),
+            ParamType::U64 => value.parse().map(Token::U64).map_err(|e| invalid(&e)),
+            ParamType::Byte => value.parse().map(Token::Byte).map_err(|e| invalid(&e)),
+            ParamType::Bool => value.parse().map(Token::Bool).map_err(|e| invalid(&e)),
+            ParamType::B256 => {
+                let bytes =
+                    Vec::from_hex(value.trim_start_matches("0x")).map_err(|e| invalid(&e))?;
+                let b256 = bytes.try_into().map_err(|bytes: Vec<u8>| {
+                    invalid(&format!("expected 32 bytes, got {}", bytes.len()))
+                })?;
+                Ok(Token::B256(b256))
+            }
+            ParamType::String(len) => {
+                if value.len() != *len {
+                    return Err(invalid(&format!(
+                        "expected {} bytes, got {}",
+                        len,
+                        value.len()
+                    )));
+                }
+                Ok(Token::String(value.to_string()))
+            }
+            ParamType::DynamicString => Ok(Token::DynamicString(value.to_string())),
+            _ => Err(Error::InvalidType(format!(
+                "`{}` isn't a primitive type",
+                param
+            ))),
+        }
+    }
+
     /// Creates a struct `Token` from an array of parameter types and a string of values.
     /// I.e. it takes a string containing values "value_1, value_2, value_3" and an array
     /// of `ParamType` containing the type of each value, in order:
@@ -409,9 +509,20 @@ impl ABIParser {
         fn_name: &str,
         value: &'a [u8],
     ) -> Result<Vec<Token>, Error> {
-        let parsed_abi: JsonABI = serde_json::from_str(abi)?;
+        let parsed_abi = parse_json_abi(abi)?;
 
-        let entry = parsed_abi.iter().find(|e| e.name == fn_name);
+        self.decode_function_output(&parsed_abi, fn_name, value)
+    }
+
+    /// Same as `decode`, but takes an already parsed ABI. Looks up the outputs of
+    /// `fn_name` and decodes `data` according to them.
+    pub fn decode_function_output(
+        &self,
+        abi: &JsonABI,
+        fn_name: &str,
+        data: &[u8],
+    ) -> Result<Vec<Token>, Error> {
+        let entry = abi.iter().find(|e| e.name == fn_name);
 
         if entry.is_none() {
             return Err(Error::InvalidName(format!(
@@ -427,7 +538,7 @@ impl ABIParser {
             Ok(params) => {
                 let mut decoder = ABIDecoder::new();
 
-                Ok(decoder.decode(&params, value)?)
+                Ok(decoder.decode(&params, data)?)
             }
             Err(e) => Err(e),
         }
@@ -486,10 +597,95 @@ impl ABIParser {
         Ok(result)
     }
 
+    /// Returns the canonical signature of an ABI function, e.g.
+    /// `takes_u32_returns_bool(u32)`. Structs and enums are expanded into
+    /// `s(<field_1>,...,<field_n>)`.
+    pub fn function_signature(&self, function: &Function) -> Result<String, Error> {
+        self.build_fn_selector(&function.name, &function.inputs)
+    }
+
+    /// Lists the name, signature, and argument and output types of every function in `abi`.
+    pub fn list_functions(&self, abi: &JsonABI) -> Vec<FunctionInfo> {
+        abi.iter()
+            .map(|function| FunctionInfo {
+                name: function.name.clone(),
+                signature: format!(
+                    "{}({})",
+                    function.name,
+                    function
+                        .inputs
+                        .iter()
+                        .map(|param| self.build_fn_selector_params(param))
+                        .join(",")
+                ),
+                inputs: function
+                    .inputs
+                    .iter()
+                    .map(|param| (param.name.clone(), param.type_field.clone()))
+                    .collect(),
+                outputs: function
+                    .outputs
+                    .iter()
+                    .map(|param| param.type_field.clone())
+                    .collect(),
+            })
+            .collect()
+    }
+
+    /// Merges two ABIs, e.g. built from several forc artifacts, into one holding the
+    /// functions of both. Functions defined in both must be identical, and so must
+    /// custom types sharing the same name, otherwise this fails with `Error::ABIConflict`.
+    pub fn merge(&self, a: JsonABI, b: JsonABI) -> Result<JsonABI, Error> {
+        let mut custom_types = HashMap::new();
+        for function in a.iter().chain(b.iter()) {
+            for param in function.inputs.iter().chain(function.outputs.iter()) {
+                collect_custom_types(param, &mut custom_types)?;
+            }
+        }
+
+        let mut merged = a;
+        for function in b {
+            let selector = self.function_selector(&function)?;
+
+            let mut duplicate = false;
+            for existing in &merged {
+                let same_selector = self.function_selector(existing)? == selector;
+                if existing.name != function.name && !same_selector {
+                    continue;
+                }
+                if *existing != function {
+                    return Err(Error::ABIConflict(format!(
+                        "function `{}` is defined as both `{}` and `{}`",
+                        function.name,
+                        self.function_signature(existing)?,
+                        self.function_signature(&function)?
+                    )));
+                }
+                duplicate = true;
+            }
+
+            if !duplicate {
+                merged.push(function);
+            }
+        }
+
+        Ok(merged)
+    }
+
+    /// Returns the 8-byte selector the node uses to dispatch calls to `function`,
+    /// derived from its canonical signature.
+    pub fn function_selector(&self, function: &Function) -> Result<Selector, Error> {
+        let signature = self.function_signature(function)?;
+        Ok(ABIEncoder::encode_function_selector(signature.as_bytes()))
+    }
+
     fn build_fn_selector_params(&self, param: &Property) -> String {
         let mut result: String = String::new();
 
-        if param.type_field.contains("struct ") || param.type_field.contains("enum ") {
+        if let Some(element) = vector_element(param) {
+            // Will return `"Vec<element>"`.
+            result.push_str(&format!("Vec<{}>", self.build_fn_selector_params(&element)));
+        } else if param.type_field.contains("struct ") || param.type_field.contains("enum ") {
             // Custom type, need to break down inner fields
             // Will return `"s(field_1,field_2,...,field_n)"`.
             result.push_str("s(");
@@ -515,12 +711,262 @@ impl ABIParser {
     }
 }
 
+/// Records the struct and enum types used by `param`, failing if a type with the
+/// same name was already recorded with a different shape.
+fn collect_custom_types(
+    param: &Property,
+    custom_types: &mut HashMap<String, Property>,
+) -> Result<(), Error> {
+    if let Some(element) = vector_element(param) {
+        return collect_custom_types(&element, custom_types);
+    }
+
+    let components = match &param.components {
+        Some(components) => components,
+        None => return Ok(()),
+    };
+
+    if param.type_field.contains("struct ") || param.type_field.contains("enum ") {
+        match custom_types.get(&param.type_field) {
+            Some(existing) if existing.components != param.components => {
+                return Err(Error::ABIConflict(format!(
+                    "`{}` is defined with different fields",
+                    param.type_field
+                )));
+            }
+            Some(_) => {}
+            None => {
+                custom_types.insert(param.type_field.clone(), param.clone());
+            }
+        }
+    }
+
+    for component in components {
+        collect_custom_types(component, custom_types)?;
+    }
+
+    Ok(())
+}
+
+/// Parses a JSON ABI, either in the flat format, a list of functions declaring their types
+/// inline, or in the program ABI format of newer versions of forc, which is converted to
+/// the former.
+pub fn parse_json_abi(json: &str) -> Result<JsonABI, Error> {
+    let value: serde_json::Value = serde_json::from_str(json)?;
+
+    if value.is_object() {
+        let program_abi: ProgramABI = serde_json::from_value(value)?;
+        return flatten_program_abi(&program_abi);
+    }
+
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Configurable constant of a program ABI, with its type resolved the way the flat format
+/// spells it, see `parse_configurables`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Configurable {
+    pub name: String,
+    pub property: Property,
+    /// Position of the constant in the program's bytecode, in bytes.
+    pub offset: u64,
+}
+
+/// Parses the configurable constants of a JSON ABI. Only the program ABI format declares
+/// them, there are none in the flat format.
+pub fn parse_configurables(json: &str) -> Result<Vec<Configurable>, Error> {
+    let value: serde_json::Value = serde_json::from_str(json)?;
+    if !value.is_object() {
+        return Ok(vec![]);
+    }
+
+    let program_abi: ProgramABI = serde_json::from_value(value)?;
+    let types: HashMap<usize, &TypeDeclaration> =
+        program_abi.types.iter().map(|t| (t.type_id, t)).collect();
+
+    program_abi
+        .configurables
+        .iter()
+        .flatten()
+        .map(|configurable| {
+            Ok(Configurable {
+                name: configurable.name.clone(),
+                property: resolve_type_application(&configurable.configurable_type, &types)?,
+                offset: configurable.offset,
+            })
+        })
+        .collect()
+}
+
+/// Type of the values a program logs under `log_id`, resolved the way the flat format
+/// spells it, see `parse_log_types`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogType {
+    pub log_id: u64,
+    pub property: Property,
+}
+
+/// Parses the types of the values logged by a program, from the `loggedTypes` of a JSON
+/// ABI. Only the program ABI format declares them, there are none in the flat format.
+pub fn parse_log_types(json: &str) -> Result<Vec<LogType>, Error> {
+    let value: serde_json::Value = serde_json::from_str(json)?;
+    if !value.is_object() {
+        return Ok(vec![]);
+    }
+
+    let program_abi: ProgramABI = serde_json::from_value(value)?;
+    let types: HashMap<usize, &TypeDeclaration> =
+        program_abi.types.iter().map(|t| (t.type_id, t)).collect();
+
+    program_abi
+        .logged_types
+        .iter()
+        .flatten()
+        .map(|logged_type| {
+            Ok(LogType {
+                log_id: logged_type.log_id,
+                property: resolve_type_application(&logged_type.logged_type, &types)?,
+            })
+        })
+        .collect()
+}
+
+/// Converts a program ABI into the flat format, resolving its type ids.
+pub fn flatten_program_abi(abi: &ProgramABI) -> Result<JsonABI, Error> {
+    let types: HashMap<usize, &TypeDeclaration> =
+        abi.types.iter().map(|t| (t.type_id, t)).collect();
+
+    abi.functions
+        .iter()
+        .map(|function| {
+            let inputs = function
+                .inputs
+                .iter()
+                .map(|input| resolve_type_application(input, &types))
+                .collect::<Result<Vec<_>, Error>>()?;
+            let output = resolve_type_application(&function.output, &types)?;
+
+            // `storage` with the arguments `read` and `write` is `storage(read, write)`
+            let attributes = function.attributes.as_ref().map(|attributes| {
+                attributes
+                    .iter()
+                    .map(|a| match a.arguments.is_empty() {
+                        true => a.name.clone(),
+                        false => format!("{}({})", a.name, a.arguments.join(", ")),
+                    })
+                    .collect()
+            });
+
+            Ok(Function {
+                type_field: "function".to_string(),
+                inputs,
+                name: function.name.clone(),
+                outputs: vec![output],
+                attributes,
+            })
+        })
+        .collect()
+}
+
+/// Turns the use of a declared type into a property, spelling out the type it refers to
+/// the way the flat format does, e.g. `[u64; 2]` rather than `[_; 2]`. Fails on types
+/// containing themselves, which can't be spelled out.
+fn resolve_type_application(
+    application: &TypeApplication,
+    types: &HashMap<usize, &TypeDeclaration>,
+) -> Result<Property, Error> {
+    resolve_nested_type_application(application, types, &mut vec![])
+}
+
+/// Same as `resolve_type_application`, within the types of `enclosing`.
+fn resolve_nested_type_application(
+    application: &TypeApplication,
+    types: &HashMap<usize, &TypeDeclaration>,
+    enclosing: &mut Vec<usize>,
+) -> Result<Property, Error> {
+    let declaration = types.get(&application.type_id).ok_or_else(|| {
+        Error::MissingData(format!("type id {} isn't declared", application.type_id))
+    })?;
+
+    if enclosing.contains(&application.type_id) {
+        return Err(Error::InvalidType(format!(
+            "type id {} contains itself",
+            application.type_id
+        )));
+    }
+
+    enclosing.push(application.type_id);
+    let components = declaration
+        .components
+        .iter()
+        .flatten()
+        .map(|component| resolve_nested_type_application(component, types, enclosing))
+        .collect::<Result<Vec<_>, Error>>()?;
+    enclosing.pop();
+
+    let type_field = &declaration.type_field;
+    let type_field = if type_field.starts_with("generic ") {
+        return Err(Error::InvalidType(format!(
+            "generic types aren't supported, found `{}`",
+            type_field
+        )));
+    } else if type_field.starts_with('[') {
+        // The element type of `[_; n]` is its only component
+        let element = components.first().ok_or_else(|| {
+            Error::MissingData(format!(
+                "array type id {} has no element",
+                declaration.type_id
+            ))
+        })?;
+        type_field.replacen('_', &element.type_field, 1)
+    } else if type_field.starts_with('(') {
+        let types: Vec<&str> = components.iter().map(|c| c.type_field.as_str()).collect();
+        format!("({})", types.join(", "))
+    } else {
+        type_field.clone()
+    };
+
+    // Only custom types list their components in the flat format
+    let components = match type_field.starts_with("struct ") || type_field.starts_with("enum ") {
+        true => Some(components),
+        false => None,
+    };
+
+    Ok(Property {
+        name: application.name.clone(),
+        type_field,
+        components,
+    })
+}
+
 /// Turns a JSON property into ParamType
+/// Whitespace around the type is ignored, and primitive types are matched regardless
+/// of their case, e.g. `" U64 "` is a `u64`. Common misspellings of primitive types,
+/// such as `uint64`, are rejected with the canonical spelling.
 pub fn parse_param(param: &Property) -> Result<ParamType, Error> {
+    let type_field = param.type_field.trim();
+    if type_field != param.type_field {
+        return parse_param(&Property {
+            type_field: type_field.to_string(),
+            ..param.clone()
+        });
+    }
+
     match ParamType::from_str(&param.type_field) {
         // Simple case (primitive types, no arrays, including string)
         Ok(param_type) => Ok(param_type),
         Err(_) => {
+            if let (None, Some(canonical)) =
+                (&param.components, canonical_spelling(&param.type_field))
+            {
+                return Err(Error::InvalidType(format!(
+                    "Unknown type `{}`, did you mean `{}`?",
+                    param.type_field, canonical
+                )));
+            }
+            if let Some(element) = vector_element(param) {
+                return Ok(ParamType::Vector(Box::new(parse_param(&element)?)));
+            }
             if param.type_field.contains("struct") || param.type_field.contains("enum") {
                 return parse_custom_type_param(param);
             }
@@ -541,6 +987,37 @@ pub fn parse_param(param: &Property) -> Result<ParamType, Error> {
     }
 }
 
+/// Returns the element of a `Vec<T>` property, e.g. `struct Point` for `Vec<struct Point>`.
+/// The components of a vector of custom types are those of its element.
+pub fn vector_element(param: &Property) -> Option<Property> {
+    let element = param
+        .type_field
+        .trim()
+        .strip_prefix("Vec<")?
+        .strip_suffix('>')?
+        .trim();
+
+    Some(Property {
+        name: param.name.clone(),
+        type_field: element.to_string(),
+        components: param.components.clone(),
+    })
+}
+
+/// Returns the Sway spelling of a primitive type named as in other languages.
+fn canonical_spelling(type_field: &str) -> Option<&'static str> {
+    match type_field.to_lowercase().as_str() {
+        "uint8" => Some("u8"),
+        "uint16" => Some("u16"),
+        "uint32" => Some("u32"),
+        "uint64" | "uint" => Some("u64"),
+        "boolean" => Some("bool"),
+        "bytes32" | "bits256" => Some("b256"),
+        "string" => Some("str"),
+        _ => None,
+    }
+}
+
 pub fn parse_tuple_param(param: &Property) -> Result<ParamType, Error> {
     let mut params: Vec<ParamType> = Vec::new();
 
@@ -622,6 +1099,29 @@ mod tests {
     use super::*;
     use crate::ParamType;
 
+    #[test]
+    fn parse_type_field_variations() {
+        let property = |type_field: &str| Property {
+            name: "unused".to_string(),
+            type_field: type_field.to_string(),
+            components: None,
+        };
+
+        assert_eq!(parse_param(&property(" u64 ")).unwrap(), ParamType::U64);
+        assert_eq!(parse_param(&property("Bool")).unwrap(), ParamType::Bool);
+        assert_eq!(
+            parse_param(&property(" [u8; 2]\n")).unwrap(),
+            ParamType::Array(Box::new(ParamType::U8), 2)
+        );
+
+        let error = parse_param(&property("Uint64")).unwrap_err();
+        assert!(matches!(error, Error::InvalidType(_)));
+        assert_eq!(
+            error.to_string(),
+            "Invalid type: Unknown type `Uint64`, did you mean `u64`?"
+        );
+    }
+
     #[test]
     fn parse_string_and_array_param() {
         let array_prop = Property {
@@ -649,6 +1149,14 @@ mod tests {
         let expected = "Invalid type: Expected parameter type `[T; n]`, found `str[5]`";
         let result = parse_array_param(&string_prop).unwrap_err().to_string();
         assert_eq!(result, expected);
+
+        let dynamic_string_prop = Property {
+            name: "some_string".to_string(),
+            type_field: "str".to_string(),
+            components: None,
+        };
+        let result = parse_param(&dynamic_string_prop).unwrap();
+        assert_eq!(result, ParamType::DynamicString);
     }
 
     #[test]
@@ -697,6 +1205,94 @@ mod tests {
         assert_eq!(decoded_return, expected_return);
     }
 
+    #[test]
+    fn decode_function_output() {
+        let json_abi = r#"
+        [
+            {
+                "type":"contract",
+                "inputs":[
+                    {
+                        "name":"arg",
+                        "type":"u32"
+                    }
+                ],
+                "name":"takes_u32_returns_bool",
+                "outputs":[
+                    {
+                        "name":"",
+                        "type":"bool"
+                    }
+                ]
+            }
+        ]
+        "#;
+        let parsed_abi: JsonABI = serde_json::from_str(json_abi).unwrap();
+
+        let abi = ABIParser::new();
+
+        let return_value = [
+            0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x1, // true
+        ];
+
+        let decoded_return = abi
+            .decode_function_output(&parsed_abi, "takes_u32_returns_bool", &return_value)
+            .unwrap();
+
+        assert_eq!(decoded_return, vec![Token::Bool(true)]);
+
+        let result = abi.decode_function_output(&parsed_abi, "unknown", &return_value);
+        assert!(matches!(result, Err(Error::InvalidName(_))));
+    }
+
+    #[test]
+    fn encode_function_call() {
+        let json_abi = r#"
+        [
+            {
+                "type":"contract",
+                "inputs":[
+                    {
+                        "name":"arg",
+                        "type":"u32"
+                    }
+                ],
+                "name":"takes_u32_returns_bool",
+                "outputs":[
+                    {
+                        "name":"",
+                        "type":"bool"
+                    }
+                ]
+            }
+        ]
+        "#;
+        let parsed_abi: JsonABI = serde_json::from_str(json_abi).unwrap();
+
+        let abi = ABIParser::new();
+
+        let encoded = abi
+            .encode_function_call(&parsed_abi, "takes_u32_returns_bool", &[Token::U32(10)])
+            .unwrap();
+
+        let expected_selector = [0x0, 0x0, 0x0, 0x0, 0x63, 0x55, 0xe6, 0xee];
+        assert_eq!(encoded[..8], expected_selector);
+        assert_eq!(hex::encode(&encoded[8..]), "000000000000000a");
+
+        let result = abi.encode_function_call(
+            &parsed_abi,
+            "takes_u32_returns_bool",
+            &[Token::U32(10), Token::U32(11)],
+        );
+        assert!(matches!(
+            result,
+            Err(Error::ArgumentCountMismatch {
+                expected: 1,
+                got: 2
+            })
+        ));
+    }
+
     #[test]
     fn simple_encode_and_decode() {
         let json_abi = r#"
@@ -908,6 +1504,40 @@ mod tests {
         assert_eq!(tokens, expected_tokens);
     }
 
+    #[test]
+    fn token_from_str() {
+        let b256 = format!("0x{}", "d5".repeat(32));
+        assert_eq!(
+            ABIParser::token_from_str(&ParamType::B256, &b256).unwrap(),
+            Token::B256([0xd5; 32])
+        );
+        assert_eq!(
+            ABIParser::token_from_str(&ParamType::U64, " 1024 ").unwrap(),
+            Token::U64(1024)
+        );
+
+        // Too short for a b256
+        assert!(matches!(
+            ABIParser::token_from_str(&ParamType::B256, "0xd5d5"),
+            Err(Error::InvalidType(_))
+        ));
+        // Not hex
+        assert!(matches!(
+            ABIParser::token_from_str(&ParamType::B256, &"zz".repeat(32)),
+            Err(Error::InvalidType(_))
+        ));
+        // Out of range
+        assert!(matches!(
+            ABIParser::token_from_str(&ParamType::U8, "256"),
+            Err(Error::InvalidType(_))
+        ));
+        // Composite types aren't literals
+        assert!(matches!(
+            ABIParser::token_from_str(&ParamType::Struct(vec![ParamType::U8]), "(1)"),
+            Err(Error::InvalidType(_))
+        ));
+    }
+
     #[test]
     fn nested_array_encode_and_decode() {
         let json_abi = r#"
@@ -1342,6 +1972,238 @@ mod tests {
         assert_eq!(selector, "my_func(s(bool,s(u64,u32)))");
     }
 
+    #[test]
+    fn list_functions() {
+        let json_abi = r#"
+        [
+            {
+                "type":"function",
+                "inputs":[
+                    {
+                        "name":"value",
+                        "type":"u64"
+                    },
+                    {
+                        "name":"flag",
+                        "type":"bool"
+                    }
+                ],
+                "name":"store",
+                "outputs":[]
+            },
+            {
+                "type":"function",
+                "inputs":[
+                    {
+                        "name":"person",
+                        "type":"struct Person",
+                        "components": [
+                            {
+                                "name": "name",
+                                "type": "str[4]"
+                            },
+                            {
+                                "name": "age",
+                                "type": "u8"
+                            }
+                        ]
+                    }
+                ],
+                "name":"greet",
+                "outputs":[
+                    {
+                        "name":"",
+                        "type":"str[5]"
+                    }
+                ]
+            }
+        ]
+        "#;
+        let abi: JsonABI = serde_json::from_str(json_abi).unwrap();
+
+        let functions = ABIParser::new().list_functions(&abi);
+
+        assert_eq!(
+            functions,
+            vec![
+                FunctionInfo {
+                    name: "store".into(),
+                    signature: "store(u64,bool)".into(),
+                    inputs: vec![
+                        ("value".into(), "u64".into()),
+                        ("flag".into(), "bool".into())
+                    ],
+                    outputs: vec![],
+                },
+                FunctionInfo {
+                    name: "greet".into(),
+                    signature: "greet(s(str[4],u8))".into(),
+                    inputs: vec![("person".into(), "struct Person".into())],
+                    outputs: vec!["str[5]".into()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn merge_abis() {
+        let person = r#"
+            {
+                "name":"person",
+                "type":"struct Person",
+                "components": [
+                    {
+                        "name": "age",
+                        "type": "u8"
+                    }
+                ]
+            }
+        "#;
+        let abi_a: JsonABI = serde_json::from_str(&format!(
+            r#"[
+                {{ "type":"function", "inputs":[{}], "name":"greet", "outputs":[] }},
+                {{ "type":"function", "inputs":[], "name":"count", "outputs":[{{ "name":"", "type":"u64" }}] }}
+            ]"#,
+            person
+        ))
+        .unwrap();
+        let abi_b: JsonABI = serde_json::from_str(&format!(
+            r#"[
+                {{ "type":"function", "inputs":[{}], "name":"greet", "outputs":[] }},
+                {{ "type":"function", "inputs":[{}], "name":"age_of", "outputs":[{{ "name":"", "type":"u8" }}] }}
+            ]"#,
+            person, person
+        ))
+        .unwrap();
+
+        let parser = ABIParser::new();
+
+        let merged = parser.merge(abi_a.clone(), abi_b).unwrap();
+        let names: Vec<_> = merged.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["greet", "count", "age_of"]);
+
+        // Same function name, different signature
+        let abi_c: JsonABI = serde_json::from_str(
+            r#"[{ "type":"function", "inputs":[{ "name":"x", "type":"u32" }], "name":"count", "outputs":[] }]"#,
+        )
+        .unwrap();
+        let err = parser.merge(abi_a.clone(), abi_c).unwrap_err();
+        assert!(matches!(err, Error::ABIConflict(_)));
+
+        // Same custom type name, different fields
+        let abi_d: JsonABI = serde_json::from_str(
+            r#"[
+                {
+                    "type":"function",
+                    "inputs":[
+                        {
+                            "name":"person",
+                            "type":"struct Person",
+                            "components": [{ "name": "name", "type": "str[4]" }]
+                        }
+                    ],
+                    "name":"rename",
+                    "outputs":[]
+                }
+            ]"#,
+        )
+        .unwrap();
+        let err = parser.merge(abi_a, abi_d).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Conflicting ABI definitions: `struct Person` is defined with different fields"
+        );
+    }
+
+    #[test]
+    fn function_signature_and_selector() {
+        let abi = ABIParser::new();
+
+        let primitive = Function {
+            type_field: "function".into(),
+            attributes: None,
+            inputs: vec![Property {
+                name: "arg".into(),
+                type_field: "u32".into(),
+                components: None,
+            }],
+            name: "takes_u32_returns_bool".into(),
+            outputs: vec![],
+        };
+
+        assert_eq!(
+            abi.function_signature(&primitive).unwrap(),
+            "takes_u32_returns_bool(u32)"
+        );
+        assert_eq!(
+            abi.function_selector(&primitive).unwrap(),
+            [0x0, 0x0, 0x0, 0x0, 0x63, 0x55, 0xe6, 0xee]
+        );
+
+        let with_struct = Function {
+            type_field: "function".into(),
+            attributes: None,
+            inputs: vec![Property {
+                name: "value".into(),
+                type_field: "struct TestStruct".into(),
+                components: Some(vec![
+                    Property {
+                        name: "a".into(),
+                        type_field: "bool".into(),
+                        components: None,
+                    },
+                    Property {
+                        name: "b".into(),
+                        type_field: "[u8; 2]".into(),
+                        components: None,
+                    },
+                ]),
+            }],
+            name: "boo".into(),
+            outputs: vec![],
+        };
+
+        assert_eq!(
+            abi.function_signature(&with_struct).unwrap(),
+            "boo(s(bool,[u8;2]))"
+        );
+
+        let with_vector = Function {
+            type_field: "function".into(),
+            attributes: None,
+            inputs: vec![Property {
+                name: "points".into(),
+                type_field: "Vec<struct Point>".into(),
+                components: Some(vec![
+                    Property {
+                        name: "x".into(),
+                        type_field: "u64".into(),
+                        components: None,
+                    },
+                    Property {
+                        name: "y".into(),
+                        type_field: "u64".into(),
+                        components: None,
+                    },
+                ]),
+            }],
+            name: "takes_points".into(),
+            outputs: vec![],
+        };
+
+        assert_eq!(
+            abi.function_signature(&with_vector).unwrap(),
+            "takes_points(Vec<s(u64,u64)>)"
+        );
+        assert_eq!(
+            parse_param(&with_vector.inputs[0]).unwrap(),
+            ParamType::Vector(Box::new(ParamType::Struct(vec![
+                ParamType::U64,
+                ParamType::U64
+            ])))
+        );
+    }
+
     #[test]
     fn compiler_generated_abi_test() {
         let json_abi = r#"
@@ -1423,4 +2285,98 @@ mod tests {
         let expected_encode = "00000000e33a11ce0000000000000001000000000000002a";
         assert_eq!(encoded, expected_encode);
     }
+
+    #[test]
+    fn parse_program_abi_configurables() {
+        let program_abi = r#"
+        {
+            "types": [
+                { "typeId": 0, "type": "u64", "components": null, "typeParameters": null }
+            ],
+            "functions": [],
+            "loggedTypes": [],
+            "configurables": [
+                {
+                    "name": "MAX_SUPPLY",
+                    "configurableType": { "name": "", "type": 0, "typeArguments": null },
+                    "offset": 120
+                }
+            ]
+        }
+        "#;
+
+        let configurables = parse_configurables(program_abi).unwrap();
+        assert_eq!(
+            configurables,
+            vec![Configurable {
+                name: "MAX_SUPPLY".to_string(),
+                property: Property {
+                    name: "".to_string(),
+                    type_field: "u64".to_string(),
+                    components: None,
+                },
+                offset: 120,
+            }]
+        );
+
+        // The flat format has no configurables
+        assert!(parse_configurables("[]").unwrap().is_empty());
+    }
+
+    #[test]
+    fn parse_program_abi_log_types() {
+        let program_abi = r#"
+        {
+            "types": [
+                { "typeId": 0, "type": "u64", "components": null, "typeParameters": null },
+                { "typeId": 1, "type": "b256", "components": null, "typeParameters": null }
+            ],
+            "functions": [],
+            "loggedTypes": [
+                { "logId": 0, "loggedType": { "name": "", "type": 0, "typeArguments": null } },
+                { "logId": 1, "loggedType": { "name": "", "type": 1, "typeArguments": null } }
+            ]
+        }
+        "#;
+
+        let log_types = parse_log_types(program_abi).unwrap();
+        let types: Vec<(u64, &str)> = log_types
+            .iter()
+            .map(|log_type| (log_type.log_id, log_type.property.type_field.as_str()))
+            .collect();
+        assert_eq!(types, vec![(0, "u64"), (1, "b256")]);
+
+        // The flat format has no logged types
+        assert!(parse_log_types("[]").unwrap().is_empty());
+    }
+
+    #[test]
+    fn reject_recursive_program_abi_type() {
+        // `struct Node` has a component of its own type
+        let program_abi = r#"
+        {
+            "types": [
+                {
+                    "typeId": 0,
+                    "type": "struct Node",
+                    "components": [{ "name": "next", "type": 0, "typeArguments": null }],
+                    "typeParameters": null
+                }
+            ],
+            "functions": [
+                {
+                    "inputs": [{ "name": "node", "type": 0, "typeArguments": null }],
+                    "name": "visit",
+                    "output": { "name": "", "type": 0, "typeArguments": null }
+                }
+            ],
+            "loggedTypes": []
+        }
+        "#;
+
+        assert!(matches!(
+            parse_json_abi(program_abi),
+            Err(Error::InvalidType(_))
+        ));
+    }
 }
@@ -1,5 +1,8 @@
+use crate::constants::WORD_SIZE;
 use core::fmt;
+use fuel_tx::{Address, ContractId};
 use fuel_types::bytes::padded_len;
+use std::str::FromStr;
 use strum_macros::EnumString;
 
 pub mod abi_decoder;
@@ -7,7 +10,10 @@ pub mod abi_encoder;
 pub mod code_gen;
 pub mod constants;
 pub mod errors;
+#[cfg(feature = "fuzz")]
+pub mod fuzz;
 pub mod json_abi;
+pub mod parameters;
 pub mod rustfmt;
 pub mod source;
 pub mod types;
@@ -16,7 +22,9 @@ pub mod utils;
 pub type ByteArray = [u8; 8];
 pub type Selector = ByteArray;
 pub type Bits256 = [u8; 32];
-pub type EnumSelector = (u8, Token);
+/// The discriminant of an enum's variant, the token it holds, and the types of all of the
+/// enum's variants, which the encoder needs to pad the enum to its biggest variant.
+pub type EnumSelector = (u8, Token, Vec<ParamType>);
 
 #[derive(Debug, Clone, EnumString, PartialEq, Eq)]
 #[strum(ascii_case_insensitive)]
@@ -29,8 +37,14 @@ pub enum ParamType {
     Byte,
     B256,
     Array(Box<ParamType>, usize),
-    #[strum(serialize = "str")]
+    /// Dynamically-sized vector, encoded as its length followed by its elements.
+    #[strum(disabled)]
+    Vector(Box<ParamType>),
+    #[strum(disabled)]
     String(usize),
+    /// Dynamically-sized string, encoded as its length followed by its UTF-8 bytes.
+    #[strum(serialize = "str")]
+    DynamicString,
     #[strum(disabled)]
     Struct(Vec<ParamType>),
     #[strum(disabled)]
@@ -55,6 +69,8 @@ impl ParamType {
             Self::B256 => true,
             // Strings are bigger than one `WORD` when its size > 8.
             Self::String(size) => size > &8,
+            // Dynamic strings always carry their length on top of their data.
+            Self::DynamicString => true,
             Self::Struct(params) => match params.len() {
                 // If only one component in this struct
                 // check if this element itself is bigger than a `WORD`.
@@ -65,6 +81,8 @@ impl ParamType {
             Self::Enum(_params) => true,
             // Arrays seem to always be inside `ReturnData`.
             Self::Array(_params, _l) => true,
+            // Vectors always carry their length on top of their elements.
+            Self::Vector(_param) => true,
             // The other primitive types are inside `Return`,
             // thus smaller than one `WORD`.
             Self::Tuple(params) => {
@@ -76,6 +94,28 @@ impl ParamType {
             _ => false,
         }
     }
+
+    /// Returns the number of bytes a value of this type occupies once encoded.
+    /// Every primitive is padded to a `WORD`, strings are rounded up to a
+    /// multiple of a `WORD`, and enums take a `WORD` for the discriminant
+    /// plus the size of their biggest variant. Dynamic strings and vectors only
+    /// account for their length prefix, as the size of their data depends on the value.
+    pub fn encoded_size(&self) -> usize {
+        match self {
+            Self::U8 | Self::U16 | Self::U32 | Self::U64 | Self::Bool | Self::Byte => WORD_SIZE,
+            Self::B256 => 32,
+            Self::String(len) => (len + WORD_SIZE - 1) / WORD_SIZE * WORD_SIZE,
+            Self::DynamicString | Self::Vector(_) => WORD_SIZE,
+            Self::Array(param, len) => param.encoded_size() * len,
+            Self::Struct(params) | Self::Tuple(params) => {
+                params.iter().map(|p| p.encoded_size()).sum()
+            }
+            Self::Enum(variants) => {
+                let biggest_variant = variants.iter().map(|p| p.encoded_size()).max();
+                WORD_SIZE + biggest_variant.unwrap_or(0)
+            }
+        }
+    }
 }
 
 impl fmt::Display for ParamType {
@@ -90,6 +130,9 @@ impl fmt::Display for ParamType {
                 let arr_str = format!("Array({},{})", boxed_type_str, size);
                 write!(f, "{}", arr_str)
             }
+            ParamType::Vector(t) => {
+                write!(f, "Vector(Box::new(ParamType::{}))", t)
+            }
             ParamType::Struct(inner) => {
                 let inner_strings: Vec<String> =
                     inner.iter().map(|p| format!("ParamType::{}", p)).collect();
@@ -123,7 +166,9 @@ pub enum Token {
     Byte(u8),
     B256(Bits256),
     Array(Vec<Token>),
+    Vector(Vec<Token>),
     String(String),
+    DynamicString(String),
     Struct(Vec<Token>),
     Enum(Box<EnumSelector>),
     Tuple(Vec<Token>),
@@ -181,7 +226,7 @@ impl Tokenizable for bool {
 impl Tokenizable for String {
     fn from_token(token: Token) -> Result<Self, InvalidOutputType> {
         match token {
-            Token::String(data) => Ok(data),
+            Token::String(data) | Token::DynamicString(data) => Ok(data),
             other => Err(InvalidOutputType(format!(
                 "Expected `String`, got {:?}",
                 other
@@ -208,15 +253,41 @@ impl Tokenizable for Bits256 {
     }
 }
 
-impl<T: Tokenizable> Tokenizable for Vec<T> {
+// Arrays of `b256` are generated as fixed-size arrays rather than `Vec`s, as a
+// `[[u8; 32]; N]` can't be confused with a byte array. Other arrays stay `Vec`s:
+// a generic `[T; N]` impl would overlap with `Bits256`'s, since `u8` is
+// `Tokenizable`, and the orphan rule forbids generated bindings from
+// implementing `Tokenizable` for arrays of their own structs and enums.
+impl<const N: usize> Tokenizable for [Bits256; N] {
     fn from_token(token: Token) -> Result<Self, InvalidOutputType> {
         match token {
             Token::Array(data) => {
-                let mut v: Vec<T> = Vec::new();
-                for tok in data {
-                    v.push(T::from_token(tok.clone()).unwrap());
-                }
-                Ok(v)
+                let len = data.len();
+                let b256s = data
+                    .into_iter()
+                    .map(Bits256::from_token)
+                    .collect::<Result<Vec<_>, _>>()?;
+                b256s
+                    .try_into()
+                    .map_err(|_| InvalidOutputType(format!("Expected {} `b256`s, got {}", N, len)))
+            }
+            other => Err(InvalidOutputType(format!(
+                "Expected `[b256; {}]`, got {:?}",
+                N, other
+            ))),
+        }
+    }
+    fn into_token(self) -> Token {
+        Token::Array(self.into_iter().map(Token::B256).collect())
+    }
+}
+
+impl<T: Tokenizable> Tokenizable for Vec<T> {
+    fn from_token(token: Token) -> Result<Self, InvalidOutputType> {
+        match token {
+            // An empty array, e.g. `[T; 0]`, is an empty `Vec`
+            Token::Array(data) | Token::Vector(data) => {
+                data.into_iter().map(T::from_token).collect()
             }
             other => Err(InvalidOutputType(format!("Expected `T`, got {:?}", other))),
         }
@@ -288,6 +359,76 @@ impl Tokenizable for u64 {
     }
 }
 
+// Lighter conversions into `Token`, e.g. to write `vec![10u64.into(), true.into()]`
+// when encoding ad-hoc calls.
+impl From<u8> for Token {
+    fn from(value: u8) -> Self {
+        Token::U8(value)
+    }
+}
+
+impl From<u16> for Token {
+    fn from(value: u16) -> Self {
+        Token::U16(value)
+    }
+}
+
+impl From<u32> for Token {
+    fn from(value: u32) -> Self {
+        Token::U32(value)
+    }
+}
+
+impl From<u64> for Token {
+    fn from(value: u64) -> Self {
+        Token::U64(value)
+    }
+}
+
+impl From<bool> for Token {
+    fn from(value: bool) -> Self {
+        Token::Bool(value)
+    }
+}
+
+impl From<&str> for Token {
+    fn from(value: &str) -> Self {
+        Token::String(value.to_string())
+    }
+}
+
+impl From<String> for Token {
+    fn from(value: String) -> Self {
+        Token::String(value)
+    }
+}
+
+impl Token {
+    /// Parses a hex string, with or without the `0x` prefix, into a `Token::B256`.
+    /// This isn't a `TryFrom<&str>` impl since `From<&str>` already gives `Token`
+    /// an infallible one.
+    pub fn b256_from_hex(hex_str: &str) -> Result<Token, errors::Error> {
+        let bytes = hex::decode(hex_str.trim_start_matches("0x"))?;
+        let b256: Bits256 = bytes.try_into().map_err(|_| errors::Error::InvalidData)?;
+
+        Ok(Token::B256(b256))
+    }
+
+    /// Tokenizes `value` as a `str[len]`, failing if it isn't exactly `len` bytes long,
+    /// as the node would otherwise read the wrong number of bytes.
+    pub fn fixed_string(value: &str, len: usize) -> Result<Token, errors::Error> {
+        if value.len() != len {
+            return Err(errors::Error::InvalidType(format!(
+                "expected a `str[{}]`, got {} bytes",
+                len,
+                value.len()
+            )));
+        }
+
+        Ok(Token::String(value.to_string()))
+    }
+}
+
 // Here we implement `Tokenizable` for a given tuple of a given length.
 // This is done this way because we can't use `impl<T> Tokenizable for (T,)`.
 // So we implement `Tokenizable` for each tuple length, covering
@@ -447,3 +588,127 @@ pub fn pad_string(s: &str) -> Vec<u8> {
 
     padded
 }
+
+/// Parses a hex string, with or without the `0x` prefix, into a `ContractId`.
+/// The error holds the input, so that the faulty id can be told apart.
+pub fn parse_contract_id(s: &str) -> Result<ContractId, errors::Error> {
+    ContractId::from_str(s).map_err(|_| errors::Error::InvalidAddress(s.to_string()))
+}
+
+/// Parses a hex string, with or without the `0x` prefix, into an `Address`.
+pub fn parse_address(s: &str) -> Result<Address, errors::Error> {
+    Address::from_str(s).map_err(|_| errors::Error::InvalidAddress(s.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encoded_size_of_primitives() {
+        assert_eq!(ParamType::U8.encoded_size(), 8);
+        assert_eq!(ParamType::Bool.encoded_size(), 8);
+        assert_eq!(ParamType::U64.encoded_size(), 8);
+        assert_eq!(ParamType::B256.encoded_size(), 32);
+    }
+
+    #[test]
+    fn encoded_size_of_strings() {
+        assert_eq!(ParamType::String(4).encoded_size(), 8);
+        assert_eq!(ParamType::String(8).encoded_size(), 8);
+        assert_eq!(ParamType::String(23).encoded_size(), 24);
+    }
+
+    #[test]
+    fn encoded_size_of_composite_types() {
+        let two_u64 = ParamType::Struct(vec![ParamType::U64, ParamType::U64]);
+        assert_eq!(two_u64.encoded_size(), 16);
+
+        let array = ParamType::Array(Box::new(ParamType::U16), 3);
+        assert_eq!(array.encoded_size(), 24);
+
+        // Discriminant + biggest variant (b256)
+        let an_enum = ParamType::Enum(vec![ParamType::U32, ParamType::B256]);
+        assert_eq!(an_enum.encoded_size(), 40);
+    }
+
+    #[test]
+    fn b256_array_tokens() {
+        let b256s = [[1u8; 32], [2u8; 32], [3u8; 32], [4u8; 32]];
+
+        let token = b256s.into_token();
+        assert_eq!(
+            token,
+            Token::Array(b256s.iter().copied().map(Token::B256).collect())
+        );
+        assert_eq!(<[Bits256; 4]>::from_token(token.clone()).unwrap(), b256s);
+
+        assert!(<[Bits256; 3]>::from_token(token).is_err());
+    }
+
+    #[test]
+    fn empty_array_tokens() {
+        let token = Token::Array(vec![]);
+        assert_eq!(Vec::<u64>::from_token(token).unwrap(), Vec::<u64>::new());
+        assert_eq!(Vec::<u64>::new().into_token(), Token::Array(vec![]));
+
+        // A mismatched element is an error rather than a panic
+        let token = Token::Array(vec![Token::Bool(true)]);
+        assert!(Vec::<u64>::from_token(token).is_err());
+    }
+
+    #[test]
+    fn primitives_into_tokens() {
+        let tokens: Vec<Token> = vec![
+            8u8.into(),
+            16u16.into(),
+            32u32.into(),
+            64u64.into(),
+            true.into(),
+            "hello".into(),
+            String::from("world").into(),
+        ];
+
+        assert_eq!(u8::from_token(tokens[0].clone()).unwrap(), 8);
+        assert_eq!(u16::from_token(tokens[1].clone()).unwrap(), 16);
+        assert_eq!(u32::from_token(tokens[2].clone()).unwrap(), 32);
+        assert_eq!(u64::from_token(tokens[3].clone()).unwrap(), 64);
+        assert!(bool::from_token(tokens[4].clone()).unwrap());
+        assert_eq!(String::from_token(tokens[5].clone()).unwrap(), "hello");
+        assert_eq!(String::from_token(tokens[6].clone()).unwrap(), "world");
+    }
+
+    #[test]
+    fn b256_token_from_hex() {
+        let hex_str = "0x0101010101010101010101010101010101010101010101010101010101010101";
+
+        let token = Token::b256_from_hex(hex_str).unwrap();
+        assert_eq!(Bits256::from_token(token).unwrap(), [1u8; 32]);
+
+        let token = Token::b256_from_hex(&hex_str[2..]).unwrap();
+        assert_eq!(token, Token::B256([1u8; 32]));
+
+        assert!(Token::b256_from_hex("0x0101").is_err());
+        assert!(Token::b256_from_hex("not hex").is_err());
+    }
+
+    #[test]
+    fn parse_ids_from_hex() {
+        let hex_str = "0x0101010101010101010101010101010101010101010101010101010101010101";
+
+        assert_eq!(
+            parse_contract_id(hex_str).unwrap(),
+            ContractId::new([1u8; 32])
+        );
+        assert_eq!(
+            parse_address(&hex_str[2..]).unwrap(),
+            Address::new([1u8; 32])
+        );
+
+        let result = parse_contract_id("0x0101");
+        assert!(matches!(result, Err(errors::Error::InvalidAddress(s)) if s == "0x0101"));
+
+        let result = parse_address("not hex");
+        assert!(matches!(result, Err(errors::Error::InvalidAddress(s)) if s == "not hex"));
+    }
+}
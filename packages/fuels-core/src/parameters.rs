@@ -0,0 +1,110 @@
+use crate::constants::{
+    DEFAULT_BYTE_PRICE, DEFAULT_GAS_LIMIT, DEFAULT_GAS_MARGIN, DEFAULT_GAS_PRICE, DEFAULT_MATURITY,
+};
+use crate::errors::Error;
+
+/// A gas margin of 1, in basis points.
+const GAS_MARGIN_UNIT: u64 = 10_000;
+
+#[derive(Debug, Clone)]
+pub struct TxParameters {
+    pub gas_price: u64,
+    pub gas_limit: u64,
+    pub byte_price: u64,
+    pub maturity: u32,
+    /// Multiplier applied to the estimated gas when the gas limit is estimated, in basis
+    /// points
+    pub gas_margin: u64,
+    /// Smallest change a transfer may leave, 0 to allow any
+    pub min_output_amount: u64,
+}
+
+impl Default for TxParameters {
+    fn default() -> Self {
+        Self {
+            gas_price: DEFAULT_GAS_PRICE,
+            gas_limit: DEFAULT_GAS_LIMIT,
+            byte_price: DEFAULT_BYTE_PRICE,
+            // By default, transaction is immediately valid
+            maturity: DEFAULT_MATURITY,
+            gas_margin: DEFAULT_GAS_MARGIN,
+            min_output_amount: 0,
+        }
+    }
+}
+
+impl TxParameters {
+    pub fn new(
+        gas_price: Option<u64>,
+        gas_limit: Option<u64>,
+        byte_price: Option<u64>,
+        maturity: Option<u32>,
+    ) -> Self {
+        Self {
+            gas_price: gas_price.unwrap_or(DEFAULT_GAS_PRICE),
+            gas_limit: gas_limit.unwrap_or(DEFAULT_GAS_LIMIT),
+            byte_price: byte_price.unwrap_or(DEFAULT_BYTE_PRICE),
+            maturity: maturity.unwrap_or(DEFAULT_MATURITY),
+            gas_margin: DEFAULT_GAS_MARGIN,
+            min_output_amount: 0,
+        }
+    }
+
+    /// Sets the multiplier applied to the estimated gas when the gas limit is estimated,
+    /// e.g. 1.1 for a 10% margin. Fails if it's below 1 or not a number.
+    pub fn gas_margin(mut self, gas_margin: f64) -> Result<Self, Error> {
+        if !gas_margin.is_finite() || gas_margin < 1.0 {
+            return Err(Error::InvalidGasMargin(gas_margin));
+        }
+        self.gas_margin = (gas_margin * GAS_MARGIN_UNIT as f64).round() as u64;
+        Ok(self)
+    }
+
+    /// Makes transfers fail rather than leave change below `min_output_amount`, to keep
+    /// dust coins out of the UTXO set.
+    pub fn min_output_amount(mut self, min_output_amount: u64) -> Self {
+        self.min_output_amount = min_output_amount;
+        self
+    }
+
+    /// Applies the gas margin to `gas_used`, rounding up, without going above `max_gas`.
+    pub fn apply_gas_margin(&self, gas_used: u64, max_gas: u64) -> u64 {
+        let unit = GAS_MARGIN_UNIT as u128;
+        let gas_limit = (gas_used as u128 * self.gas_margin as u128 + unit - 1) / unit;
+        gas_limit.min(max_gas as u128) as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_gas_margin() {
+        let params = TxParameters::default();
+        assert_eq!(params.apply_gas_margin(1000, u64::MAX), 1100);
+        assert_eq!(params.apply_gas_margin(100, u64::MAX), 110);
+
+        let params = TxParameters::default().gas_margin(1.1).unwrap();
+        assert_eq!(params.apply_gas_margin(100, u64::MAX), 110);
+
+        let params = TxParameters::default().gas_margin(1.5).unwrap();
+        assert_eq!(params.apply_gas_margin(3, u64::MAX), 5);
+        assert_eq!(params.apply_gas_margin(1000, 1200), 1200);
+        assert_eq!(params.apply_gas_margin(u64::MAX, u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn reject_gas_margin_below_one() {
+        for margin in [0.9, 0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert!(
+                matches!(
+                    TxParameters::default().gas_margin(margin),
+                    Err(Error::InvalidGasMargin(_))
+                ),
+                "{} should be rejected",
+                margin
+            );
+        }
+    }
+}
@@ -1,15 +1,62 @@
 //! This module implements basic `rustfmt` code formatting.
 
 use anyhow::{anyhow, Result};
+use std::fs;
 use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 
+/// Edition the bindings are formatted with, unless configured otherwise.
+pub const DEFAULT_EDITION: &str = "2021";
+
 /// Format the raw input source string and return formatted output.
 pub fn format<S>(source: S) -> Result<String>
 where
     S: AsRef<str>,
 {
-    let mut rustfmt = Command::new("rustfmt")
+    spawn(source, Command::new("rustfmt"))
+}
+
+/// Same as `format`, with the given `edition` and the configuration file at `config_path`.
+/// Without a configuration file, `rustfmt` is given an empty one, so that it formats with
+/// its defaults rather than picking up one from the current workspace or a parent directory.
+pub fn format_with_config<S>(source: S, edition: &str, config_path: Option<&Path>) -> Result<String>
+where
+    S: AsRef<str>,
+{
+    let default_config;
+    let config_path = match config_path {
+        Some(config_path) => config_path,
+        None => {
+            default_config = empty_config()?;
+            &default_config
+        }
+    };
+
+    let mut command = Command::new("rustfmt");
+    command
+        .args(["--edition", edition])
+        .arg("--config-path")
+        .arg(config_path);
+
+    spawn(source, command)
+}
+
+/// Writes an empty `rustfmt` configuration file in the temporary directory and returns
+/// its path.
+fn empty_config() -> Result<PathBuf> {
+    let dir = std::env::temp_dir().join("fuels-rustfmt-defaults");
+    fs::create_dir_all(&dir)?;
+    let path = dir.join("rustfmt.toml");
+    fs::write(&path, "")?;
+    Ok(path)
+}
+
+fn spawn<S>(source: S, mut command: Command) -> Result<String>
+where
+    S: AsRef<str>,
+{
+    let mut rustfmt = command
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .spawn()?;
@@ -23,7 +23,7 @@ impl Source {
     /// Contract ABIs can be retrieved from the local filesystem or it can
     /// be provided in-line. It accepts:
     ///
-    /// - raw ABI JSON
+    /// - raw ABI JSON, either a list of functions or a program ABI object
     ///
     /// - `relative/path/to/Contract.json`: a relative path to an ABI JSON file.
     /// This relative path is rooted in the current working directory.
@@ -36,7 +36,7 @@ impl Source {
     {
         let source = source.as_ref().trim();
 
-        if source.starts_with('[') || source.starts_with('\n') {
+        if source.starts_with('[') || source.starts_with('{') || source.starts_with('\n') {
             return Ok(Source::String(source.to_owned()));
         }
         let root = env::current_dir()?.canonicalize()?;
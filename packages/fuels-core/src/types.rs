@@ -1,12 +1,14 @@
 use crate::errors::Error;
 use anyhow::Result;
-use proc_macro2::TokenStream;
+use proc_macro2::{Literal, TokenStream};
 use quote::quote;
 
 use crate::ParamType;
 
 /// Expands a [`ParamType`] into a TokenStream.
 /// Used to expand functions when generating type-safe bindings of a JSON ABI.
+/// As a [`ParamType`] doesn't know the name of its type, an empty struct, enum
+/// or tuple is reported as such, callers knowing the name should report it instead.
 pub fn expand_type(kind: &ParamType) -> Result<TokenStream, Error> {
     match kind {
         ParamType::U8 | ParamType::Byte => Ok(quote! { u8 }),
@@ -15,14 +17,21 @@ pub fn expand_type(kind: &ParamType) -> Result<TokenStream, Error> {
         ParamType::U64 => Ok(quote! { u64 }),
         ParamType::Bool => Ok(quote! { bool }),
         ParamType::B256 => Ok(quote! { [u8; 32] }),
-        ParamType::String(_) => Ok(quote! { String }),
-        ParamType::Array(t, _size) => {
+        ParamType::String(_) | ParamType::DynamicString => Ok(quote! { String }),
+        // Only arrays of `b256` keep their size, other arrays can't have a
+        // `Tokenizable` impl, see `Tokenizable for [Bits256; N]`
+        ParamType::Array(t, size) if **t == ParamType::B256 => {
+            let inner = expand_type(t)?;
+            let size = Literal::usize_unsuffixed(*size);
+            Ok(quote! { [#inner; #size] })
+        }
+        ParamType::Array(t, _size) | ParamType::Vector(t) => {
             let inner = expand_type(t)?;
             Ok(quote! { ::std::vec::Vec<#inner> })
         }
         ParamType::Struct(members) => {
             if members.is_empty() {
-                return Err(Error::InvalidData);
+                return Err(Error::EmptyCompositeType("struct".to_string()));
             }
             let members = members
                 .iter()
@@ -32,7 +41,7 @@ pub fn expand_type(kind: &ParamType) -> Result<TokenStream, Error> {
         }
         ParamType::Enum(members) => {
             if members.is_empty() {
-                return Err(Error::InvalidData);
+                return Err(Error::EmptyCompositeType("enum".to_string()));
             }
             let members = members
                 .iter()
@@ -42,7 +51,7 @@ pub fn expand_type(kind: &ParamType) -> Result<TokenStream, Error> {
         }
         ParamType::Tuple(members) => {
             if members.is_empty() {
-                return Err(Error::InvalidData);
+                return Err(Error::EmptyCompositeType("tuple".to_string()));
             }
 
             let members = members
@@ -53,3 +62,23 @@ pub fn expand_type(kind: &ParamType) -> Result<TokenStream, Error> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expand_b256_array() {
+        let b256_array = ParamType::Array(Box::new(ParamType::B256), 4);
+        assert_eq!(
+            expand_type(&b256_array).unwrap().to_string(),
+            "[[u8 ; 32] ; 4]"
+        );
+
+        let u64_array = ParamType::Array(Box::new(ParamType::U64), 4);
+        assert_eq!(
+            expand_type(&u64_array).unwrap().to_string(),
+            ":: std :: vec :: Vec < u64 >"
+        );
+    }
+}
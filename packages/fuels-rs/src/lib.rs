@@ -42,6 +42,9 @@ pub mod prelude {
 
     pub use super::contract::contract::Contract;
     pub use super::contract::parameters::*;
+    pub use super::contract::predicate::Predicate;
+    pub use super::contract::session::ContractSession;
+    pub use super::contract::test_helpers::*;
     pub use super::core::constants::*;
     pub use super::core::errors::Error;
     pub use super::core::{Token, Tokenizable};
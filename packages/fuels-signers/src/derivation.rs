@@ -0,0 +1,293 @@
+//! Hierarchical deterministic (BIP-32) key derivation, following the BIP-44 path layout:
+//! `m/44'/<coin_type>'/<account>'/<change>/<index>`.
+
+use bip39::Mnemonic;
+use hmac::{Hmac, Mac, NewMac};
+use secp256k1::{PublicKey, Secp256k1, SecretKey};
+use sha2::Sha512;
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Fuel's registered SLIP-44 coin type.
+pub const FUEL_COIN_TYPE: u32 = 1179993420;
+
+/// Offset added to an index to mark it as hardened.
+pub const HARDENED: u32 = 1 << 31;
+
+const BIP44_PURPOSE: u32 = 44;
+
+/// An error involving key derivation.
+#[derive(Debug, Error)]
+pub enum DerivationError {
+    /// The derived bytes aren't a valid secp256k1 secret key. This is astronomically
+    /// unlikely; BIP-32 says to move on to the next index when it happens.
+    #[error("Invalid derived key: {0}")]
+    InvalidKey(#[from] secp256k1::Error),
+    /// The derivation path isn't of the form `m/44'/0'/0`.
+    #[error("Invalid derivation path `{0}`: {1}")]
+    InvalidPath(String, String),
+    /// The mnemonic phrase has a word that isn't in the BIP-39 word list, the wrong
+    /// number of words, or a wrong checksum.
+    #[error("Invalid mnemonic phrase: {0}")]
+    InvalidMnemonic(#[from] bip39::Error),
+}
+
+/// The configurable part of a BIP-44 derivation path. Networks using different
+/// coin types derive distinct keys, and thus addresses, from the same seed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DerivationConfig {
+    pub coin_type: u32,
+    pub account: u32,
+    pub change: u32,
+}
+
+impl Default for DerivationConfig {
+    fn default() -> Self {
+        Self {
+            coin_type: FUEL_COIN_TYPE,
+            account: 0,
+            change: 0,
+        }
+    }
+}
+
+impl DerivationConfig {
+    pub fn new(coin_type: u32, account: u32, change: u32) -> Self {
+        Self {
+            coin_type,
+            account,
+            change,
+        }
+    }
+
+    /// Returns the full derivation path for the address at `index`.
+    pub fn path(&self, index: u32) -> Vec<u32> {
+        vec![
+            BIP44_PURPOSE | HARDENED,
+            self.coin_type | HARDENED,
+            self.account | HARDENED,
+            self.change,
+            index,
+        ]
+    }
+
+    /// Returns the derivation path for the address at `index` in its usual string
+    /// notation, e.g. `m/44'/1179993420'/0'/0/0`.
+    pub fn path_string(&self, index: u32) -> String {
+        format!(
+            "m/{}'/{}'/{}'/{}/{}",
+            BIP44_PURPOSE, self.coin_type, self.account, self.change, index
+        )
+    }
+}
+
+/// A derivation path, parsed from its usual string notation, e.g.
+/// `m/44'/1179993420'/0'/0/5`. Hardened indices are marked with a trailing `'` or `h`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DerivationPath(Vec<u32>);
+
+impl DerivationPath {
+    /// Returns the indices of the path, with the `HARDENED` bit set on hardened ones.
+    pub fn indices(&self) -> &[u32] {
+        &self.0
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = DerivationError;
+
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: String| DerivationError::InvalidPath(path.to_string(), reason);
+
+        let mut segments = path.trim().split('/');
+        if segments.next() != Some("m") {
+            return Err(invalid("it must start with `m`".to_string()));
+        }
+
+        segments
+            .map(|segment| {
+                let (index, hardened) = match segment.strip_suffix(&['\'', 'h', 'H'][..]) {
+                    Some(index) => (index, true),
+                    None => (segment, false),
+                };
+                let index: u32 = index
+                    .parse()
+                    .map_err(|_| invalid(format!("`{}` isn't a valid index", segment)))?;
+                if index >= HARDENED {
+                    return Err(invalid(format!(
+                        "index {} is too large, indices must be lower than {}",
+                        index, HARDENED
+                    )));
+                }
+
+                Ok(if hardened { index | HARDENED } else { index })
+            })
+            .collect::<Result<_, _>>()
+            .map(DerivationPath)
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "m")?;
+        for index in &self.0 {
+            match index & HARDENED {
+                0 => write!(f, "/{}", index)?,
+                _ => write!(f, "/{}'", index & !HARDENED)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Turns a BIP-39 mnemonic phrase into the seed keys are derived from, with the given
+/// passphrase. The phrase is checked against the BIP-39 English word list and its
+/// checksum, so that a mistyped phrase is rejected rather than deriving another wallet.
+pub fn mnemonic_to_seed(phrase: &str, passphrase: &str) -> Result<[u8; 64], DerivationError> {
+    Ok(Mnemonic::parse(phrase)?.to_seed(passphrase))
+}
+
+/// Derives the secret key at `path` from the given seed, as specified by BIP-32.
+pub fn derive_secret_key(seed: &[u8], path: &[u32]) -> Result<SecretKey, DerivationError> {
+    let secp = Secp256k1::new();
+
+    let master = hmac_sha512(b"Bitcoin seed", seed);
+    let mut secret = SecretKey::from_slice(&master[..32])?;
+    let mut chain_code = [0u8; 32];
+    chain_code.copy_from_slice(&master[32..]);
+
+    for &index in path {
+        let mut data = Vec::with_capacity(37);
+        if index & HARDENED != 0 {
+            data.push(0);
+            data.extend_from_slice(&secret[..]);
+        } else {
+            data.extend_from_slice(&PublicKey::from_secret_key(&secp, &secret).serialize());
+        }
+        data.extend_from_slice(&index.to_be_bytes());
+
+        let derived = hmac_sha512(&chain_code, &data);
+
+        let mut child = SecretKey::from_slice(&derived[..32])?;
+        child.add_assign(&secret[..])?;
+
+        secret = child;
+        chain_code.copy_from_slice(&derived[32..]);
+    }
+
+    Ok(secret)
+}
+
+fn hmac_sha512(key: &[u8], data: &[u8]) -> [u8; 64] {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC accepts keys of any size");
+    mac.update(data);
+
+    let mut output = [0u8; 64];
+    output.copy_from_slice(&mac.finalize().into_bytes());
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test vector 1 from BIP-32
+    const SEED: &str = "000102030405060708090a0b0c0d0e0f";
+
+    #[test]
+    fn derive_bip32_test_vector() {
+        let seed = hex::decode(SEED).unwrap();
+
+        let master = derive_secret_key(&seed, &[]).unwrap();
+        assert_eq!(
+            hex::encode(&master[..]),
+            "e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35"
+        );
+
+        let child = derive_secret_key(&seed, &[HARDENED]).unwrap();
+        assert_eq!(
+            hex::encode(&child[..]),
+            "edb2e14f9ee77d26dd93b4ecede8d16ed408ce149b6cd80b0715a2d911a0afea"
+        );
+    }
+
+    #[test]
+    fn parse_derivation_path() {
+        let path = DerivationPath::from_str("m/44'/1179993420h/0'/0/5").unwrap();
+        assert_eq!(
+            path.indices(),
+            &[44 | HARDENED, FUEL_COIN_TYPE | HARDENED, HARDENED, 0, 5]
+        );
+        assert_eq!(path.to_string(), "m/44'/1179993420'/0'/0/5");
+        assert_eq!(path.indices(), &DerivationConfig::default().path(5)[..],);
+
+        assert!(DerivationPath::from_str("m").unwrap().indices().is_empty());
+
+        for malformed in [
+            "",
+            "44'/0'",
+            "m/",
+            "m//0",
+            "m/x'",
+            "m/-1",
+            "m/0''",
+            "m/2147483648",
+            "m/2147483648'",
+            "m/4294967296",
+        ] {
+            assert!(
+                matches!(
+                    DerivationPath::from_str(malformed),
+                    Err(DerivationError::InvalidPath(..))
+                ),
+                "{} should be rejected",
+                malformed
+            );
+        }
+    }
+
+    #[test]
+    fn mnemonic_seed_test_vector() {
+        // First test vector from the BIP-39 reference implementation
+        let seed = mnemonic_to_seed(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            "TREZOR",
+        )
+        .unwrap();
+        assert_eq!(
+            hex::encode(seed),
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"
+        );
+    }
+
+    #[test]
+    fn reject_invalid_mnemonic() {
+        for phrase in [
+            // Wrong checksum
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon",
+            // `abandn` isn't in the word list
+            "abandn abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            // Too few words
+            "abandon abandon about",
+        ] {
+            assert!(
+                matches!(
+                    mnemonic_to_seed(phrase, ""),
+                    Err(DerivationError::InvalidMnemonic(_))
+                ),
+                "{} should be rejected",
+                phrase
+            );
+        }
+    }
+
+    #[test]
+    fn derivation_path_string() {
+        let config = DerivationConfig::default();
+        assert_eq!(config.path_string(0), "m/44'/1179993420'/0'/0/0");
+
+        let config = DerivationConfig::new(1, 2, 1);
+        assert_eq!(config.path_string(3), "m/44'/1'/2'/1/3");
+    }
+}
@@ -1,12 +1,17 @@
+pub mod derivation;
 pub mod provider;
 pub mod signature;
+mod transport;
+pub mod typed_data;
 pub mod util;
 pub mod wallet;
 
 use signature::Signature;
+use typed_data::{typed_data_hash, Domain, TypedDataError};
 
 use async_trait::async_trait;
 use fuel_tx::{Address, Transaction};
+use fuels_core::{ParamType, Tokenizable};
 use std::error::Error;
 
 /// A wallet instantiated with a locally stored private key
@@ -28,17 +33,59 @@ pub trait Signer: std::fmt::Debug + Send + Sync {
     /// Signs the transaction
     async fn sign_transaction(&self, message: &mut Transaction) -> Result<Signature, Self::Error>;
 
+    /// Signs the ABI-encoded `data`, of the ABI type `param_type`, within `domain`, see
+    /// `typed_data::typed_data_hash`. The signer can be recovered with
+    /// `Signature::recover_typed_data`. Signs the digest with `sign_message` by default,
+    /// which requires the signer's errors to hold the error hashing `data`.
+    async fn sign_typed_data<T: Tokenizable + Clone + Send + Sync>(
+        &self,
+        data: &T,
+        param_type: &ParamType,
+        domain: &Domain,
+    ) -> Result<Signature, Self::Error>
+    where
+        Self::Error: From<TypedDataError>,
+    {
+        let digest = typed_data_hash(data, param_type, domain)?;
+        self.sign_message(digest).await
+    }
+
     /// Returns the signer's Fuel Address
     fn address(&self) -> Address;
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::util::test_helpers::{setup_address_and_coins, setup_test_provider};
-    use fuel_tx::{AssetId, Bytes32, Input, Output, UtxoId};
+    use crate::derivation::DerivationConfig;
+    use crate::provider::{ConsensusParameters, Provider, ProviderError};
+    use crate::signature::{recover_signer, SignatureError};
+    use crate::typed_data::typed_data_hash;
+    use crate::util::test_helpers::{
+        setup_address_and_coins, setup_address_and_coins_with_asset,
+        setup_address_and_coins_with_assets, setup_test_provider, setup_test_provider_and_wallet,
+    };
+    use crate::util::{
+        address_from_public_key, change_outputs, estimated_byte_size, public_key_from_bytes,
+        transaction_id, validate_without_signature, verify_and_recover, verify_matches,
+        verify_message, ValidationError,
+    };
+    use crate::wallet::WalletError;
+    use fuel_gql_client::client::schema::coin::{Coin, CoinStatus};
+    use fuel_gql_client::client::types::TransactionStatus;
+    use fuel_gql_client::client::FuelClient;
+    use fuel_tx::{AssetId, Bytes32, Input, Output, Receipt, UtxoId};
+    use fuels_core::errors::Error;
+    use fuels_core::parameters::TxParameters;
+    use futures::{StreamExt, TryStreamExt};
     use rand::{rngs::StdRng, RngCore, SeedableRng};
-    use secp256k1::SecretKey;
+    use secp256k1::{PublicKey, Secp256k1, SecretKey};
+    use std::net::SocketAddr;
     use std::str::FromStr;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
 
     use super::*;
 
@@ -70,6 +117,216 @@ mod tests {
         signature.verify(message, recovered_address).unwrap();
     }
 
+    #[tokio::test]
+    async fn verify_message_signer() {
+        let (secret, _) = setup_address_and_coins(1, 1);
+        let (other_secret, _) = setup_address_and_coins(1, 1);
+
+        let (provider, _) = setup_test_provider(vec![]).await;
+        let wallet = LocalWallet::new_from_private_key(secret, provider.clone()).unwrap();
+        let other_wallet = LocalWallet::new_from_private_key(other_secret, provider).unwrap();
+
+        let message = "my message";
+        let signature = wallet.sign_message(message).await.unwrap();
+
+        verify_message(message, &signature, &wallet.address()).unwrap();
+
+        let result = verify_message(message, &signature, &other_wallet.address());
+        assert!(matches!(result, Err(Error::SignatureVerification(_))));
+
+        // Same signer, different message
+        let result = verify_message("another message", &signature, &wallet.address());
+        assert!(matches!(result, Err(Error::SignatureVerification(_))));
+    }
+
+    #[tokio::test]
+    async fn recover_message_signer() {
+        let (secret, _) = setup_address_and_coins(1, 1);
+        let (provider, _) = setup_test_provider(vec![]).await;
+        let wallet = LocalWallet::new_from_private_key(secret, provider).unwrap();
+
+        let message = "login nonce 1234";
+        let signature = wallet.sign_message(message).await.unwrap();
+
+        assert_eq!(
+            verify_and_recover(message, &signature).unwrap(),
+            wallet.address()
+        );
+        assert!(verify_matches(message, &signature, &wallet.address()).unwrap());
+
+        // A tampered message recovers someone else
+        let tampered = "login nonce 1235";
+        assert_ne!(
+            verify_and_recover(tampered, &signature).unwrap(),
+            wallet.address()
+        );
+        assert!(!verify_matches(tampered, &signature, &wallet.address()).unwrap());
+    }
+
+    #[tokio::test]
+    async fn address_from_either_public_key_encoding() {
+        let (secret, _) = setup_address_and_coins(1, 1);
+        let (provider, _) = setup_test_provider(vec![]).await;
+        let wallet = LocalWallet::new_from_private_key(secret, provider).unwrap();
+
+        let public_key = PublicKey::from_secret_key(&Secp256k1::new(), &secret);
+        let compressed = public_key_from_bytes(&public_key.serialize()).unwrap();
+        let uncompressed = public_key_from_bytes(&public_key.serialize_uncompressed()).unwrap();
+
+        assert_eq!(address_from_public_key(&compressed), wallet.address());
+        assert_eq!(address_from_public_key(&uncompressed), wallet.address());
+
+        let truncated = &public_key.serialize_uncompressed()[1..];
+        assert!(matches!(
+            public_key_from_bytes(truncated),
+            Err(Error::InvalidData)
+        ));
+    }
+
+    #[test]
+    fn transaction_byte_size() {
+        use fuel_types::bytes::SerializableVec;
+
+        let mut tx = Transaction::script(
+            0,
+            1_000_000,
+            0,
+            0,
+            // RET(ONE)
+            vec![0x24, 0x40, 0x00, 0x00],
+            vec![],
+            vec![Input::coin(
+                UtxoId::default(),
+                Address::zeroed(),
+                10,
+                AssetId::default(),
+                0,
+                0,
+                vec![],
+                vec![],
+            )],
+            vec![Output::coin(Address::zeroed(), 1, AssetId::default())],
+            vec![],
+        );
+
+        assert_eq!(estimated_byte_size(&tx), tx.to_bytes().len());
+    }
+
+    #[test]
+    fn validate_transaction_without_witness() {
+        let tx = Transaction::script(
+            0,
+            1_000_000,
+            0,
+            0,
+            // RET(ONE)
+            vec![0x24, 0x40, 0x00, 0x00],
+            vec![],
+            vec![Input::coin(
+                UtxoId::default(),
+                Address::zeroed(),
+                10,
+                AssetId::default(),
+                0,
+                0,
+                vec![],
+                vec![],
+            )],
+            vec![Output::coin(Address::zeroed(), 1, AssetId::default())],
+            vec![],
+        );
+
+        assert_eq!(
+            validate_without_signature(&tx),
+            Err(vec![ValidationError::MissingWitness(0, 0)])
+        );
+    }
+
+    #[tokio::test]
+    async fn signatures_are_deterministic() {
+        let secret =
+            SecretKey::from_str("5f70feeff1f229e4a95e1056e8b4d80d0b24b565674860cc213bdb07127ce1b1")
+                .unwrap();
+        let (provider, _) = setup_test_provider(vec![]).await;
+        let wallet = LocalWallet::new_from_private_key(secret, provider).unwrap();
+
+        let first = wallet.sign_message("my message").await.unwrap();
+        let second = wallet.sign_message("my message").await.unwrap();
+        assert_eq!(first.compact, second.compact);
+
+        let with_recovery = first.with_recovery();
+        assert!(with_recovery.recovery_id <= 1);
+        assert_eq!(Signature::from(with_recovery), first);
+    }
+
+    #[tokio::test]
+    async fn sign_and_recover_typed_data() {
+        let mut rng = StdRng::seed_from_u64(2322u64);
+        let mut secret_seed = [0u8; 32];
+        rng.fill_bytes(&mut secret_seed);
+
+        let secret =
+            SecretKey::from_slice(&secret_seed).expect("Failed to generate random secret!");
+
+        let (provider, _) = setup_test_provider(vec![]).await;
+        let wallet = LocalWallet::new_from_private_key(secret, provider).unwrap();
+
+        let domain = Domain::new("my dapp", "1", 0, Default::default());
+
+        // Both payloads encode to the same bytes, but have different types
+        let permit = (10u64, false);
+        let permit_type = ParamType::Tuple(vec![ParamType::U64, ParamType::Bool]);
+        let other = (10u64, 0u64);
+        let other_type = ParamType::Tuple(vec![ParamType::U64, ParamType::U64]);
+
+        assert_ne!(
+            typed_data_hash(&permit, &permit_type, &domain).unwrap(),
+            typed_data_hash(&other, &other_type, &domain).unwrap()
+        );
+
+        let signature = wallet
+            .sign_typed_data(&permit, &permit_type, &domain)
+            .await
+            .unwrap();
+
+        let recovered_address = signature
+            .recover_typed_data(&permit, &permit_type, &domain)
+            .unwrap();
+        assert_eq!(wallet.address(), recovered_address);
+
+        let recovered_address = signature
+            .recover_typed_data(&other, &other_type, &domain)
+            .unwrap();
+        assert_ne!(wallet.address(), recovered_address);
+    }
+
+    #[tokio::test]
+    async fn wallet_from_private_key_str() {
+        let key = "5f70feeff1f229e4a95e1056e8b4d80d0b24b565674860cc213bdb07127ce1b1";
+        let (provider, _) = setup_test_provider(vec![]).await;
+
+        let expected =
+            LocalWallet::new_from_private_key(SecretKey::from_str(key).unwrap(), provider.clone())
+                .unwrap();
+        for key in [key.to_string(), format!("0x{}", key)] {
+            let wallet = LocalWallet::new_from_private_key_str(&key, provider.clone()).unwrap();
+            assert_eq!(wallet.address(), expected.address());
+        }
+
+        for malformed in [
+            "0x5f70",
+            "zz70feeff1f229e4a95e1056e8b4d80d0b24b565674860cc213bdb07127ce1b1",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+        ] {
+            let result = LocalWallet::new_from_private_key_str(malformed, provider.clone());
+            assert!(matches!(result, Err(WalletError::InvalidPrivateKey(_))));
+            assert!(matches!(
+                Error::from(result.unwrap_err()),
+                Error::InvalidPrivateKey(_)
+            ));
+        }
+    }
+
     #[tokio::test]
     async fn sign_tx_and_verify() {
         let secret =
@@ -124,6 +381,487 @@ mod tests {
         signature.verify(&tx.id(), recovered_address).unwrap();
     }
 
+    #[tokio::test]
+    async fn run_raw_script() {
+        let (pk, coins) = setup_address_and_coins(1, 1);
+        let (provider, _) = setup_test_provider(coins).await;
+        let wallet = LocalWallet::new_from_private_key(pk, provider.clone()).unwrap();
+
+        let inputs = wallet
+            .get_asset_inputs_for_amount(AssetId::default(), 1, 0)
+            .await
+            .unwrap();
+        let outputs = vec![Output::change(wallet.address(), 0, AssetId::default())];
+
+        let receipts = provider
+            .run_script(
+                &wallet,
+                hex::decode("24400000").unwrap(),
+                vec![],
+                inputs,
+                outputs,
+                &TxParameters::default(),
+            )
+            .await
+            .unwrap();
+
+        assert!(receipts
+            .iter()
+            .any(|r| matches!(r, Receipt::ScriptResult { .. })));
+    }
+
+    /// Requests received by a `counting_proxy`.
+    #[derive(Default)]
+    struct RequestCounts {
+        total: AtomicUsize,
+        in_flight: AtomicUsize,
+        max_in_flight: AtomicUsize,
+    }
+
+    /// Starts a proxy in front of the node at `node` which counts the requests sent to it.
+    /// Each request is held for `delay` before being forwarded, so that concurrent
+    /// requests overlap. A request is in flight until its response starts.
+    async fn counting_proxy(node: SocketAddr, delay: Duration) -> (SocketAddr, Arc<RequestCounts>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let counts = Arc::new(RequestCounts::default());
+
+        let proxy_counts = counts.clone();
+        tokio::spawn(async move {
+            loop {
+                let (client, _) = listener.accept().await.unwrap();
+                let upstream = TcpStream::connect(node).await.unwrap();
+                tokio::spawn(proxy_connection(
+                    client,
+                    upstream,
+                    proxy_counts.clone(),
+                    delay,
+                ));
+            }
+        });
+
+        (address, counts)
+    }
+
+    async fn proxy_connection(
+        client: TcpStream,
+        upstream: TcpStream,
+        counts: Arc<RequestCounts>,
+        delay: Duration,
+    ) {
+        let (mut client_read, mut client_write) = client.into_split();
+        let (mut upstream_read, mut upstream_write) = upstream.into_split();
+        // Whether the last request of this connection hasn't been answered yet
+        let pending = Arc::new(AtomicBool::new(false));
+
+        let requests = {
+            let counts = counts.clone();
+            let pending = pending.clone();
+            async move {
+                let mut buffer = vec![];
+                let mut chunk = [0u8; 4096];
+                loop {
+                    while let Some(len) = request_len(&buffer) {
+                        let request: Vec<u8> = buffer.drain(..len).collect();
+                        counts.total.fetch_add(1, Ordering::SeqCst);
+                        let current = counts.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                        counts.max_in_flight.fetch_max(current, Ordering::SeqCst);
+                        pending.store(true, Ordering::SeqCst);
+
+                        tokio::time::sleep(delay).await;
+                        if upstream_write.write_all(&request).await.is_err() {
+                            return;
+                        }
+                    }
+                    match client_read.read(&mut chunk).await {
+                        Ok(0) | Err(_) => return,
+                        Ok(n) => buffer.extend_from_slice(&chunk[..n]),
+                    }
+                }
+            }
+        };
+        let responses = async move {
+            let mut chunk = [0u8; 4096];
+            loop {
+                match upstream_read.read(&mut chunk).await {
+                    Ok(0) | Err(_) => return,
+                    Ok(n) => {
+                        if pending.swap(false, Ordering::SeqCst) {
+                            counts.in_flight.fetch_sub(1, Ordering::SeqCst);
+                        }
+                        if client_write.write_all(&chunk[..n]).await.is_err() {
+                            return;
+                        }
+                    }
+                }
+            }
+        };
+
+        tokio::join!(requests, responses);
+    }
+
+    /// Length of the first HTTP request in `buffer`, once it was received entirely.
+    fn request_len(buffer: &[u8]) -> Option<usize> {
+        let head = buffer.windows(4).position(|w| w == b"\r\n\r\n")? + 4;
+        let body = String::from_utf8_lossy(&buffer[..head])
+            .lines()
+            .find_map(|line| {
+                let (name, value) = line.split_once(':')?;
+                if name.eq_ignore_ascii_case("content-length") {
+                    value.trim().parse::<usize>().ok()
+                } else {
+                    None
+                }
+            })
+            .unwrap_or(0);
+
+        (buffer.len() >= head + body).then(|| head + body)
+    }
+
+    #[tokio::test]
+    async fn provider_limits_concurrent_requests() {
+        let (_, node) = setup_test_provider(vec![]).await;
+        let (proxy, counts) = counting_proxy(node, Duration::from_millis(20)).await;
+        let provider = Provider::new(FuelClient::from(proxy)).with_max_concurrency(3);
+
+        let requests = (0..20).map(|_| {
+            let provider = provider.clone();
+            tokio::spawn(async move { provider.chain_info().await })
+        });
+        for request in futures::future::join_all(requests).await {
+            request.unwrap().unwrap();
+        }
+
+        assert_eq!(counts.total.load(Ordering::SeqCst), 20);
+        assert_eq!(counts.max_in_flight.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn zero_max_concurrency_sends_requests_one_at_a_time() {
+        let (_, node) = setup_test_provider(vec![]).await;
+        let (proxy, counts) = counting_proxy(node, Duration::from_millis(20)).await;
+        let provider = Provider::new(FuelClient::from(proxy)).with_max_concurrency(0);
+
+        let (first, second) = tokio::join!(provider.chain_info(), provider.chain_info());
+        first.unwrap();
+        second.unwrap();
+
+        assert_eq!(counts.total.load(Ordering::SeqCst), 2);
+        assert_eq!(counts.max_in_flight.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn provider_sends_requests_with_the_given_client() {
+        let (_, node) = setup_test_provider(vec![]).await;
+        let client = || {
+            reqwest::Client::builder()
+                .timeout(Duration::from_millis(100))
+                .build()
+                .unwrap()
+        };
+
+        let (proxy, counts) = counting_proxy(node, Duration::ZERO).await;
+        let provider = Provider::from_client(client(), &proxy.to_string()).unwrap();
+        provider.chain_info().await.unwrap();
+        assert_eq!(counts.total.load(Ordering::SeqCst), 1);
+
+        // The proxy holds the request longer than the client's timeout
+        let (proxy, counts) = counting_proxy(node, Duration::from_millis(500)).await;
+        let provider = Provider::from_client(client(), &proxy.to_string()).unwrap();
+        let result = provider.chain_info().await;
+        assert!(matches!(result, Err(ProviderError::ConnectionError(_))));
+        assert_eq!(counts.total.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn derive_with_different_coin_types() {
+        let seed = [7u8; 32];
+        let (provider, _) = setup_test_provider(vec![]).await;
+
+        let fuel = DerivationConfig::default();
+        let custom = DerivationConfig::new(1, 0, 0);
+
+        let wallet_1 = LocalWallet::new_from_seed(&seed, fuel, 0, provider.clone()).unwrap();
+        let wallet_2 = LocalWallet::new_from_seed(&seed, custom, 0, provider.clone()).unwrap();
+        let wallet_3 = LocalWallet::new_from_seed(&seed, fuel, 0, provider).unwrap();
+
+        assert_ne!(wallet_1.address(), wallet_2.address());
+        assert_eq!(wallet_1.address(), wallet_3.address());
+    }
+
+    #[tokio::test]
+    async fn recover_signer_from_witness() {
+        let secret =
+            SecretKey::from_str("5f70feeff1f229e4a95e1056e8b4d80d0b24b565674860cc213bdb07127ce1b1")
+                .unwrap();
+
+        let (provider, _) = setup_test_provider(vec![]).await;
+        let wallet = LocalWallet::new_from_private_key(secret, provider).unwrap();
+
+        let input_coin = Input::coin(
+            UtxoId::new(Bytes32::zeroed(), 0),
+            wallet.address(),
+            10000000,
+            AssetId::from([0u8; 32]),
+            0,
+            0,
+            vec![],
+            vec![],
+        );
+        let build_tx = |gas_price| {
+            Transaction::script(
+                gas_price,
+                1000000,
+                0,
+                0,
+                hex::decode("24400000").unwrap(),
+                vec![],
+                vec![input_coin.clone()],
+                vec![],
+                vec![],
+            )
+        };
+
+        let mut tx = build_tx(0);
+        wallet.sign_transaction(&mut tx).await.unwrap();
+
+        assert_eq!(recover_signer(&tx, 0).unwrap(), wallet.address());
+        assert!(matches!(
+            recover_signer(&tx, 1),
+            Err(SignatureError::MissingWitness(1))
+        ));
+
+        // Same witness on a different transaction, i.e. with a different id
+        let mut tampered = build_tx(1);
+        tampered.set_witnesses(tx.witnesses().to_vec());
+
+        assert_ne!(recover_signer(&tampered, 0).unwrap(), wallet.address());
+    }
+
+    #[tokio::test]
+    async fn sign_tx_at_witness_indexes() {
+        let (pk_1, _) = setup_address_and_coins(1, 1);
+        let (pk_2, _) = setup_address_and_coins(1, 1);
+
+        let (provider, _) = setup_test_provider(vec![]).await;
+        let wallet_1 = LocalWallet::new_from_private_key(pk_1, provider.clone()).unwrap();
+        let wallet_2 = LocalWallet::new_from_private_key(pk_2, provider).unwrap();
+
+        // Each wallet's coin points to its own witness
+        let inputs = vec![
+            Input::coin(
+                UtxoId::new(Bytes32::zeroed(), 0),
+                wallet_1.address(),
+                1,
+                AssetId::default(),
+                0,
+                0,
+                vec![],
+                vec![],
+            ),
+            Input::coin(
+                UtxoId::new(Bytes32::zeroed(), 1),
+                wallet_2.address(),
+                1,
+                AssetId::default(),
+                1,
+                0,
+                vec![],
+                vec![],
+            ),
+        ];
+
+        let mut tx = Transaction::script(
+            0,
+            1000000,
+            0,
+            0,
+            hex::decode("24400000").unwrap(),
+            vec![],
+            inputs,
+            vec![],
+            vec![],
+        );
+
+        // Sign out of order to make sure the index, not the call order, decides the position
+        wallet_2.sign_transaction_at(&mut tx, 1).await.unwrap();
+        wallet_1.sign_transaction_at(&mut tx, 0).await.unwrap();
+
+        assert_eq!(tx.witnesses().len(), 2);
+
+        for (index, wallet) in [&wallet_1, &wallet_2].iter().enumerate() {
+            let signature = Signature::try_from(tx.witnesses()[index].as_ref()).unwrap();
+            assert_eq!(signature.recover(&tx.id()).unwrap(), wallet.address());
+        }
+
+        // Wallet 1's coin references witness 0, so it can't sign at index 1
+        let result = wallet_1.sign_transaction_at(&mut tx, 1).await;
+        assert!(matches!(result, Err(WalletError::WitnessIndexMismatch(1))));
+    }
+
+    #[tokio::test]
+    async fn add_signatures_to_shared_tx() {
+        let (pk_1, _) = setup_address_and_coins(1, 1);
+        let (pk_2, _) = setup_address_and_coins(1, 1);
+        let (pk_3, _) = setup_address_and_coins(1, 1);
+        let (provider, _) = setup_test_provider(vec![]).await;
+        let wallet_1 = LocalWallet::new_from_private_key(pk_1, provider.clone()).unwrap();
+        let wallet_2 = LocalWallet::new_from_private_key(pk_2, provider.clone()).unwrap();
+        let wallet_3 = LocalWallet::new_from_private_key(pk_3, provider).unwrap();
+
+        let inputs = [&wallet_1, &wallet_2]
+            .iter()
+            .enumerate()
+            .map(|(index, wallet)| {
+                Input::coin(
+                    UtxoId::new(Bytes32::zeroed(), index as u8),
+                    wallet.address(),
+                    1,
+                    AssetId::default(),
+                    index as u8,
+                    0,
+                    vec![],
+                    vec![],
+                )
+            })
+            .collect();
+        let mut tx = Transaction::script(
+            0,
+            1000000,
+            0,
+            0,
+            hex::decode("24400000").unwrap(),
+            vec![],
+            inputs,
+            vec![],
+            vec![],
+        );
+
+        // Each party adds its signature to the partially signed transaction
+        wallet_2.add_signature_to_tx(&mut tx).await.unwrap();
+        wallet_1.add_signature_to_tx(&mut tx).await.unwrap();
+
+        assert_eq!(tx.witnesses().len(), 2);
+        for (index, wallet) in [&wallet_1, &wallet_2].iter().enumerate() {
+            let signature = Signature::try_from(tx.witnesses()[index].as_ref()).unwrap();
+            assert_eq!(signature.recover(&tx.id()).unwrap(), wallet.address());
+        }
+
+        let result = wallet_3.add_signature_to_tx(&mut tx).await;
+        assert!(matches!(result, Err(WalletError::NoOwnedInputs)));
+    }
+
+    #[tokio::test]
+    async fn sign_unsigned_transfer_tx() {
+        let (pk_1, mut coins) = setup_address_and_coins(1, 10);
+        let (pk_2, coins_2) = setup_address_and_coins(1, 10);
+        let (pk_3, coins_3) = setup_address_and_coins(1, 10);
+        coins.extend(coins_2);
+        coins.extend(coins_3);
+
+        let (provider, _) = setup_test_provider(coins).await;
+        let wallet_1 = LocalWallet::new_from_private_key(pk_1, provider.clone()).unwrap();
+        let wallet_2 = LocalWallet::new_from_private_key(pk_2, provider.clone()).unwrap();
+        let wallet_3 = LocalWallet::new_from_private_key(pk_3, provider.clone()).unwrap();
+
+        // The witness indexes are assigned when building the transaction
+        let mut inputs = vec![];
+        for wallet in [&wallet_1, &wallet_2] {
+            let wallet_inputs = wallet
+                .get_asset_inputs_for_amount(AssetId::default(), 10, 0)
+                .await
+                .unwrap();
+            inputs.extend(wallet_inputs);
+        }
+        let outputs = vec![Output::coin(wallet_3.address(), 20, AssetId::default())];
+
+        let result = provider.build_transfer_tx_unsigned(&inputs, &outputs, 1_000_000, 1);
+        assert!(result.is_err());
+
+        let mut tx = provider
+            .build_transfer_tx_unsigned(&inputs, &outputs, 1_000_000, 2)
+            .unwrap();
+        assert_eq!(tx.witnesses().len(), 2);
+
+        wallet_2.add_signature_to_tx(&mut tx).await.unwrap();
+        wallet_1.add_signature_to_tx(&mut tx).await.unwrap();
+        assert_eq!(tx.witnesses().len(), 2);
+
+        provider.send_transaction(&tx).await.unwrap();
+        let balances = wallet_3.get_balances().await.unwrap();
+        assert_eq!(balances.get(&AssetId::default()), Some(&30));
+    }
+
+    #[tokio::test]
+    async fn connect_to_unreachable_node() {
+        let secret =
+            SecretKey::from_str("5f70feeff1f229e4a95e1056e8b4d80d0b24b565674860cc213bdb07127ce1b1")
+                .unwrap();
+
+        // Nothing is listening on this port, so the handshake must fail.
+        let result = LocalWallet::connect(secret, "http://127.0.0.1:1", "local_testnet").await;
+
+        assert!(matches!(result, Err(WalletError::ProviderError(_))));
+    }
+
+    #[tokio::test]
+    async fn connect_to_node_on_another_network() {
+        let (secret, _) = setup_address_and_coins(1, 1);
+        let (provider, address) = setup_test_provider(vec![]).await;
+        let url = format!("http://{}", address);
+        let network = provider.chain_info().await.unwrap().name;
+
+        let wallet = LocalWallet::connect(secret, &url, &network).await.unwrap();
+        assert_eq!(wallet.provider.chain_info().await.unwrap().name, network);
+
+        let result = LocalWallet::connect(secret, &url, "another_network").await;
+        assert!(matches!(
+            result,
+            Err(WalletError::ProviderError(ProviderError::UnexpectedNetwork { expected, actual }))
+                if expected == "another_network" && actual == network
+        ));
+    }
+
+    #[tokio::test]
+    async fn node_errors_are_surfaced() {
+        let (pk, _) = setup_address_and_coins(1, 1);
+        let (provider, _) = setup_test_provider(vec![]).await;
+        let wallet = LocalWallet::new_from_private_key(pk, provider.clone()).unwrap();
+
+        // This coin was never created in the node, so the transaction is invalid.
+        let input_coin = Input::coin(
+            UtxoId::new(Bytes32::zeroed(), 0),
+            wallet.address(),
+            1,
+            AssetId::default(),
+            0,
+            0,
+            vec![],
+            vec![],
+        );
+
+        let mut tx = provider.build_transfer_tx(&[input_coin], &[]);
+        wallet.sign_transaction(&mut tx).await.unwrap();
+
+        let error: Error = provider.send_transaction(&tx).await.unwrap_err().into();
+
+        assert!(matches!(error, Error::Node(_)));
+    }
+
+    #[tokio::test]
+    async fn unreachable_node_is_not_a_node_error() {
+        // Nothing listens on the port of a listener that was dropped
+        let address = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let provider = Provider::connect(address).await.unwrap();
+
+        let error = provider.chain_info().await.unwrap_err();
+        assert!(matches!(error, ProviderError::ConnectionError(_)));
+        assert!(matches!(Error::from(error), Error::InfrastructureError(_)));
+    }
+
     #[tokio::test]
     async fn send_transaction() {
         // Setup two sets of coins, one for each wallet, each containing 1 coin with 1 amount.
@@ -146,10 +884,11 @@ mod tests {
         assert_eq!(wallet_2_initial_coins.len(), 1);
 
         // Transfer 1 from wallet 1 to wallet 2
-        let _receipts = wallet_1
+        let result = wallet_1
             .transfer(&wallet_2.address(), 1, Default::default())
             .await
             .unwrap();
+        assert_ne!(result.tx_id, Bytes32::zeroed());
 
         // Currently ignoring the effect on wallet 1, as coins aren't being marked as spent for now
         let _wallet_1_final_coins = wallet_1.get_coins().await.unwrap();
@@ -169,12 +908,837 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn transfer_coins_with_change() {
-        // Setup two sets of coins, one for each wallet, each containing 1 coin with 5 amounts each.
-        let (pk_1, mut coins_1) = setup_address_and_coins(1, 5);
-        let (pk_2, coins_2) = setup_address_and_coins(1, 5);
+    async fn submit_without_waiting() {
+        let (pk, coins) = setup_address_and_coins(1, 5);
+        let (provider, _) = setup_test_provider(coins).await;
+        let wallet = LocalWallet::new_from_private_key(pk, provider.clone()).unwrap();
 
-        coins_1.extend(coins_2);
+        let inputs = wallet
+            .get_asset_inputs_for_amount(AssetId::default(), 1, 0)
+            .await
+            .unwrap();
+        let outputs = vec![
+            Output::coin(wallet.address(), 1, AssetId::default()),
+            Output::change(wallet.address(), 0, AssetId::default()),
+        ];
+        let mut tx = provider.build_transfer_tx(&inputs, &outputs);
+        wallet.sign_transaction(&mut tx).await.unwrap();
+
+        let id = provider.submit_transaction(&tx).await.unwrap();
+        assert_eq!(id, tx.id());
+        assert_ne!(id, Bytes32::zeroed());
+
+        // Wait for the transaction to be executed before asking for its receipts
+        let statuses: Vec<TransactionStatus> = provider
+            .subscribe_transaction_status(id)
+            .await
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+        assert!(matches!(
+            statuses.last(),
+            Some(TransactionStatus::Success { .. })
+        ));
+
+        let receipts = provider.get_receipts(&id).await.unwrap();
+        assert!(!receipts.is_empty());
+    }
+
+    #[tokio::test]
+    async fn transaction_status_stream() {
+        let (pk, coins) = setup_address_and_coins(1, 5);
+        let (provider, _) = setup_test_provider(coins).await;
+        let wallet = LocalWallet::new_from_private_key(pk, provider.clone()).unwrap();
+
+        let inputs = wallet
+            .get_asset_inputs_for_amount(AssetId::default(), 1, 0)
+            .await
+            .unwrap();
+        let outputs = vec![
+            Output::coin(wallet.address(), 1, AssetId::default()),
+            Output::change(wallet.address(), 0, AssetId::default()),
+        ];
+        let mut tx = provider.build_transfer_tx(&inputs, &outputs);
+        wallet.sign_transaction(&mut tx).await.unwrap();
+
+        provider.client.submit(&tx).await.unwrap();
+
+        let statuses: Vec<TransactionStatus> = provider
+            .subscribe_transaction_status(tx.id())
+            .await
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            statuses.last(),
+            Some(TransactionStatus::Success { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn block_stream() {
+        let (pk, coins) = setup_address_and_coins(1, 5);
+        let (provider, _) = setup_test_provider(coins).await;
+        let wallet = LocalWallet::new_from_private_key(pk, provider.clone()).unwrap();
+
+        let mut blocks = Box::pin(provider.subscribe_blocks().await.unwrap());
+        let first = blocks.next().await.unwrap().unwrap();
+
+        // The node produces a block for each transaction
+        wallet
+            .transfer(&wallet.address(), 1, Default::default())
+            .await
+            .unwrap();
+        let next = blocks.next().await.unwrap().unwrap();
+
+        assert!(next.height.0 > first.height.0);
+    }
+
+    #[tokio::test]
+    async fn watch_only_wallet() {
+        let (pk, coins) = setup_address_and_coins(2, 5);
+        let (provider, _) = setup_test_provider(coins).await;
+
+        let wallet = LocalWallet::new_from_private_key(pk, provider.clone()).unwrap();
+        let watch_only = wallet.to_watch_only();
+
+        assert_eq!(watch_only.address(), wallet.address());
+
+        // Queries work as they do on the full wallet
+        assert_eq!(watch_only.get_coins().await.unwrap().len(), 2);
+        let balances = watch_only.get_balances().await.unwrap();
+        assert_eq!(balances.get(&AssetId::default()), Some(&10));
+
+        // Signing a transfer doesn't
+        let inputs = wallet
+            .get_asset_inputs_for_amount(AssetId::default(), 1, 0)
+            .await
+            .unwrap();
+        let outputs = vec![Output::coin(wallet.address(), 1, AssetId::default())];
+        let mut tx = provider.build_transfer_tx(&inputs, &outputs);
+
+        let result = watch_only.sign_transaction(&mut tx).await;
+        assert!(matches!(result, Err(WalletError::WatchOnly)));
+    }
+
+    #[tokio::test]
+    async fn concurrent_transfers_use_distinct_coins() {
+        let (pk_1, mut coins_1) = setup_address_and_coins(3, 1);
+        let (pk_2, coins_2) = setup_address_and_coins(1, 1);
+
+        coins_1.extend(coins_2);
+
+        let (provider, _) = setup_test_provider(coins_1).await;
+
+        let wallet_1 = LocalWallet::new_from_private_key(pk_1, provider.clone()).unwrap();
+        let wallet_2 = LocalWallet::new_from_private_key(pk_2, provider).unwrap();
+
+        // Each transfer needs exactly one coin; without reservations they'd all pick
+        // the same one and two of them would be rejected.
+        let to = wallet_2.address();
+        let (first, second, third) = tokio::join!(
+            wallet_1.transfer(&to, 1, Default::default()),
+            wallet_1.transfer(&to, 1, Default::default()),
+            wallet_1.transfer(&to, 1, Default::default()),
+        );
+
+        assert!(first.is_ok());
+        assert!(second.is_ok());
+        assert!(third.is_ok());
+
+        let wallet_2_final_coins = wallet_2.get_coins().await.unwrap();
+        assert_eq!(wallet_2_final_coins.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn available_balance_excludes_pending_transfers() {
+        let (pk, coins) = setup_address_and_coins(2, 5);
+        let (provider, _) = setup_test_provider(coins).await;
+        let wallet = LocalWallet::new_from_private_key(pk, provider).unwrap();
+
+        let asset_id = AssetId::default();
+        assert_eq!(wallet.available_balance(asset_id).await.unwrap(), 10);
+
+        // The coins of a transfer are reserved until it goes through
+        let inputs = wallet
+            .reserve_asset_inputs_for_amount(asset_id, 1, 0)
+            .await
+            .unwrap();
+        assert_eq!(wallet.available_balance(asset_id).await.unwrap(), 5);
+        assert_eq!(
+            wallet.get_balances().await.unwrap().get(&asset_id),
+            Some(&10)
+        );
+
+        wallet.release_inputs(&inputs);
+        assert_eq!(wallet.available_balance(asset_id).await.unwrap(), 10);
+
+        // Once sent, only the change is left to spend, and the spent coin isn't kept
+        // reserved
+        let to = Address::zeroed();
+        wallet.transfer(&to, 3, asset_id).await.unwrap();
+        assert_eq!(wallet.available_balance(asset_id).await.unwrap(), 7);
+        assert!(wallet.reserved_coins.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn change_output_per_input_asset() {
+        let owner = Address::new([1u8; 32]);
+        let other_asset = AssetId::new([2u8; 32]);
+        let coin = |index: u8, asset_id: AssetId| {
+            let utxo_id = UtxoId::new(Bytes32::new([index; 32]), 0);
+            Input::coin(utxo_id, owner, 5, asset_id, 0, 0, vec![], vec![])
+        };
+        let inputs = vec![
+            coin(1, other_asset),
+            coin(2, AssetId::default()),
+            coin(3, other_asset),
+        ];
+
+        let change_to = Address::new([3u8; 32]);
+        assert_eq!(
+            change_outputs(&inputs, change_to),
+            vec![
+                Output::change(change_to, 0, other_asset),
+                Output::change(change_to, 0, AssetId::default()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn local_transaction_id_matches_node() {
+        let (pk_1, mut coins) = setup_address_and_coins(1, 10);
+        let (pk_2, coins_2) = setup_address_and_coins(1, 10);
+        coins.extend(coins_2);
+
+        let (provider, _) = setup_test_provider(coins).await;
+        let wallet_1 = LocalWallet::new_from_private_key(pk_1, provider.clone()).unwrap();
+        let wallet_2 = LocalWallet::new_from_private_key(pk_2, provider.clone()).unwrap();
+
+        let inputs = wallet_1
+            .get_asset_inputs_for_amount(AssetId::default(), 4, 0)
+            .await
+            .unwrap();
+        let outputs = vec![
+            Output::coin(wallet_2.address(), 4, AssetId::default()),
+            Output::change(wallet_1.address(), 0, AssetId::default()),
+        ];
+        let mut tx = provider.build_transfer_tx(&inputs, &outputs);
+
+        let unsigned_id = transaction_id(&tx);
+        wallet_1.sign_transaction(&mut tx).await.unwrap();
+        assert_eq!(transaction_id(&tx), unsigned_id);
+
+        let submitted_id = provider.submit_transaction(&tx).await.unwrap();
+        assert_eq!(submitted_id, unsigned_id);
+    }
+
+    #[tokio::test]
+    async fn base_asset_info() {
+        let (provider, _) = setup_test_provider_and_wallet().await;
+
+        match provider.asset_info(&AssetId::default()).await {
+            Ok(info) => assert_eq!(info.asset_id, AssetId::default()),
+            Err(e) => assert!(matches!(e, ProviderError::Unsupported(_)), "{}", e),
+        }
+    }
+
+    #[tokio::test]
+    async fn messages_not_exposed_by_the_node() {
+        let (provider, wallet) = setup_test_provider_and_wallet().await;
+
+        match provider.get_messages(&wallet.address()).await {
+            Ok(messages) => assert!(messages.is_empty()),
+            Err(e) => assert!(matches!(e, ProviderError::Unsupported(_)), "{}", e),
+        }
+    }
+
+    #[tokio::test]
+    async fn transfer_with_explicit_inputs() {
+        let (pk_1, mut coins_1) = setup_address_and_coins(3, 5);
+        let (pk_2, coins_2) = setup_address_and_coins(1, 5);
+        coins_1.extend(coins_2);
+
+        let (provider, _) = setup_test_provider(coins_1).await;
+        let wallet_1 = LocalWallet::new_from_private_key(pk_1, provider.clone()).unwrap();
+        let wallet_2 = LocalWallet::new_from_private_key(pk_2, provider).unwrap();
+
+        // Spend the first and last coins, leaving the middle one untouched
+        let coins = wallet_1.get_coins().await.unwrap();
+        let chosen: Vec<UtxoId> = vec![
+            UtxoId::from(coins[0].utxo_id.clone()),
+            UtxoId::from(coins[2].utxo_id.clone()),
+        ];
+        let untouched = UtxoId::from(coins[1].utxo_id.clone());
+
+        let inputs: Vec<Input> = chosen
+            .iter()
+            .map(|utxo_id| {
+                Input::coin(
+                    *utxo_id,
+                    wallet_1.address(),
+                    5,
+                    AssetId::default(),
+                    0,
+                    0,
+                    vec![],
+                    vec![],
+                )
+            })
+            .collect();
+
+        let to = wallet_2.address();
+        let params = TxParameters::default();
+
+        // 11 isn't covered by the chosen coins
+        let result = wallet_1
+            .transfer_with_inputs(inputs.clone(), &to, 11, AssetId::default(), &params)
+            .await;
+        assert!(matches!(result, Err(WalletError::NotEnoughCoins(11, _))));
+
+        // Coins of another wallet can't be spent
+        let foreign = wallet_2.get_asset_inputs_for_amount(AssetId::default(), 1, 0);
+        let foreign = foreign.await.unwrap();
+        let result = wallet_1
+            .transfer_with_inputs(foreign, &to, 1, AssetId::default(), &params)
+            .await;
+        assert!(matches!(result, Err(WalletError::ForeignInput(_))));
+
+        // The amount of an input must be the one of its coin
+        let mut inflated = inputs.clone();
+        if let Input::Coin { amount, .. } = &mut inflated[0] {
+            *amount = 10;
+        }
+        let result = wallet_1
+            .transfer_with_inputs(inflated, &to, 11, AssetId::default(), &params)
+            .await;
+        assert!(matches!(result, Err(WalletError::UnspendableInput(_))));
+
+        wallet_1
+            .transfer_with_inputs(inputs.clone(), &to, 7, AssetId::default(), &params)
+            .await
+            .unwrap();
+        assert!(wallet_1.reserved_coins.lock().unwrap().is_empty());
+
+        // The coins are spent now
+        let result = wallet_1
+            .transfer_with_inputs(inputs, &to, 7, AssetId::default(), &params)
+            .await;
+        assert!(matches!(result, Err(WalletError::UnspendableInput(_))));
+
+        let coins = wallet_1.get_coins().await.unwrap();
+        for coin in coins {
+            let utxo_id = UtxoId::from(coin.utxo_id);
+            if chosen.contains(&utxo_id) {
+                assert!(matches!(coin.status, CoinStatus::Spent));
+            } else if utxo_id == untouched {
+                assert!(matches!(coin.status, CoinStatus::Unspent));
+            }
+        }
+
+        let balances = wallet_2.get_balances().await.unwrap();
+        assert_eq!(balances.get(&AssetId::default()), Some(&12));
+    }
+
+    #[tokio::test]
+    async fn transfer_with_change_address() {
+        let (pk_1, mut coins_1) = setup_address_and_coins(1, 5);
+        let (pk_2, coins_2) = setup_address_and_coins(1, 5);
+        let (pk_3, coins_3) = setup_address_and_coins(1, 5);
+
+        coins_1.extend(coins_2);
+        coins_1.extend(coins_3);
+
+        let (provider, _) = setup_test_provider(coins_1).await;
+
+        let wallet_1 = LocalWallet::new_from_private_key(pk_1, provider.clone()).unwrap();
+        let wallet_2 = LocalWallet::new_from_private_key(pk_2, provider.clone()).unwrap();
+        let change_wallet = LocalWallet::new_from_private_key(pk_3, provider).unwrap();
+
+        // Transfer 2 from wallet 1 to wallet 2, sending the change to the third wallet.
+        wallet_1
+            .transfer_with_change_address(
+                &wallet_2.address(),
+                2,
+                Default::default(),
+                Some(change_wallet.address()),
+            )
+            .await
+            .unwrap();
+
+        // 5 initial + 3 of change
+        let change_coins = change_wallet.get_coins().await.unwrap();
+        let total_amount: u64 = change_coins.iter().map(|c| c.amount.0).sum();
+        assert_eq!(total_amount, 8);
+
+        let wallet_1_balances = wallet_1.get_balances().await.unwrap();
+        assert_eq!(wallet_1_balances.get(&AssetId::default()), None);
+    }
+
+    #[tokio::test]
+    async fn transfer_custom_asset_with_base_asset_fee() {
+        let asset_id = AssetId::new([1u8; 32]);
+        let (pk_1, mut coins) = setup_address_and_coins(2, 1_000_000);
+        coins[0].1.asset_id = asset_id;
+        let (pk_2, coins_2) = setup_address_and_coins(1, 1);
+        coins.extend(coins_2);
+        let spent: Vec<UtxoId> = coins.iter().take(2).map(|(utxo_id, _)| *utxo_id).collect();
+
+        let (provider, _) = setup_test_provider(coins).await;
+        let wallet_1 = LocalWallet::new_from_private_key(pk_1, provider.clone()).unwrap();
+        let wallet_2 = LocalWallet::new_from_private_key(pk_2, provider).unwrap();
+
+        let params = TxParameters::new(Some(1), None, Some(1), None);
+        wallet_1
+            .transfer_with_params(&wallet_2.address(), 10, asset_id, &params)
+            .await
+            .unwrap();
+
+        // Both the custom asset coin and the base asset coin paying the fee are spent
+        let coins = wallet_1.get_coins().await.unwrap();
+        let spent_coins = coins
+            .into_iter()
+            .filter(|coin| spent.contains(&UtxoId::from(coin.utxo_id.clone())))
+            .filter(|coin| matches!(coin.status, CoinStatus::Spent))
+            .count();
+        assert_eq!(spent_coins, 2);
+
+        let balances = wallet_1.get_balances().await.unwrap();
+        assert_eq!(balances.get(&asset_id), Some(&(1_000_000 - 10)));
+        assert!(balances.get(&AssetId::default()).copied().unwrap_or(0) < 1_000_000);
+        let balances = wallet_2.get_balances().await.unwrap();
+        assert_eq!(balances.get(&asset_id), Some(&10));
+    }
+
+    #[tokio::test]
+    async fn wallet_with_several_assets() {
+        let asset_1 = AssetId::new([1u8; 32]);
+        let asset_2 = AssetId::new([2u8; 32]);
+        let (pk_1, mut coins) =
+            setup_address_and_coins_with_assets(2, 5, &[AssetId::default(), asset_1]);
+        let (pk_2, coins_2) = setup_address_and_coins_with_asset(1, 7, asset_2);
+        coins.extend(coins_2);
+
+        let (provider, _) = setup_test_provider(coins).await;
+        let wallet_1 = LocalWallet::new_from_private_key(pk_1, provider.clone()).unwrap();
+        let wallet_2 = LocalWallet::new_from_private_key(pk_2, provider).unwrap();
+
+        let balances = wallet_1.get_balances().await.unwrap();
+        assert_eq!(balances.len(), 2);
+        assert_eq!(balances.get(&AssetId::default()), Some(&10));
+        assert_eq!(balances.get(&asset_1), Some(&10));
+
+        let balances = wallet_2.get_balances().await.unwrap();
+        assert_eq!(balances.len(), 1);
+        assert_eq!(balances.get(&asset_2), Some(&7));
+    }
+
+    #[tokio::test]
+    async fn transfer_rejects_dust_change() {
+        let (pk_1, mut coins) = setup_address_and_coins(1, 10);
+        let (pk_2, coins_2) = setup_address_and_coins(1, 10);
+        coins.extend(coins_2);
+
+        let (provider, _) = setup_test_provider(coins).await;
+        let wallet_1 = LocalWallet::new_from_private_key(pk_1, provider.clone()).unwrap();
+        let wallet_2 = LocalWallet::new_from_private_key(pk_2, provider).unwrap();
+
+        // Sending 9 out of 10 would leave a coin of 1
+        let params = TxParameters::default().min_output_amount(10);
+        let result = wallet_1
+            .transfer_with_params(&wallet_2.address(), 9, AssetId::default(), &params)
+            .await;
+        assert!(matches!(
+            result,
+            Err(WalletError::DustChange(asset_id, 1, 10)) if asset_id == AssetId::default()
+        ));
+
+        // The coin was released, and spending all of it leaves no change
+        wallet_1
+            .transfer_with_params(&wallet_2.address(), 10, AssetId::default(), &params)
+            .await
+            .unwrap();
+        let balances = wallet_2.get_balances().await.unwrap();
+        assert_eq!(balances.get(&AssetId::default()), Some(&20));
+    }
+
+    #[tokio::test]
+    async fn dust_change_with_paid_gas() {
+        let base_asset = AssetId::default();
+        let asset_id = AssetId::from([1u8; 32]);
+        let (pk, coins) =
+            setup_address_and_coins_with_assets(1, 1_000_005, &[base_asset, asset_id]);
+
+        let (provider, _) = setup_test_provider(coins).await;
+        let wallet = LocalWallet::new_from_private_key(pk, provider).unwrap();
+        let to = Address::new([2u8; 32]);
+        let params = TxParameters::new(Some(1), None, None, None).min_output_amount(10);
+
+        // Dust of another asset than the base one is still rejected
+        let result = wallet
+            .transfer_with_params(&to, 999_999, asset_id, &params)
+            .await;
+        assert!(matches!(result, Err(WalletError::DustChange(id, 6, 10)) if id == asset_id));
+
+        // Paying the whole gas limit would only leave 4 of the base asset, but the fee
+        // is charged on the gas actually used, so the transfer isn't rejected
+        wallet
+            .transfer_with_params(&to, 1, base_asset, &params)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn transfer_dry_run() {
+        let (pk_1, mut coins) = setup_address_and_coins(1, 10);
+        let (pk_2, coins_2) = setup_address_and_coins(1, 10);
+        coins.extend(coins_2);
+
+        let (provider, _) = setup_test_provider(coins).await;
+        let wallet_1 = LocalWallet::new_from_private_key(pk_1, provider.clone()).unwrap();
+        let wallet_2 = LocalWallet::new_from_private_key(pk_2, provider).unwrap();
+
+        let (tx, receipts) = wallet_1
+            .transfer_dry_run(
+                &wallet_2.address(),
+                3,
+                AssetId::default(),
+                &TxParameters::default(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            tx.outputs(),
+            &[
+                Output::coin(wallet_2.address(), 3, AssetId::default()),
+                Output::change(wallet_1.address(), 0, AssetId::default()),
+            ]
+        );
+        assert!(receipts
+            .iter()
+            .any(|r| matches!(r, Receipt::ScriptResult { .. })));
+
+        // Nothing was broadcast
+        for wallet in [&wallet_1, &wallet_2] {
+            let balances = wallet.get_balances().await.unwrap();
+            assert_eq!(balances.get(&AssetId::default()), Some(&10));
+        }
+
+        // The coin wasn't left reserved by the dry-run
+        wallet_1
+            .transfer(&wallet_2.address(), 3, AssetId::default())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn coin_spent() {
+        let (pk_1, mut coins) = setup_address_and_coins(1, 10);
+        let (pk_2, coins_2) = setup_address_and_coins(1, 10);
+        let utxo_id = coins[0].0;
+        coins.extend(coins_2);
+
+        let (provider, _) = setup_test_provider(coins).await;
+        let wallet_1 = LocalWallet::new_from_private_key(pk_1, provider.clone()).unwrap();
+        let wallet_2 = LocalWallet::new_from_private_key(pk_2, provider.clone()).unwrap();
+
+        assert!(!provider.is_coin_spent(&utxo_id).await.unwrap());
+
+        wallet_1
+            .transfer(&wallet_2.address(), 3, AssetId::default())
+            .await
+            .unwrap();
+
+        assert!(provider.is_coin_spent(&utxo_id).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn coins_at_block() {
+        let (pk_1, mut coins) = setup_address_and_coins(1, 10);
+        let (pk_2, coins_2) = setup_address_and_coins(1, 10);
+        coins.extend(coins_2);
+
+        let (provider, _) = setup_test_provider(coins).await;
+        let wallet_1 = LocalWallet::new_from_private_key(pk_1, provider.clone()).unwrap();
+        let wallet_2 = LocalWallet::new_from_private_key(pk_2, provider.clone()).unwrap();
+
+        let before = provider.chain_info().await.unwrap().latest_block.height.0;
+        let coins_before = wallet_1.get_coins_at_block(before).await.unwrap();
+        assert_eq!(coins_before.len(), 1);
+
+        wallet_1
+            .transfer(&wallet_2.address(), 3, Default::default())
+            .await
+            .unwrap();
+
+        let after = provider.chain_info().await.unwrap().latest_block.height.0;
+        assert!(after > before);
+        let utxo_ids = |coins: &[Coin]| -> Vec<UtxoId> {
+            coins
+                .iter()
+                .map(|coin| coin.utxo_id.clone().into())
+                .collect()
+        };
+        let coins_after = wallet_1.get_coins_at_block(after).await.unwrap();
+        assert_ne!(utxo_ids(&coins_before), utxo_ids(&coins_after));
+
+        // The coin spent by the transfer is still found before it, as it was then
+        let past = wallet_1.get_coins_at_block(before).await.unwrap();
+        assert_eq!(utxo_ids(&past), utxo_ids(&coins_before));
+        assert!(past
+            .iter()
+            .all(|coin| matches!(coin.status, CoinStatus::Unspent)));
+
+        let result = wallet_1.get_coins_at_block(after + 1).await;
+        assert!(matches!(
+            result,
+            Err(WalletError::ProviderError(
+                ProviderError::TransactionRequestError(_)
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn split_coin() {
+        let (pk, coins) = setup_address_and_coins(1, 100);
+        let (provider, _) = setup_test_provider(coins).await;
+        let wallet = LocalWallet::new_from_private_key(pk, provider).unwrap();
+
+        wallet.split_coin(100, 4, Default::default()).await.unwrap();
+
+        let coins = wallet.get_coins().await.unwrap();
+        let split: Vec<_> = coins
+            .iter()
+            .filter(|c| matches!(c.status, CoinStatus::Unspent) && c.amount.0 == 25)
+            .collect();
+        assert_eq!(split.len(), 4);
+
+        let balances = wallet.get_balances().await.unwrap();
+        assert_eq!(balances.get(&AssetId::default()), Some(&100));
+
+        // The node doesn't expose its limits, so those of `fuel-tx` apply, minus the change
+        let max_split = ConsensusParameters::default().max_outputs as usize - 1;
+        let result = wallet.split_coin(100, 1000, Default::default()).await;
+        assert!(matches!(
+            result,
+            Err(WalletError::InvalidSplit(1000, max)) if max == max_split
+        ));
+    }
+
+    #[tokio::test]
+    async fn consensus_parameters_not_exposed_by_the_node() {
+        let (provider, node) = setup_test_provider(vec![]).await;
+        assert!(matches!(
+            provider.consensus_parameters().await,
+            Err(ProviderError::Unsupported(_))
+        ));
+
+        // Without a query of its own, a `FuelClient` can't ask for them
+        let provider = Provider::new(FuelClient::from(node));
+        assert!(matches!(
+            provider.consensus_parameters().await,
+            Err(ProviderError::Unsupported(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn transfer_with_too_high_gas_limit() {
+        let (pk_1, mut coins_1) = setup_address_and_coins(1, 1);
+        let (pk_2, coins_2) = setup_address_and_coins(1, 1);
+
+        coins_1.extend(coins_2);
+
+        let (provider, _) = setup_test_provider(coins_1).await;
+
+        let wallet_1 = LocalWallet::new_from_private_key(pk_1, provider.clone()).unwrap();
+        let wallet_2 = LocalWallet::new_from_private_key(pk_2, provider.clone()).unwrap();
+
+        let max_gas_per_tx = ConsensusParameters::default().max_gas_per_tx;
+
+        let result = wallet_1
+            .transfer_with_gas_limit(&wallet_2.address(), 1, Default::default(), u64::MAX)
+            .await;
+
+        let err = result.unwrap_err();
+        assert!(matches!(
+            err,
+            WalletError::GasLimitTooHigh(u64::MAX, max) if max == max_gas_per_tx
+        ));
+        assert!(matches!(
+            Error::from(err),
+            Error::GasLimitTooHigh(u64::MAX, _)
+        ));
+
+        // Nothing was spent
+        assert_eq!(wallet_1.get_coins().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn transfer_gas_limit_is_estimated_with_margin() {
+        let (pk_1, mut coins_1) = setup_address_and_coins(1, 1);
+        let (pk_2, coins_2) = setup_address_and_coins(1, 1);
+
+        coins_1.extend(coins_2);
+
+        let (provider, _) = setup_test_provider(coins_1).await;
+
+        let wallet_1 = LocalWallet::new_from_private_key(pk_1, provider.clone()).unwrap();
+        let wallet_2 = LocalWallet::new_from_private_key(pk_2, provider.clone()).unwrap();
+
+        let max_gas_per_tx = ConsensusParameters::default().max_gas_per_tx;
+
+        let inputs = wallet_1
+            .get_asset_inputs_for_amount(AssetId::default(), 1, 0)
+            .await
+            .unwrap();
+        let outputs = vec![
+            Output::coin(wallet_2.address(), 1, AssetId::default()),
+            Output::change(wallet_1.address(), 0, AssetId::default()),
+        ];
+
+        let mut tx = provider.build_transfer_tx_with_gas_limit(&inputs, &outputs, max_gas_per_tx);
+        wallet_1.sign_transaction(&mut tx).await.unwrap();
+        let gas_used = provider.estimate_gas_used(&tx).await.unwrap();
+
+        let params = TxParameters::default().gas_margin(1.5).unwrap();
+        let gas_limit = wallet_1
+            .estimate_transfer_gas_limit(&inputs, &outputs, &params)
+            .await
+            .unwrap();
+
+        assert_eq!(gas_limit, (gas_used * 3 + 1) / 2);
+
+        let result = wallet_1
+            .transfer_with_params(&wallet_2.address(), 1, Default::default(), &params)
+            .await
+            .unwrap();
+        assert!(!result.receipts.is_empty());
+        assert_eq!(wallet_2.get_coins().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn transfer_to_many_in_chunks() {
+        let (pk, mut coins) = setup_address_and_coins(3, 10);
+        let recipients: Vec<_> = (0..5)
+            .map(|_| {
+                let (pk, recipient_coins) = setup_address_and_coins(1, 1);
+                coins.extend(recipient_coins);
+                pk
+            })
+            .collect();
+
+        let (provider, _) = setup_test_provider(coins).await;
+        let wallet = LocalWallet::new_from_private_key(pk, provider.clone()).unwrap();
+        let recipients: Vec<_> = recipients
+            .into_iter()
+            .map(|pk| LocalWallet::new_from_private_key(pk, provider.clone()).unwrap())
+            .collect();
+
+        let payments: Vec<_> = recipients.iter().map(|w| (w.address(), 2)).collect();
+
+        // 2 recipients and the change per transaction
+        let results = wallet
+            .transfer_to_many_with_limits(&payments, Default::default(), 8, 3)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 3);
+
+        for recipient in &recipients {
+            let balances = recipient.get_balances().await.unwrap();
+            assert_eq!(balances.get(&AssetId::default()), Some(&3));
+        }
+
+        // Nothing is sent if the total can't be covered
+        let too_much: Vec<_> = recipients.iter().map(|w| (w.address(), 10)).collect();
+        let result = wallet.transfer_to_many(&too_much, Default::default()).await;
+        assert!(matches!(result, Err(WalletError::NotEnoughCoins(50, _))));
+
+        let balances = wallet.get_balances().await.unwrap();
+        assert_eq!(balances.get(&AssetId::default()), Some(&20));
+
+        // Amounts adding up past `u64::MAX` are rejected rather than overflowing
+        let overflowing = vec![
+            (recipients[0].address(), u64::MAX),
+            (recipients[1].address(), 1),
+        ];
+        let result = wallet
+            .transfer_to_many(&overflowing, Default::default())
+            .await;
+        assert!(matches!(result, Err(WalletError::AmountOverflow)));
+
+        // Coins reserved by another transfer don't count towards the balance
+        let reserved = wallet
+            .reserve_asset_inputs_for_amount(AssetId::default(), 10, 0)
+            .await
+            .unwrap();
+        let payments: Vec<_> = recipients[..2].iter().map(|w| (w.address(), 6)).collect();
+        let result = wallet.transfer_to_many(&payments, Default::default()).await;
+        assert!(matches!(result, Err(WalletError::NotEnoughCoins(12, _))));
+        wallet.release_inputs(&reserved);
+    }
+
+    #[tokio::test]
+    async fn cached_coins() {
+        let (pk_1, mut coins_1) = setup_address_and_coins(1, 1);
+        let (pk_2, coins_2) = setup_address_and_coins(1, 1);
+
+        coins_1.extend(coins_2);
+
+        let (provider, node) = setup_test_provider(coins_1).await;
+
+        // Wallet 1 goes through a proxy counting its requests to the node
+        let (proxy, counts) = counting_proxy(node, Duration::ZERO).await;
+        let wallet_1 =
+            LocalWallet::new_from_private_key(pk_1, Provider::new(FuelClient::from(proxy)))
+                .unwrap()
+                .with_coin_cache(Duration::from_secs(60));
+        let wallet_2 = LocalWallet::new_from_private_key(pk_2, provider).unwrap();
+
+        assert_eq!(wallet_1.get_coins().await.unwrap().len(), 1);
+        let requests = counts.total.load(Ordering::SeqCst);
+        assert!(requests > 0);
+
+        // Wallet 1 receives a coin, but doesn't see it until its cache expires since
+        // the second query is served without asking the node.
+        wallet_2
+            .transfer(&wallet_1.address(), 1, Default::default())
+            .await
+            .unwrap();
+        assert_eq!(wallet_1.get_coins().await.unwrap().len(), 1);
+        assert_eq!(counts.total.load(Ordering::SeqCst), requests);
+
+        let uncached = wallet_1
+            .provider
+            .get_coins(&wallet_1.address())
+            .await
+            .unwrap();
+        assert_eq!(uncached.len(), 2);
+
+        // Transferring from wallet 1 drops the cache
+        wallet_1
+            .transfer(&wallet_2.address(), 1, Default::default())
+            .await
+            .unwrap();
+        let refreshed = wallet_1.get_coins().await.unwrap();
+        let uncached = wallet_1
+            .provider
+            .get_coins(&wallet_1.address())
+            .await
+            .unwrap();
+        assert_eq!(refreshed.len(), uncached.len());
+    }
+
+    #[tokio::test]
+    async fn transfer_coins_with_change() {
+        // Setup two sets of coins, one for each wallet, each containing 1 coin with 5 amounts each.
+        let (pk_1, mut coins_1) = setup_address_and_coins(1, 5);
+        let (pk_2, coins_2) = setup_address_and_coins(1, 5);
+
+        coins_1.extend(coins_2);
 
         let (provider, _) = setup_test_provider(coins_1).await;
 
@@ -188,10 +1752,11 @@ mod tests {
         assert_eq!(wallet_2_initial_coins.len(), 1);
 
         // Transfer 2 from wallet 1 to wallet 2.
-        let _receipts = wallet_1
+        let result = wallet_1
             .transfer(&wallet_2.address(), 2, Default::default())
             .await
             .unwrap();
+        assert_ne!(result.tx_id, Bytes32::zeroed());
 
         let wallet_1_final_coins = wallet_1.get_coins().await.unwrap();
 
@@ -1,14 +1,35 @@
 use fuel_core::service::{Config, FuelService};
-use fuel_gql_client::client::schema::coin::Coin;
-use fuel_gql_client::client::{FuelClient, PageDirection, PaginationRequest};
+use fuel_gql_client::client::schema::{
+    block::Block,
+    chain::ChainInfo,
+    coin::{Coin, CoinStatus},
+};
+use fuel_gql_client::client::{
+    types::TransactionStatus, FuelClient, PageDirection, PaginationRequest,
+};
+use fuel_tx::consts::{MAX_GAS_PER_TX, MAX_INPUTS, MAX_OUTPUTS, MAX_WITNESSES};
 use fuel_tx::Receipt;
-use fuel_tx::{Address, AssetId, Input, Output, Transaction};
+use fuel_tx::{Address, AssetId, Bytes32, ContractId, Input, Output, Transaction, UtxoId, Witness};
 use fuel_vm::consts::REG_ONE;
+use futures::{stream, Stream};
+use std::collections::HashMap;
+use std::future::Future;
 use std::io;
+use std::mem::discriminant;
 use std::net::SocketAddr;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::Semaphore;
+
+use crate::transport::Transport;
+use crate::Signer;
 
 use fuel_vm::prelude::Opcode;
+use fuels_core::constants::DEFAULT_GAS_LIMIT;
 use fuels_core::errors::Error;
+use fuels_core::parameters::TxParameters;
+use serde::Deserialize;
 use thiserror::Error;
 
 /// An error involving a signature.
@@ -16,28 +37,376 @@ use thiserror::Error;
 pub enum ProviderError {
     #[error("Request failed: {0}")]
     TransactionRequestError(String),
+    /// The node answered the request with an error, e.g. rejecting a transaction, whose
+    /// message holds the node's explanation.
     #[error(transparent)]
-    ClientRequestError(#[from] io::Error),
+    ClientRequestError(io::Error),
+    /// The node couldn't be reached, or didn't answer in time.
+    #[error("Connection failed: {0}")]
+    ConnectionError(String),
+    /// The node is reachable but runs another network than the expected one.
+    #[error("Expected a node on the network `{expected}`, found one on `{actual}`")]
+    UnexpectedNetwork { expected: String, actual: String },
+    /// The node doesn't provide what the request needs.
+    #[error("Not supported by the node: {0}")]
+    Unsupported(String),
+}
+
+/// Tells the errors answered by the node apart from the failures to reach it, which
+/// `FuelClient` both reports as `io::Error`s: the latter are, or are caused by, an
+/// `io::Error` of a connection kind.
+impl From<io::Error> for ProviderError {
+    fn from(e: io::Error) -> Self {
+        let mut source: Option<&(dyn std::error::Error + 'static)> = Some(&e);
+        while let Some(error) = source {
+            if let Some(io_error) = error.downcast_ref::<io::Error>() {
+                if is_connection_failure(io_error.kind()) {
+                    return ProviderError::ConnectionError(e.to_string());
+                }
+                // The error wrapped by an `io::Error` isn't its `source`
+                if let Some(inner) = io_error.get_ref() {
+                    source = Some(inner);
+                    continue;
+                }
+            }
+            source = error.source();
+        }
+
+        ProviderError::ClientRequestError(e)
+    }
+}
+
+fn is_connection_failure(kind: io::ErrorKind) -> bool {
+    matches!(
+        kind,
+        io::ErrorKind::ConnectionRefused
+            | io::ErrorKind::ConnectionReset
+            | io::ErrorKind::ConnectionAborted
+            | io::ErrorKind::NotConnected
+            | io::ErrorKind::AddrNotAvailable
+            | io::ErrorKind::BrokenPipe
+            | io::ErrorKind::TimedOut
+            | io::ErrorKind::UnexpectedEof
+    )
+}
+
+impl From<ProviderError> for Error {
+    fn from(e: ProviderError) -> Self {
+        match e {
+            ProviderError::ClientRequestError(e) => Error::Node(e.to_string()),
+            ProviderError::Unsupported(message) => Error::Unsupported(message),
+            _ => Error::InfrastructureError(e.to_string()),
+        }
+    }
+}
+
+/// How often subscriptions ask the node for a new status or block.
+const STATUS_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Default longest time a subscription waits before retrying a failed request.
+pub const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(5);
+
+/// Limits enforced by the node on the transactions it accepts. The default ones are those
+/// of `fuel-tx`, which nodes that don't expose their parameters validate against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConsensusParameters {
+    pub max_gas_per_tx: u64,
+    pub max_inputs: u64,
+    pub max_outputs: u64,
+    pub max_witnesses: u64,
+}
+
+/// The consensus parameters as answered by the node, which serializes `U64`s as strings.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NodeConsensusParameters {
+    max_gas_per_tx: String,
+    max_inputs: String,
+    max_outputs: String,
+    max_witnesses: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ChainConsensusParameters {
+    consensus_parameters: NodeConsensusParameters,
+}
+
+#[derive(Deserialize)]
+struct ConsensusParametersQuery {
+    chain: ChainConsensusParameters,
+}
+
+const CONSENSUS_PARAMETERS_QUERY: &str =
+    "{ chain { consensusParameters { maxGasPerTx maxInputs maxOutputs maxWitnesses } } }";
+
+impl TryFrom<NodeConsensusParameters> for ConsensusParameters {
+    type Error = ProviderError;
+
+    fn try_from(params: NodeConsensusParameters) -> Result<Self, Self::Error> {
+        let parse = |value: String| {
+            value.parse::<u64>().map_err(|_| {
+                ProviderError::TransactionRequestError(format!(
+                    "The node answered an invalid consensus parameter: {}",
+                    value
+                ))
+            })
+        };
+
+        Ok(Self {
+            max_gas_per_tx: parse(params.max_gas_per_tx)?,
+            max_inputs: parse(params.max_inputs)?,
+            max_outputs: parse(params.max_outputs)?,
+            max_witnesses: parse(params.max_witnesses)?,
+        })
+    }
 }
 
+impl Default for ConsensusParameters {
+    fn default() -> Self {
+        Self {
+            max_gas_per_tx: MAX_GAS_PER_TX,
+            max_inputs: MAX_INPUTS as u64,
+            max_outputs: MAX_OUTPUTS as u64,
+            max_witnesses: MAX_WITNESSES as u64,
+        }
+    }
+}
+
+/// What the node knows about an asset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AssetInfo {
+    pub asset_id: AssetId,
+    /// Amount of the asset in circulation, minted minus burned.
+    pub total_supply: u64,
+}
+
+/// A message sent to `recipient` from outside the chain, e.g. by a bridge, which its
+/// recipient can spend as an input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    pub message_id: Bytes32,
+    pub amount: u64,
+    pub sender: Address,
+    pub recipient: Address,
+    pub nonce: u64,
+    pub data: Vec<u8>,
+    /// Height of the block of the bridged chain the message was sent at.
+    pub da_height: u64,
+}
+
+/// A message as answered by the node, which serializes `U64`s and bytes as strings.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NodeMessage {
+    message_id: String,
+    amount: String,
+    sender: String,
+    recipient: String,
+    nonce: String,
+    data: String,
+    da_height: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MessagesPageInfo {
+    has_next_page: bool,
+    end_cursor: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct MessageEdge {
+    node: NodeMessage,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MessageConnection {
+    edges: Vec<MessageEdge>,
+    page_info: MessagesPageInfo,
+}
+
+#[derive(Deserialize)]
+struct MessagesQuery {
+    messages: MessageConnection,
+}
+
+impl TryFrom<NodeMessage> for Message {
+    type Error = ProviderError;
+
+    fn try_from(message: NodeMessage) -> Result<Self, Self::Error> {
+        let invalid = |value: &str| {
+            ProviderError::TransactionRequestError(format!(
+                "The node answered an invalid message field: {}",
+                value
+            ))
+        };
+        let number = |value: &str| value.parse::<u64>().map_err(|_| invalid(value));
+        let bytes =
+            |value: &str| hex::decode(value.trim_start_matches("0x")).map_err(|_| invalid(value));
+
+        Ok(Self {
+            message_id: Bytes32::from_str(&message.message_id)
+                .map_err(|_| invalid(&message.message_id))?,
+            amount: number(&message.amount)?,
+            sender: Address::from_str(&message.sender).map_err(|_| invalid(&message.sender))?,
+            recipient: Address::from_str(&message.recipient)
+                .map_err(|_| invalid(&message.recipient))?,
+            nonce: number(&message.nonce)?,
+            data: bytes(&message.data)?,
+            da_height: number(&message.da_height)?,
+        })
+    }
+}
+
+/// Default maximum number of requests a `Provider` sends to the node at the same time.
+pub const DEFAULT_MAX_CONCURRENCY: usize = 10;
+
 /// Encapsulates common client operations in the SDK.
 /// Note that you may also use `client`, which is an instance
-/// of `FuelClient`, directly, which providers a broader API.
+/// of `FuelClient`, directly, which providers a broader API. Its requests don't go
+/// through the `reqwest::Client` of a provider created with `from_client`.
 #[derive(Debug, Clone)]
 pub struct Provider {
     pub client: FuelClient,
+    /// Sends the provider's own requests.
+    transport: Transport,
+    /// Caps the requests in flight, shared between clones of the provider.
+    concurrency: Arc<Semaphore>,
+    /// Longest time subscriptions wait before retrying after losing the node.
+    max_backoff: Duration,
+    /// The node's consensus parameters once fetched, shared between clones.
+    consensus_parameters: Arc<Mutex<Option<ConsensusParameters>>>,
 }
 
 impl Provider {
     pub fn new(client: FuelClient) -> Self {
-        Self { client }
+        Self {
+            transport: Transport::Client(client.clone()),
+            client,
+            concurrency: Arc::new(Semaphore::new(DEFAULT_MAX_CONCURRENCY)),
+            max_backoff: DEFAULT_MAX_BACKOFF,
+            consensus_parameters: Default::default(),
+        }
+    }
+
+    /// Creates a provider sending its requests to the node at `url` with `client`, e.g. to
+    /// set timeouts, a proxy or default headers, which `FuelClient` doesn't allow. The
+    /// scheme of `url` defaults to `http`. This doesn't send any request.
+    pub fn from_client(client: reqwest::Client, url: &str) -> Result<Self, ProviderError> {
+        let transport = Transport::http(client, url)?;
+        let fuel_client =
+            FuelClient::new(url).map_err(|e| ProviderError::ConnectionError(e.to_string()))?;
+
+        Ok(Self {
+            transport,
+            ..Self::new(fuel_client)
+        })
+    }
+
+    /// Sets the longest time subscriptions wait between retries when requests to the
+    /// node fail, e.g. on a network blip. They give up once a request fails after
+    /// waiting `max_backoff`.
+    pub fn with_max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Limits the number of requests this provider, and its clones, send to the node at
+    /// the same time to `max_concurrency`. Requests above it wait for others to finish.
+    /// Requests made directly through `client` aren't limited. A `max_concurrency` of 0
+    /// is raised to 1, which would otherwise block every request.
+    pub fn with_max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.concurrency = Arc::new(Semaphore::new(max_concurrency.max(1)));
+        self
+    }
+
+    /// Runs `request` once fewer than the maximum number of requests are in flight.
+    pub async fn throttle<F: Future>(&self, request: F) -> F::Output {
+        // The semaphore is never closed
+        let _permit = self.concurrency.acquire().await.unwrap();
+        request.await
     }
 
     /// Shallow wrapper on client's submit.
-    pub async fn send_transaction(&self, tx: &Transaction) -> io::Result<Vec<Receipt>> {
-        let tx_id = self.client.submit(tx).await?;
+    pub async fn send_transaction(&self, tx: &Transaction) -> Result<Vec<Receipt>, ProviderError> {
+        let tx_id = self.throttle(self.transport.submit(tx)).await?;
+
+        self.throttle(self.transport.receipts(&tx_id.0.to_string()))
+            .await
+    }
+
+    /// Submits the transaction and returns the id the node reports for it, without waiting
+    /// for it to be executed. Use `get_receipts` or `subscribe_transaction_status` to
+    /// follow it up. The id can be known before submitting with `util::transaction_id`.
+    pub async fn submit_transaction(&self, tx: &Transaction) -> Result<Bytes32, ProviderError> {
+        let tx_id = self.throttle(self.transport.submit(tx)).await?;
+
+        Bytes32::from_str(&tx_id.0.to_string()).map_err(|_| {
+            ProviderError::TransactionRequestError(format!(
+                "The node reported an invalid transaction id: {}",
+                tx_id.0
+            ))
+        })
+    }
+
+    /// Returns the gas used by the script of `tx`, as reported by a dry-run.
+    /// `tx` must have a gas limit high enough for the dry-run to complete.
+    pub async fn estimate_gas_used(&self, tx: &Transaction) -> Result<u64, ProviderError> {
+        let receipts = self.throttle(self.transport.dry_run(tx)).await?;
 
-        Ok(self.client.receipts(&tx_id.0.to_string()).await?)
+        receipts
+            .iter()
+            .find_map(|receipt| match receipt {
+                Receipt::ScriptResult { gas_used, .. } => Some(*gas_used),
+                _ => None,
+            })
+            .ok_or_else(|| {
+                ProviderError::TransactionRequestError(
+                    "Dry-run didn't return a script result".into(),
+                )
+            })
+    }
+
+    /// Simulates `tx` on top of the node's latest block, without broadcasting it.
+    pub async fn dry_run(&self, tx: &Transaction) -> Result<Vec<Receipt>, ProviderError> {
+        self.throttle(self.transport.dry_run(tx)).await
+    }
+
+    /// Simulates `tx` as if it were included in the block at `height`, without
+    /// broadcasting it. The node only simulates transactions on top of its latest block,
+    /// as part of the block it produces next, so this fails with
+    /// `ProviderError::Unsupported` for any other height.
+    pub async fn dry_run_at_height(
+        &self,
+        tx: &Transaction,
+        height: u64,
+    ) -> Result<Vec<Receipt>, ProviderError> {
+        let next = self.chain_info().await?.latest_block.height.0 + 1;
+        if height != next {
+            return Err(ProviderError::Unsupported(format!(
+                "the node only simulates transactions at its next block height {}, not {}",
+                next, height
+            )));
+        }
+
+        self.dry_run(tx).await
+    }
+
+    /// Gets the receipts of the transaction `id`.
+    pub async fn get_receipts(&self, id: &Bytes32) -> Result<Vec<Receipt>, ProviderError> {
+        self.throttle(self.transport.receipts(&format!("{:#x}", id)))
+            .await
+    }
+
+    /// Gets the status of the transaction `id`, e.g. `Submitted` until it's executed.
+    pub async fn get_transaction_status(
+        &self,
+        id: &Bytes32,
+    ) -> Result<TransactionStatus, ProviderError> {
+        self.throttle(self.transport.transaction_status(&format!("{:#x}", id)))
+            .await
     }
 
     /// Launches a local `fuel-core` network based on provided config.
@@ -48,9 +417,201 @@ impl Provider {
 
     /// Connects to an existing node at the given address
     pub async fn connect(socket: SocketAddr) -> Result<Provider, Error> {
-        Ok(Self {
-            client: FuelClient::from(socket),
-        })
+        Ok(Self::from_client(
+            reqwest::Client::new(),
+            &socket.to_string(),
+        )?)
+    }
+
+    /// Connects to an existing node at the given URL and checks that it is reachable
+    /// and runs the network named `network`, e.g. `local_testnet`, by querying its chain
+    /// information. A bad URL or a node on another network fails here rather than on the
+    /// first query, the latter with `ProviderError::UnexpectedNetwork`.
+    pub async fn connect_url(url: &str, network: &str) -> Result<Provider, ProviderError> {
+        let provider = Self::from_client(reqwest::Client::new(), url)?;
+
+        let actual = provider.chain_info().await?.name;
+        if actual != network {
+            return Err(ProviderError::UnexpectedNetwork {
+                expected: network.to_string(),
+                actual,
+            });
+        }
+
+        Ok(provider)
+    }
+
+    /// Shallow wrapper on client's chain_info.
+    pub async fn chain_info(&self) -> Result<ChainInfo, ProviderError> {
+        self.throttle(self.transport.chain_info()).await
+    }
+
+    /// Returns a stream of the status transitions of the transaction `id`, e.g.
+    /// `Submitted` then `Success`, which ends after the first `Success` or `Failure`.
+    /// The node doesn't support subscriptions yet, so this polls its status and
+    /// only yields it when it changes. Fails if the node doesn't know the transaction.
+    /// Failed polls are retried with an exponential backoff, see `with_max_backoff`,
+    /// so the stream resumes where it left off after a transient disconnection. If the
+    /// node can't be reached after that, the stream yields the error and ends.
+    pub async fn subscribe_transaction_status(
+        &self,
+        id: Bytes32,
+    ) -> Result<impl Stream<Item = Result<TransactionStatus, ProviderError>>, ProviderError> {
+        let first = self.get_transaction_status(&id).await?;
+
+        let provider = self.clone();
+        let fetch = move || {
+            let provider = provider.clone();
+            async move { provider.get_transaction_status(&id).await }
+        };
+
+        Ok(poll_status(
+            first,
+            fetch,
+            discriminant,
+            |status| !matches!(status, TransactionStatus::Submitted { .. }),
+            self.max_backoff,
+        ))
+    }
+
+    /// Returns a never ending stream of the latest block of the node, starting with the
+    /// current one. The node doesn't support subscriptions yet, so this polls its chain
+    /// information and yields its latest block when the height changes: blocks produced
+    /// between two polls are skipped. Failed polls are retried like in
+    /// `subscribe_transaction_status`, and the stream ends with an error if they keep
+    /// failing.
+    pub async fn subscribe_blocks(
+        &self,
+    ) -> Result<impl Stream<Item = Result<Block, ProviderError>>, ProviderError> {
+        let first = self.chain_info().await?.latest_block;
+
+        let provider = self.clone();
+        let fetch = move || {
+            let provider = provider.clone();
+            async move { provider.chain_info().await.map(|info| info.latest_block) }
+        };
+
+        Ok(poll_status(
+            first,
+            fetch,
+            |block: &Block| block.height.0,
+            |_| false,
+            self.max_backoff,
+        ))
+    }
+
+    /// Waits until the transaction `id` is committed, i.e. until its status is `Success`
+    /// or `Failure`, and returns that status. Fails if it isn't committed within
+    /// `timeout`. The node is polled every 100ms, see `await_transaction_commit_with`.
+    pub async fn await_transaction_commit(
+        &self,
+        id: &Bytes32,
+        timeout: Duration,
+    ) -> Result<TransactionStatus, ProviderError> {
+        self.await_transaction_commit_with(id, timeout, STATUS_POLL_INTERVAL)
+            .await
+    }
+
+    /// Same as `await_transaction_commit`, polling the node every `poll_interval`.
+    /// Short intervals suit local nodes, longer ones spare rate-limited public nodes.
+    pub async fn await_transaction_commit_with(
+        &self,
+        id: &Bytes32,
+        timeout: Duration,
+        poll_interval: Duration,
+    ) -> Result<TransactionStatus, ProviderError> {
+        let fetch = move || self.get_transaction_status(id);
+
+        await_final_status(
+            fetch,
+            |status| !matches!(status, TransactionStatus::Submitted { .. }),
+            timeout,
+            poll_interval,
+        )
+        .await
+    }
+
+    /// Returns the consensus parameters of the node, which are fetched once and cached
+    /// for this provider and its clones. Fails with `ProviderError::Unsupported` if the
+    /// node doesn't expose them, as is the case of `fuel-core` 0.5, which validates
+    /// transactions against `ConsensusParameters::default()`, or if the provider was
+    /// created with `new`, whose `FuelClient` has no query for them.
+    pub async fn consensus_parameters(&self) -> Result<ConsensusParameters, ProviderError> {
+        if let Some(params) = *self.consensus_parameters.lock().unwrap() {
+            return Ok(params);
+        }
+
+        let exposed = self
+            .throttle(self.transport.has_field("ChainInfo", "consensusParameters"))
+            .await?;
+        if !exposed {
+            return Err(ProviderError::Unsupported(
+                "the node doesn't expose its consensus parameters".into(),
+            ));
+        }
+
+        let query: ConsensusParametersQuery = self
+            .throttle(self.transport.raw_query(CONSENSUS_PARAMETERS_QUERY))
+            .await?;
+        let params = ConsensusParameters::try_from(query.chain.consensus_parameters)?;
+        *self.consensus_parameters.lock().unwrap() = Some(params);
+
+        Ok(params)
+    }
+
+    /// Returns what the node knows about `asset_id`, such as its total supply.
+    /// The node doesn't expose assets through its API yet, so this fails with
+    /// `ProviderError::Unsupported` for now, which callers can match on to fall back to
+    /// something else.
+    pub async fn asset_info(&self, asset_id: &AssetId) -> Result<AssetInfo, ProviderError> {
+        Err(ProviderError::Unsupported(format!(
+            "the node has no query for the supply of asset {:#x}",
+            asset_id
+        )))
+    }
+
+    /// Returns the messages sent to `owner` which it hasn't spent yet, see `Message`.
+    /// Fails with `ProviderError::Unsupported` if the node has no query for them, as is
+    /// the case of `fuel-core` 0.5, which doesn't relay messages, or if the provider was
+    /// created with `new`, whose `FuelClient` has no query for them.
+    pub async fn get_messages(&self, owner: &Address) -> Result<Vec<Message>, ProviderError> {
+        let exposed = self
+            .throttle(self.transport.has_field("Query", "messages"))
+            .await?;
+        if !exposed {
+            return Err(ProviderError::Unsupported(
+                "the node has no query for the messages of an address".into(),
+            ));
+        }
+
+        let mut messages = vec![];
+        let mut cursor: Option<String> = None;
+        loop {
+            let after = cursor
+                .as_ref()
+                .map(|cursor| format!(", after: \"{}\"", cursor))
+                .unwrap_or_default();
+            let query = format!(
+                "{{ messages(owner: \"{}\", first: 100{}) {{ \
+                 edges {{ node {{ messageId amount sender recipient nonce data daHeight }} }} \
+                 pageInfo {{ hasNextPage endCursor }} }} }}",
+                owner, after
+            );
+            let res: MessagesQuery = self.throttle(self.transport.raw_query(&query)).await?;
+
+            for edge in res.messages.edges {
+                messages.push(Message::try_from(edge.node)?);
+            }
+            match (
+                res.messages.page_info.has_next_page,
+                res.messages.page_info.end_cursor,
+            ) {
+                (true, Some(end_cursor)) => cursor = Some(end_cursor),
+                _ => break,
+            }
+        }
+
+        Ok(messages)
     }
 
     /// Shallow wrapper on client's coins API.
@@ -61,8 +622,7 @@ impl Provider {
 
         loop {
             let res = self
-                .client
-                .coins(
+                .throttle(self.transport.coins(
                     &from.to_string(),
                     None,
                     PaginationRequest {
@@ -70,7 +630,7 @@ impl Provider {
                         results: 100,
                         direction: PageDirection::Forward,
                     },
-                )
+                ))
                 .await?;
 
             if res.results.is_empty() {
@@ -83,32 +643,230 @@ impl Provider {
         Ok(coins)
     }
 
+    /// Returns the coins `from` owned as of the block at `height`: those created at or
+    /// before it which weren't spent by then, with an `Unspent` status. The node doesn't
+    /// index past states, so they're rebuilt from its coins, spent ones included, and the
+    /// blocks of the transactions of `from` that spent them. Fails if `height` is above the
+    /// latest block, and with `ProviderError::Unsupported` if the transaction spending one
+    /// of the coins can't be found.
+    pub async fn get_coins_at_block(
+        &self,
+        from: &Address,
+        height: u64,
+    ) -> Result<Vec<Coin>, ProviderError> {
+        let latest = self.chain_info().await?.latest_block.height.0;
+        if height > latest {
+            return Err(ProviderError::TransactionRequestError(format!(
+                "Block {} isn't produced yet, the latest one is {}",
+                height, latest
+            )));
+        }
+
+        let coins: Vec<Coin> = self
+            .get_coins(from)
+            .await?
+            .into_iter()
+            .filter(|coin| coin.block_created.0 <= height)
+            .collect();
+        let spent_at = match coins
+            .iter()
+            .any(|coin| matches!(coin.status, CoinStatus::Spent))
+        {
+            true => self.spending_heights(from).await?,
+            false => HashMap::new(),
+        };
+
+        let mut coins_at_block = vec![];
+        for mut coin in coins {
+            if matches!(coin.status, CoinStatus::Spent) {
+                let utxo_id = UtxoId::from(coin.utxo_id.clone());
+                match spent_at.get(&utxo_id) {
+                    Some(&spent) if spent > height => coin.status = CoinStatus::Unspent,
+                    Some(_) => continue,
+                    None => {
+                        return Err(ProviderError::Unsupported(format!(
+                            "the transaction spending coin {:#x} wasn't found",
+                            utxo_id
+                        )))
+                    }
+                }
+            }
+            coins_at_block.push(coin);
+        }
+
+        Ok(coins_at_block)
+    }
+
+    /// Returns the height of the block each coin spent by the transactions of `owner` was
+    /// spent at, failed transactions included as they spend their inputs too.
+    async fn spending_heights(
+        &self,
+        owner: &Address,
+    ) -> Result<HashMap<UtxoId, u64>, ProviderError> {
+        let mut block_heights: HashMap<String, u64> = HashMap::new();
+        let mut spent = HashMap::new();
+
+        let mut cursor = None;
+        loop {
+            let res = self
+                .throttle(self.transport.transactions_by_owner(
+                    &owner.to_string(),
+                    PaginationRequest {
+                        cursor: cursor.clone(),
+                        results: 100,
+                        direction: PageDirection::Forward,
+                    },
+                ))
+                .await?;
+
+            for response in res.results.iter() {
+                let block_id = match &response.status {
+                    TransactionStatus::Success { block_id, .. }
+                    | TransactionStatus::Failure { block_id, .. } => block_id,
+                    TransactionStatus::Submitted { .. } => continue,
+                };
+                let height = match block_heights.get(block_id) {
+                    Some(&height) => height,
+                    None => {
+                        let block = self
+                            .throttle(self.transport.block(block_id))
+                            .await?
+                            .ok_or_else(|| {
+                                ProviderError::TransactionRequestError(format!(
+                                    "Block {} doesn't exist",
+                                    block_id
+                                ))
+                            })?;
+                        block_heights.insert(block_id.clone(), block.height.0);
+                        block.height.0
+                    }
+                };
+
+                for input in response.transaction.inputs() {
+                    if let Input::Coin { utxo_id, .. } = input {
+                        spent.insert(*utxo_id, height);
+                    }
+                }
+            }
+
+            if res.results.is_empty() || res.cursor.is_none() {
+                break;
+            }
+            cursor = res.cursor;
+        }
+
+        Ok(spent)
+    }
+
+    /// Returns the balance of every asset owned by `from`, computed from its unspent coins.
+    pub async fn get_balances(
+        &self,
+        from: &Address,
+    ) -> Result<HashMap<AssetId, u64>, ProviderError> {
+        let mut balances: HashMap<AssetId, u64> = HashMap::new();
+
+        for coin in self.get_coins(from).await? {
+            if matches!(coin.status, CoinStatus::Unspent) {
+                *balances.entry(coin.asset_id.into()).or_insert(0) += coin.amount.0;
+            }
+        }
+
+        Ok(balances)
+    }
+
+    /// Whether the coin `utxo_id` was spent, e.g. to tell if a cached coin was consumed
+    /// elsewhere. Fails if the node doesn't know the coin.
+    pub async fn is_coin_spent(&self, utxo_id: &UtxoId) -> Result<bool, ProviderError> {
+        let id = format!("{:#x}", utxo_id);
+        match self.throttle(self.transport.coin(&id)).await? {
+            Some(coin) => Ok(matches!(coin.status, CoinStatus::Spent)),
+            None => Err(ProviderError::TransactionRequestError(format!(
+                "Coin {} doesn't exist",
+                id
+            ))),
+        }
+    }
+
+    /// Returns the amount of `asset_id` held by the contract `contract_id`. Unlike the
+    /// balance of an address, this isn't made of coins, it's kept by the node.
+    pub async fn get_contract_balance(
+        &self,
+        contract_id: &ContractId,
+        asset_id: &AssetId,
+    ) -> Result<u64, ProviderError> {
+        self.throttle(self.transport.contract_balance(
+            &format!("{:#x}", contract_id),
+            Some(&format!("{:#x}", asset_id)),
+        ))
+        .await
+    }
+
     pub async fn get_spendable_coins(
         &self,
         from: &Address,
         asset_id: AssetId,
         amount: u64,
-    ) -> io::Result<Vec<Coin>> {
+    ) -> Result<Vec<Coin>, ProviderError> {
+        let asset_id = format!("{:#x}", asset_id);
         let res = self
-            .client
-            .coins_to_spend(
+            .throttle(self.transport.coins_to_spend(
                 &from.to_string(),
-                vec![(format!("{:#x}", asset_id).as_str(), amount)],
+                vec![(asset_id.as_str(), amount)],
                 None,
-            )
+            ))
             .await?;
 
         Ok(res)
     }
 
+    /// Builds a script transaction running `script` with `script_data`, signs it with
+    /// `signer` and submits it. Contract calls are built on the same kind of transaction,
+    /// this allows running arbitrary bytecode instead.
+    pub async fn run_script<S: Signer>(
+        &self,
+        signer: &S,
+        script: Vec<u8>,
+        script_data: Vec<u8>,
+        inputs: Vec<Input>,
+        outputs: Vec<Output>,
+        params: &TxParameters,
+    ) -> Result<Vec<Receipt>, ProviderError> {
+        let mut tx = Transaction::script(
+            params.gas_price,
+            params.gas_limit,
+            params.byte_price,
+            params.maturity.into(),
+            script,
+            script_data,
+            inputs,
+            outputs,
+            vec![],
+        );
+        signer.sign_transaction(&mut tx).await.map_err(|e| {
+            ProviderError::TransactionRequestError(format!("Failed to sign the script: {}", e))
+        })?;
+
+        self.send_transaction(&tx).await
+    }
+
     /// Craft a transaction used to transfer funds between two addresses.
     pub fn build_transfer_tx(&self, inputs: &[Input], outputs: &[Output]) -> Transaction {
+        self.build_transfer_tx_with_gas_limit(inputs, outputs, DEFAULT_GAS_LIMIT)
+    }
+
+    /// Same as `build_transfer_tx`, with a custom gas limit.
+    pub fn build_transfer_tx_with_gas_limit(
+        &self,
+        inputs: &[Input],
+        outputs: &[Output],
+        gas_limit: u64,
+    ) -> Transaction {
         // This script contains a single Opcode that returns immediately (RET)
         // since all this transaction does is move Inputs and Outputs around.
         let script = Opcode::RET(REG_ONE).to_bytes().to_vec();
         Transaction::Script {
             gas_price: 0,
-            gas_limit: 1_000_000,
+            gas_limit,
             byte_price: 0,
             maturity: 0,
             receipts_root: Default::default(),
@@ -121,7 +879,308 @@ impl Provider {
         }
     }
 
+    /// Same as `build_transfer_tx_with_gas_limit`, for a transaction to be signed later by
+    /// `num_signers` parties, e.g. with `Wallet::add_signature_to_tx`. It has `num_signers`
+    /// empty witnesses, and the coin inputs of each owner reference one of them, in order
+    /// of appearance: the first owner's inputs reference witness 0, the next owner's 1,
+    /// and so on. Fails if the inputs have more owners than `num_signers`.
+    pub fn build_transfer_tx_unsigned(
+        &self,
+        inputs: &[Input],
+        outputs: &[Output],
+        gas_limit: u64,
+        num_signers: usize,
+    ) -> Result<Transaction, ProviderError> {
+        let mut owners: Vec<Address> = vec![];
+        let mut inputs = inputs.to_vec();
+        for input in &mut inputs {
+            if let Input::Coin {
+                owner,
+                witness_index,
+                ..
+            } = input
+            {
+                let index = match owners.iter().position(|o| o == owner) {
+                    Some(index) => index,
+                    None => {
+                        owners.push(*owner);
+                        owners.len() - 1
+                    }
+                };
+                if index >= num_signers {
+                    return Err(ProviderError::TransactionRequestError(format!(
+                        "The inputs are owned by more than {} signers",
+                        num_signers
+                    )));
+                }
+                *witness_index = index as u8;
+            }
+        }
+
+        let mut tx = self.build_transfer_tx_with_gas_limit(&inputs, outputs, gas_limit);
+        tx.set_witnesses(vec![Witness::default(); num_signers]);
+
+        Ok(tx)
+    }
+
     // @todo
     // - Get transaction(s)
     // - Get block(s)
 }
+
+/// Yields `first`, then the results of `fetch` whenever their `key` changes, until one
+/// of them `is_final`. Failed fetches are retried after waiting twice as long as the
+/// previous attempt, up to `max_backoff`. If they keep failing, the last error is yielded
+/// and ends the stream.
+fn poll_status<T, K, F, Fut>(
+    first: T,
+    fetch: F,
+    key: impl Fn(&T) -> K,
+    is_final: impl Fn(&T) -> bool,
+    max_backoff: Duration,
+) -> impl Stream<Item = Result<T, ProviderError>>
+where
+    K: PartialEq,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, ProviderError>>,
+{
+    struct StatusPoll<T, K, F, C> {
+        fetch: F,
+        is_final: C,
+        next: Option<T>,
+        last: Option<K>,
+        done: bool,
+    }
+
+    let poll = StatusPoll {
+        fetch,
+        is_final,
+        next: Some(first),
+        last: None,
+        done: false,
+    };
+
+    stream::unfold(poll, move |mut poll| async move {
+        if poll.done {
+            return None;
+        }
+
+        loop {
+            let status = match poll.next.take() {
+                Some(status) => status,
+                None => {
+                    let mut delay = STATUS_POLL_INTERVAL;
+                    loop {
+                        tokio::time::sleep(delay).await;
+                        match (poll.fetch)().await {
+                            Ok(status) => break status,
+                            Err(_) if delay < max_backoff => {
+                                delay = (delay * 2).min(max_backoff);
+                            }
+                            Err(e) => {
+                                poll.done = true;
+                                return Some((Err(e), poll));
+                            }
+                        }
+                    }
+                }
+            };
+
+            let status_key = key(&status);
+            if poll.last.as_ref() != Some(&status_key) {
+                poll.last = Some(status_key);
+                poll.done = (poll.is_final)(&status);
+                return Some((Ok(status), poll));
+            }
+        }
+    })
+}
+
+/// Polls `fetch` every `poll_interval` until it returns a final status, failing once
+/// `timeout` has elapsed. It doesn't poll again if the next poll would be past `timeout`.
+async fn await_final_status<T, F, Fut>(
+    mut fetch: F,
+    is_final: impl Fn(&T) -> bool,
+    timeout: Duration,
+    poll_interval: Duration,
+) -> Result<T, ProviderError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, ProviderError>>,
+{
+    let deadline = tokio::time::Instant::now() + timeout;
+
+    loop {
+        let status = fetch().await?;
+        if is_final(&status) {
+            return Ok(status);
+        }
+
+        if tokio::time::Instant::now() + poll_interval > deadline {
+            return Err(ProviderError::TransactionRequestError(format!(
+                "Transaction wasn't committed within {:?}",
+                timeout
+            )));
+        }
+        tokio::time::sleep(poll_interval).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+    use std::sync::Mutex;
+
+    #[derive(Debug, PartialEq)]
+    enum Status {
+        Submitted,
+        Success,
+    }
+
+    #[tokio::test]
+    async fn status_stream_survives_a_disconnection() {
+        // What the node answers to each poll, the second one losing the connection
+        let answers = Arc::new(Mutex::new(vec![
+            Ok(Status::Submitted),
+            Err(io::Error::from(io::ErrorKind::ConnectionReset)),
+            Ok(Status::Success),
+        ]));
+
+        let fetch = move || {
+            let answer = answers.lock().unwrap().remove(0);
+            async move { answer.map_err(ProviderError::from) }
+        };
+
+        let statuses: Vec<Status> = poll_status(
+            Status::Submitted,
+            fetch,
+            discriminant,
+            |status| *status == Status::Success,
+            Duration::from_secs(1),
+        )
+        .map(Result::unwrap)
+        .collect()
+        .await;
+
+        assert_eq!(statuses, vec![Status::Submitted, Status::Success]);
+    }
+
+    #[tokio::test]
+    async fn await_commit_on_third_poll() {
+        let polls = Arc::new(Mutex::new(0));
+        let fetch = {
+            let polls = polls.clone();
+            move || {
+                let mut polls = polls.lock().unwrap();
+                *polls += 1;
+                let status = match *polls {
+                    3 => Status::Success,
+                    _ => Status::Submitted,
+                };
+                async move { Ok(status) }
+            }
+        };
+
+        let status = await_final_status(
+            fetch,
+            |status| *status == Status::Success,
+            Duration::from_secs(1),
+            Duration::from_millis(10),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(status, Status::Success);
+        assert_eq!(*polls.lock().unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn await_commit_times_out() {
+        let polls = Arc::new(Mutex::new(0));
+        let fetch = {
+            let polls = polls.clone();
+            move || {
+                *polls.lock().unwrap() += 1;
+                async { Ok(Status::Submitted) }
+            }
+        };
+
+        let result = await_final_status(
+            fetch,
+            |status| *status == Status::Success,
+            Duration::from_millis(50),
+            Duration::from_millis(20),
+        )
+        .await;
+
+        assert!(matches!(
+            result,
+            Err(ProviderError::TransactionRequestError(_))
+        ));
+        // At 0, 20 and 40ms, the next poll would be past the timeout
+        assert!(*polls.lock().unwrap() <= 3);
+    }
+
+    #[tokio::test]
+    async fn status_stream_ends_with_an_error_when_the_node_stays_down() {
+        let fetch = || async { Err(io::Error::from(io::ErrorKind::ConnectionRefused).into()) };
+
+        let statuses: Vec<Result<Status, ProviderError>> = poll_status(
+            Status::Submitted,
+            fetch,
+            discriminant,
+            |status| *status == Status::Success,
+            STATUS_POLL_INTERVAL * 4,
+        )
+        .collect()
+        .await;
+
+        assert_eq!(statuses.len(), 2);
+        assert!(matches!(statuses[0], Ok(Status::Submitted)));
+        assert!(matches!(
+            statuses[1],
+            Err(ProviderError::ConnectionError(_))
+        ));
+    }
+
+    #[test]
+    fn connection_failures_are_told_apart_from_node_errors() {
+        let refused = io::Error::from(io::ErrorKind::ConnectionRefused);
+        assert!(matches!(
+            ProviderError::from(refused),
+            ProviderError::ConnectionError(_)
+        ));
+
+        // As reported by the client, wrapping the transport's error
+        let wrapped = io::Error::new(
+            io::ErrorKind::Other,
+            io::Error::from(io::ErrorKind::TimedOut),
+        );
+        let error: Error = ProviderError::from(wrapped).into();
+        assert!(matches!(error, Error::InfrastructureError(_)));
+
+        let answered = io::Error::new(io::ErrorKind::Other, "Transaction is not valid");
+        let error: Error = ProviderError::from(answered).into();
+        assert!(matches!(error, Error::Node(message) if message == "Transaction is not valid"));
+    }
+
+    #[tokio::test]
+    async fn block_stream_yields_new_heights() {
+        // Heights of the latest block at each poll
+        let heights = Arc::new(Mutex::new(vec![1, 1, 2, 2, 4]));
+        let fetch = move || {
+            let height = heights.lock().unwrap().remove(0);
+            async move { Ok(height) }
+        };
+
+        let blocks: Vec<u64> =
+            poll_status(0, fetch, |height| *height, |_| false, STATUS_POLL_INTERVAL)
+                .take(4)
+                .map(Result::unwrap)
+                .collect()
+                .await;
+
+        assert_eq!(blocks, vec![0, 1, 2, 4]);
+    }
+}
@@ -1,7 +1,8 @@
+use crate::typed_data::{typed_data_hash, Domain};
 use fuel_crypto::Hasher;
-use fuel_tx::{Address, Bytes32, Bytes64};
+use fuel_tx::{Address, Bytes32, Bytes64, Transaction};
 use fuel_vm::crypto::secp256k1_sign_compact_recover;
-use fuels_core::Bits256;
+use fuels_core::{Bits256, ParamType, Tokenizable};
 use std::{convert::TryFrom, fmt, str::FromStr};
 use thiserror::Error;
 
@@ -21,6 +22,12 @@ pub enum SignatureError {
     /// Error in recovering public key from signature
     #[error("Public key recovery error")]
     RecoveryError,
+    /// The transaction has no witness at the given index
+    #[error("No witness at index {0}")]
+    MissingWitness(usize),
+    /// Typed data couldn't be encoded to compute its hash
+    #[error("Failed to encode typed data: {0}")]
+    TypedDataError(String),
 }
 
 /// Recovery message data.
@@ -47,10 +54,47 @@ pub enum RecoveryMessage {
 /// and `v` (1-bit) values of the signature. `r` and `s` take on
 /// their usual meaning while `v` is used for recovering the public
 /// key from a signature more quickly.
+///
+/// `Wallet` signs with deterministic nonces as specified by RFC 6979, so signing the
+/// same message with the same key always produces the same signature.
 pub struct Signature {
     pub compact: Bytes64,
 }
 
+/// A [`Signature`] with its recovery id (`v`) split from its `s` value, for formats
+/// storing them apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SignatureWithRecovery {
+    pub r: [u8; 32],
+    pub s: [u8; 32],
+    /// Either 0 or 1.
+    pub recovery_id: u8,
+}
+
+impl SignatureWithRecovery {
+    /// Serializes the signature as `r`, `s` and the recovery id, in 65 bytes.
+    pub fn to_bytes(&self) -> [u8; 65] {
+        let mut bytes = [0u8; 65];
+        bytes[..32].copy_from_slice(&self.r);
+        bytes[32..64].copy_from_slice(&self.s);
+        bytes[64] = self.recovery_id;
+        bytes
+    }
+}
+
+impl From<SignatureWithRecovery> for Signature {
+    fn from(signature: SignatureWithRecovery) -> Self {
+        let mut compact = [0u8; 64];
+        compact[..32].copy_from_slice(&signature.r);
+        compact[32..].copy_from_slice(&signature.s);
+        compact[32] |= (signature.recovery_id & 1) << 7;
+
+        Signature {
+            compact: Bytes64::from(compact),
+        }
+    }
+}
+
 impl fmt::Display for Signature {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", hex::encode(self.compact))
@@ -58,6 +102,18 @@ impl fmt::Display for Signature {
 }
 
 impl Signature {
+    /// Splits the recovery id out of the signature, see [`SignatureWithRecovery`].
+    pub fn with_recovery(&self) -> SignatureWithRecovery {
+        let mut r = [0u8; 32];
+        let mut s = [0u8; 32];
+        r.copy_from_slice(&self.compact[..32]);
+        s.copy_from_slice(&self.compact[32..]);
+        let recovery_id = s[0] >> 7;
+        s[0] &= 0x7f;
+
+        SignatureWithRecovery { r, s, recovery_id }
+    }
+
     /// Verifies that signature on `message` was produced by `address`
     pub fn verify<M, A>(&self, message: M, address: A) -> Result<(), SignatureError>
     where
@@ -95,12 +151,40 @@ impl Signature {
         };
 
         let recovered =
-            secp256k1_sign_compact_recover(self.compact.as_ref(), message_hash.as_ref()).unwrap();
+            secp256k1_sign_compact_recover(self.compact.as_ref(), message_hash.as_ref())
+                .map_err(|_| SignatureError::RecoveryError)?;
 
         let hashed = Hasher::hash(recovered);
 
         Ok(Address::new(*hashed))
     }
+
+    /// Recovers the Fuel address which signed `data`, of the ABI type `param_type`, within
+    /// `domain` with `Signer::sign_typed_data`.
+    pub fn recover_typed_data<T: Tokenizable + Clone>(
+        &self,
+        data: &T,
+        param_type: &ParamType,
+        domain: &Domain,
+    ) -> Result<Address, SignatureError> {
+        let digest = typed_data_hash(data, param_type, domain)
+            .map_err(|e| SignatureError::TypedDataError(e.to_string()))?;
+
+        // `sign_typed_data` signs the digest as a message, hashing it once more.
+        self.recover(RecoveryMessage::Data(digest.to_vec()))
+    }
+}
+
+/// Recovers the address that produced the signature stored as the witness at
+/// `witness_index` of `tx`, e.g. to check who authorized a transaction before relaying it.
+pub fn recover_signer(tx: &Transaction, witness_index: usize) -> Result<Address, SignatureError> {
+    let witness = tx
+        .witnesses()
+        .get(witness_index)
+        .ok_or(SignatureError::MissingWitness(witness_index))?;
+    let signature = Signature::try_from(witness.as_ref())?;
+
+    signature.recover(&tx.id())
 }
 
 impl<'a> TryFrom<&'a [u8]> for Signature {
@@ -195,6 +279,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn split_recovery_id() {
+        let signature = Signature::from_str(
+            "64d8b60c08a7ecab307cb11a31a7153ec7e4ff06a8fb78b4fe9c982d44c731efe63303ec5c7686a56445bacdd4ee89f592f1b3e68bded25ea404cd6806205db4"
+        ).expect("could not parse signature");
+
+        let with_recovery = signature.with_recovery();
+        assert_eq!(with_recovery.recovery_id, 1);
+        assert_eq!(with_recovery.r, signature.compact[..32]);
+        assert_eq!(with_recovery.s[0], 0x66);
+        assert_eq!(with_recovery.s[1..], signature.compact[33..]);
+
+        let bytes = with_recovery.to_bytes();
+        assert_eq!(bytes[64], 1);
+        assert_eq!(Signature::from(with_recovery), signature);
+    }
+
     #[test]
     fn signature_from_str() {
         let s1 = Signature::from_str(
@@ -0,0 +1,363 @@
+//! How a `Provider` sends its queries to the node: through a `FuelClient`, or through a
+//! `reqwest::Client` configured by the caller, e.g. with timeouts or a proxy.
+use crate::provider::ProviderError;
+use cynic::http::ReqwestExt;
+use cynic::{MutationBuilder, Operation, QueryBuilder};
+use fuel_gql_client::client::schema::{
+    self,
+    block::{Block, BlockByIdArgs},
+    chain::ChainInfo,
+    coin::{Coin, CoinByIdArgs, SpendQueryElementInput},
+    contract::ContractBalanceQueryArgs,
+    tx::{TxArg, TxIdArgs},
+    Bytes, HexString, HexString256,
+};
+use fuel_gql_client::client::{
+    types::{TransactionResponse, TransactionStatus},
+    FuelClient, PaginatedResult, PaginationRequest,
+};
+use fuel_tx::{Receipt, Transaction};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+#[derive(Debug, Clone)]
+pub(crate) enum Transport {
+    Client(FuelClient),
+    Http {
+        client: reqwest::Client,
+        url: reqwest::Url,
+    },
+}
+
+impl Transport {
+    /// Sends the queries to the GraphQL API of the node at `url` with `client`. As with
+    /// `FuelClient`, the scheme defaults to `http` and the path is set to `/graphql`.
+    pub fn http(client: reqwest::Client, url: &str) -> Result<Self, ProviderError> {
+        let url = match url.starts_with("http") {
+            true => url.to_string(),
+            false => format!("http://{}", url),
+        };
+        let mut url = reqwest::Url::parse(&url)
+            .map_err(|e| ProviderError::ConnectionError(format!("Invalid node URL: {}", e)))?;
+        url.set_path("/graphql");
+
+        Ok(Self::Http { client, url })
+    }
+
+    /// Sends `operation` over HTTP. Failing to reach the node or to read its answer is a
+    /// `ConnectionError`, errors answered by the node are returned as they are.
+    async fn query<'a, R: 'a>(
+        client: &reqwest::Client,
+        url: &reqwest::Url,
+        operation: Operation<'a, R>,
+    ) -> Result<R, ProviderError> {
+        let response = client
+            .post(url.clone())
+            .run_graphql(operation)
+            .await
+            .map_err(|e| ProviderError::ConnectionError(e.to_string()))?;
+
+        match (response.data, response.errors) {
+            (Some(data), _) => Ok(data),
+            (_, Some(errors)) => Err(node_error(
+                errors
+                    .into_iter()
+                    .map(|e| e.message)
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            )),
+            _ => Err(node_error("Invalid response")),
+        }
+    }
+
+    /// Sends `query`, which `FuelClient` has no operation for, e.g. to reach parts of the
+    /// node's API more recent than it, and deserializes its data. Fails with
+    /// `ProviderError::Unsupported` when sending through a `FuelClient`, which can't send
+    /// arbitrary queries.
+    pub async fn raw_query<T: DeserializeOwned>(&self, query: &str) -> Result<T, ProviderError> {
+        let (client, url) = match self {
+            Self::Client(_) => {
+                return Err(ProviderError::Unsupported(
+                    "this query needs a provider created with `from_client` or `connect`".into(),
+                ))
+            }
+            Self::Http { client, url } => (client, url),
+        };
+
+        let response = client
+            .post(url.clone())
+            .json(&RawQuery { query })
+            .send()
+            .await
+            .map_err(|e| ProviderError::ConnectionError(e.to_string()))?
+            .json::<RawResponse<T>>()
+            .await
+            .map_err(node_error)?;
+
+        match (response.data, response.errors) {
+            (Some(data), _) => Ok(data),
+            (_, Some(errors)) => Err(node_error(
+                errors
+                    .into_iter()
+                    .map(|e| e.message)
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            )),
+            _ => Err(node_error("Invalid response")),
+        }
+    }
+
+    /// Whether the GraphQL type `type_name` of the node's schema has the field `field`,
+    /// e.g. to tell if the node supports a query before sending it.
+    pub async fn has_field(&self, type_name: &str, field: &str) -> Result<bool, ProviderError> {
+        let query = format!(
+            "{{ __type(name: \"{}\") {{ fields {{ name }} }} }}",
+            type_name
+        );
+        let schema: TypeQuery = self.raw_query(&query).await?;
+
+        Ok(schema
+            .ty
+            .and_then(|ty| ty.fields)
+            .unwrap_or_default()
+            .iter()
+            .any(|f| f.name == field))
+    }
+
+    pub async fn submit(&self, tx: &Transaction) -> Result<HexString256, ProviderError> {
+        match self {
+            Self::Client(client) => Ok(client.submit(tx).await?),
+            Self::Http { client, url } => {
+                let tx = HexString(Bytes(tx.clone().to_bytes()));
+                let mutation = schema::tx::Submit::build(&TxArg { tx });
+                Ok(Self::query(client, url, mutation).await?.submit)
+            }
+        }
+    }
+
+    pub async fn dry_run(&self, tx: &Transaction) -> Result<Vec<Receipt>, ProviderError> {
+        match self {
+            Self::Client(client) => Ok(client.dry_run(tx).await?),
+            Self::Http { client, url } => {
+                let tx = HexString(Bytes(tx.clone().to_bytes()));
+                let query = schema::tx::DryRun::build(&TxArg { tx });
+                Self::query(client, url, query)
+                    .await?
+                    .dry_run
+                    .into_iter()
+                    .map(|receipt| receipt.try_into().map_err(node_error))
+                    .collect()
+            }
+        }
+    }
+
+    pub async fn receipts(&self, id: &str) -> Result<Vec<Receipt>, ProviderError> {
+        match self {
+            Self::Client(client) => Ok(client.receipts(id).await?),
+            Self::Http { client, url } => {
+                let query = schema::tx::TransactionQuery::build(&TxIdArgs { id: parse(id)? });
+                Self::query(client, url, query)
+                    .await?
+                    .transaction
+                    .ok_or_else(|| node_error(format!("Transaction {} not found", id)))?
+                    .receipts
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|receipt| receipt.try_into().map_err(node_error))
+                    .collect()
+            }
+        }
+    }
+
+    pub async fn transaction_status(&self, id: &str) -> Result<TransactionStatus, ProviderError> {
+        match self {
+            Self::Client(client) => Ok(client.transaction_status(id).await?),
+            Self::Http { client, url } => {
+                let query = schema::tx::TransactionQuery::build(&TxIdArgs { id: parse(id)? });
+                Self::query(client, url, query)
+                    .await?
+                    .transaction
+                    .and_then(|tx| tx.status)
+                    .ok_or_else(|| node_error(format!("Status not found for transaction {}", id)))?
+                    .try_into()
+                    .map_err(node_error)
+            }
+        }
+    }
+
+    pub async fn transactions_by_owner(
+        &self,
+        owner: &str,
+        request: PaginationRequest<String>,
+    ) -> Result<PaginatedResult<TransactionResponse, String>, ProviderError> {
+        match self {
+            Self::Client(client) => Ok(client.transactions_by_owner(owner, request).await?),
+            Self::Http { client, url } => {
+                let owner: HexString256 = parse(owner)?;
+                let query = schema::tx::TransactionsByOwnerQuery::build(&(owner, request).into());
+                Self::query(client, url, query)
+                    .await?
+                    .transactions_by_owner
+                    .try_into()
+                    .map_err(node_error)
+            }
+        }
+    }
+
+    pub async fn chain_info(&self) -> Result<ChainInfo, ProviderError> {
+        match self {
+            Self::Client(client) => Ok(client.chain_info().await?),
+            Self::Http { client, url } => {
+                let query = schema::chain::ChainQuery::build(());
+                Ok(Self::query(client, url, query).await?.chain)
+            }
+        }
+    }
+
+    pub async fn block(&self, id: &str) -> Result<Option<Block>, ProviderError> {
+        match self {
+            Self::Client(client) => Ok(client.block(id).await?),
+            Self::Http { client, url } => {
+                let query = schema::block::BlockByIdQuery::build(&BlockByIdArgs { id: parse(id)? });
+                Ok(Self::query(client, url, query).await?.block)
+            }
+        }
+    }
+
+    pub async fn coins(
+        &self,
+        owner: &str,
+        asset_id: Option<&str>,
+        request: PaginationRequest<String>,
+    ) -> Result<PaginatedResult<Coin, String>, ProviderError> {
+        match self {
+            Self::Client(client) => Ok(client.coins(owner, asset_id, request).await?),
+            Self::Http { client, url } => {
+                let owner: HexString256 = parse(owner)?;
+                let asset_id: HexString256 = match asset_id {
+                    Some(asset_id) => parse(asset_id)?,
+                    None => HexString256::default(),
+                };
+                let query = schema::coin::CoinsQuery::build(&(owner, asset_id, request).into());
+                Ok(Self::query(client, url, query).await?.coins.into())
+            }
+        }
+    }
+
+    pub async fn coin(&self, id: &str) -> Result<Option<Coin>, ProviderError> {
+        match self {
+            Self::Client(client) => Ok(client.coin(id).await?),
+            Self::Http { client, url } => {
+                let query = schema::coin::CoinByIdQuery::build(CoinByIdArgs {
+                    utxo_id: parse(id)?,
+                });
+                Ok(Self::query(client, url, query).await?.coin)
+            }
+        }
+    }
+
+    pub async fn coins_to_spend(
+        &self,
+        owner: &str,
+        spend_query: Vec<(&str, u64)>,
+        max_inputs: Option<i32>,
+    ) -> Result<Vec<Coin>, ProviderError> {
+        match self {
+            Self::Client(client) => Ok(client
+                .coins_to_spend(owner, spend_query, max_inputs)
+                .await?),
+            Self::Http { client, url } => {
+                let owner: HexString256 = parse(owner)?;
+                let spend_query = spend_query
+                    .into_iter()
+                    .map(|(asset_id, amount)| {
+                        Ok(SpendQueryElementInput {
+                            asset_id: parse(asset_id)?,
+                            amount: amount.into(),
+                        })
+                    })
+                    .collect::<Result<Vec<_>, ProviderError>>()?;
+                let query = schema::coin::CoinsToSpendQuery::build(
+                    &(owner, spend_query, max_inputs).into(),
+                );
+                Ok(Self::query(client, url, query).await?.coins_to_spend)
+            }
+        }
+    }
+
+    pub async fn contract_balance(
+        &self,
+        id: &str,
+        asset_id: Option<&str>,
+    ) -> Result<u64, ProviderError> {
+        match self {
+            Self::Client(client) => Ok(client.contract_balance(id, asset_id).await?),
+            Self::Http { client, url } => {
+                let asset: HexString256 = match asset_id {
+                    Some(asset_id) => parse(asset_id)?,
+                    None => HexString256::default(),
+                };
+                let query =
+                    schema::contract::ContractBalanceQuery::build(&ContractBalanceQueryArgs {
+                        id: parse(id)?,
+                        asset,
+                    });
+                Ok(Self::query(client, url, query)
+                    .await?
+                    .contract_balance
+                    .amount
+                    .0)
+            }
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct RawQuery<'a> {
+    query: &'a str,
+}
+
+#[derive(Deserialize)]
+struct RawResponse<T> {
+    data: Option<T>,
+    errors: Option<Vec<RawError>>,
+}
+
+#[derive(Deserialize)]
+struct RawError {
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct TypeQuery {
+    #[serde(rename = "__type")]
+    ty: Option<TypeFields>,
+}
+
+#[derive(Deserialize)]
+struct TypeFields {
+    fields: Option<Vec<Field>>,
+}
+
+#[derive(Deserialize)]
+struct Field {
+    name: String,
+}
+
+/// An error answered by the node, in the same form as those reported by `FuelClient`.
+fn node_error(message: impl ToString) -> ProviderError {
+    ProviderError::ClientRequestError(std::io::Error::new(
+        std::io::ErrorKind::Other,
+        message.to_string(),
+    ))
+}
+
+/// Parses an id given as a hex string into the type the node's schema expects.
+fn parse<T: FromStr>(id: &str) -> Result<T, ProviderError>
+where
+    T::Err: ToString,
+{
+    id.parse()
+        .map_err(|e: T::Err| ProviderError::TransactionRequestError(e.to_string()))
+}
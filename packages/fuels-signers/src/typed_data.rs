@@ -0,0 +1,169 @@
+use fuel_crypto::Hasher;
+use fuel_tx::{Bytes32, ContractId};
+use fuels_core::abi_decoder::ABIDecoder;
+use fuels_core::abi_encoder::ABIEncoder;
+use fuels_core::{ParamType, Token, Tokenizable};
+use thiserror::Error;
+
+/// Prefix of the digest of typed data, so that it can't be mistaken for the
+/// hash of a transaction or of a plain message.
+const TYPED_DATA_PREFIX: &[u8] = b"\x19\x01";
+
+/// Why typed data couldn't be hashed.
+#[derive(Debug, Error)]
+pub enum TypedDataError {
+    #[error("Failed to encode typed data: {0}")]
+    Encoding(String),
+    #[error("Typed data doesn't have the type {0:?}")]
+    TypeMismatch(ParamType),
+}
+
+/// Identifies the application a typed-data signature is meant for, so that a
+/// signature produced for one application can't be replayed on another.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Domain {
+    pub name: String,
+    pub version: String,
+    pub chain_id: u64,
+    pub verifying_contract: ContractId,
+}
+
+impl Domain {
+    pub fn new(name: &str, version: &str, chain_id: u64, verifying_contract: ContractId) -> Self {
+        Self {
+            name: name.to_string(),
+            version: version.to_string(),
+            chain_id,
+            verifying_contract,
+        }
+    }
+
+    /// Hash of the ABI-encoded domain fields.
+    pub fn separator(&self) -> Result<Bytes32, TypedDataError> {
+        let tokens = [
+            Token::DynamicString(self.name.clone()),
+            Token::DynamicString(self.version.clone()),
+            Token::U64(self.chain_id),
+            Token::B256(*self.verifying_contract),
+        ];
+        let encoded = ABIEncoder::new()
+            .encode(&tokens)
+            .map_err(|e| TypedDataError::Encoding(e.to_string()))?;
+
+        Ok(Hasher::hash(encoded))
+    }
+}
+
+/// Returns the digest signed by `Signer::sign_typed_data`:
+/// `hash(0x1901 || domain_separator || hash(type_descriptor || encoded_data))`.
+///
+/// The type descriptor of `param_type`, the ABI type of the data, is hashed along with
+/// its encoding, since different types can share the same encoding, e.g. `(u64, bool)`
+/// and `(u64, u64)`, or an empty `Vec<u64>` and an empty `Vec<bool>`. Fails if `data`
+/// doesn't have that type.
+pub fn typed_data_hash<T: Tokenizable + Clone>(
+    data: &T,
+    param_type: &ParamType,
+    domain: &Domain,
+) -> Result<Bytes32, TypedDataError> {
+    let token = data.clone().into_token();
+    let encoded = ABIEncoder::new()
+        .encode(&[token.clone()])
+        .map_err(|e| TypedDataError::Encoding(e.to_string()))?;
+
+    // The data has the type if it decodes back to itself with it
+    let decoded = ABIDecoder::new().decode(std::slice::from_ref(param_type), &encoded);
+    if !matches!(decoded.as_deref(), Ok([decoded]) if *decoded == token) {
+        return Err(TypedDataError::TypeMismatch(param_type.clone()));
+    }
+
+    let mut struct_data = type_descriptor(param_type).into_bytes();
+    struct_data.extend(encoded);
+    let struct_hash = Hasher::hash(struct_data);
+
+    let mut digest = TYPED_DATA_PREFIX.to_vec();
+    digest.extend_from_slice(domain.separator()?.as_ref());
+    digest.extend_from_slice(struct_hash.as_ref());
+
+    Ok(Hasher::hash(digest))
+}
+
+/// Describes a type, e.g. `s(u64,[bool;2])` for a struct holding a `u64` and an array of
+/// two `bool`s.
+fn type_descriptor(param_type: &ParamType) -> String {
+    let join = |param_types: &[ParamType]| {
+        param_types
+            .iter()
+            .map(type_descriptor)
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+
+    match param_type {
+        ParamType::U8 => "u8".to_string(),
+        ParamType::U16 => "u16".to_string(),
+        ParamType::U32 => "u32".to_string(),
+        ParamType::U64 => "u64".to_string(),
+        ParamType::Bool => "bool".to_string(),
+        ParamType::Byte => "byte".to_string(),
+        ParamType::B256 => "b256".to_string(),
+        ParamType::Array(inner, len) => format!("[{};{}]", type_descriptor(inner), len),
+        ParamType::Vector(inner) => format!("Vec<{}>", type_descriptor(inner)),
+        ParamType::String(len) => format!("str[{}]", len),
+        ParamType::DynamicString => "str".to_string(),
+        ParamType::Struct(members) => format!("s({})", join(members)),
+        ParamType::Enum(variants) => format!("e({})", join(variants)),
+        ParamType::Tuple(members) => format!("({})", join(members)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn type_descriptors() {
+        let param_type = ParamType::Struct(vec![
+            ParamType::U64,
+            ParamType::Array(Box::new(ParamType::Bool), 2),
+            ParamType::Tuple(vec![ParamType::U8, ParamType::DynamicString]),
+        ]);
+
+        assert_eq!(type_descriptor(&param_type), "s(u64,[bool;2],(u8,str))");
+    }
+
+    #[test]
+    fn domain_separates_hashes() {
+        let data = (1u64, true);
+        let param_type = ParamType::Tuple(vec![ParamType::U64, ParamType::Bool]);
+        let domain = Domain::new("app", "1", 0, ContractId::zeroed());
+        let other_domain = Domain::new("other app", "1", 0, ContractId::zeroed());
+
+        assert_ne!(
+            typed_data_hash(&data, &param_type, &domain).unwrap(),
+            typed_data_hash(&data, &param_type, &other_domain).unwrap()
+        );
+    }
+
+    #[test]
+    fn empty_arrays_of_different_types() {
+        let domain = Domain::default();
+        let u64s = ParamType::Array(Box::new(ParamType::U64), 0);
+        let bools = ParamType::Array(Box::new(ParamType::Bool), 0);
+
+        assert_ne!(
+            typed_data_hash(&Vec::<u64>::new(), &u64s, &domain).unwrap(),
+            typed_data_hash(&Vec::<bool>::new(), &bools, &domain).unwrap()
+        );
+    }
+
+    #[test]
+    fn data_of_another_type() {
+        let param_type = ParamType::Tuple(vec![ParamType::U64, ParamType::U64]);
+
+        assert!(matches!(
+            typed_data_hash(&(1u64, true), &param_type, &Domain::default()),
+            Err(TypedDataError::TypeMismatch(_))
+        ));
+    }
+}
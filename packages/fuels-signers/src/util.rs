@@ -1,3 +1,171 @@
+use crate::signature::{RecoveryMessage, Signature};
+use fuel_crypto::Hasher;
+use fuel_tx::{Address, AssetId, Bytes32, Input, Output, Transaction, UtxoId};
+use fuel_types::bytes::SizedBytes;
+use fuels_core::errors::Error;
+use secp256k1::PublicKey;
+use std::collections::{HashMap, HashSet};
+use thiserror::Error;
+
+/// Verifies that `signature` is the signature of `message`, as produced by
+/// `Signer::sign_message`, by the owner of `expected`.
+pub fn verify_message(
+    message: impl AsRef<[u8]>,
+    signature: &Signature,
+    expected: &Address,
+) -> Result<(), Error> {
+    signature
+        .verify(RecoveryMessage::Data(message.as_ref().to_vec()), *expected)
+        .map_err(|e| Error::SignatureVerification(e.to_string()))
+}
+
+/// Recovers the address that signed `message`, as produced by `Signer::sign_message`.
+/// A tampered message or signature recovers another address, so the result must be
+/// compared to the expected signer, see `verify_matches`.
+pub fn verify_and_recover(
+    message: impl AsRef<[u8]>,
+    signature: &Signature,
+) -> Result<Address, Error> {
+    signature
+        .recover(RecoveryMessage::Data(message.as_ref().to_vec()))
+        .map_err(|e| Error::SignatureVerification(e.to_string()))
+}
+
+/// Whether `message` was signed by the owner of `expected`. Unlike `verify_message`, a
+/// signature by someone else isn't an error, only a signature nothing can be recovered
+/// from is.
+pub fn verify_matches(
+    message: impl AsRef<[u8]>,
+    signature: &Signature,
+    expected: &Address,
+) -> Result<bool, Error> {
+    Ok(verify_and_recover(message, signature)? == *expected)
+}
+
+/// Returns the address of `public_key`, the hash of its uncompressed encoding without the
+/// leading `0x04` byte, as for the wallets of `Wallet::new_from_private_key`.
+pub fn address_from_public_key(public_key: &PublicKey) -> Address {
+    let public = public_key.serialize_uncompressed();
+    Address::new(*Hasher::hash(&public[1..]))
+}
+
+/// Parses a public key from either its compressed (33 bytes) or uncompressed (65 bytes)
+/// encoding. Fails with `Error::InvalidData` for any other length, or if the bytes
+/// aren't a valid key.
+pub fn public_key_from_bytes(bytes: &[u8]) -> Result<PublicKey, Error> {
+    match bytes.len() {
+        33 | 65 => PublicKey::from_slice(bytes).map_err(|_| Error::InvalidData),
+        _ => Err(Error::InvalidData),
+    }
+}
+
+/// Returns the size of `tx` once serialized, which is what the node charges its byte
+/// price on: the transaction pays `estimated_byte_size(&tx) * byte_price` for its bytes,
+/// on top of what it pays for gas.
+pub fn estimated_byte_size(tx: &Transaction) -> usize {
+    tx.serialized_size()
+}
+
+/// Returns a change output to `to` for each asset spent by the coins of `inputs`, in the
+/// order the assets first appear, so that nothing the inputs hold above what the other
+/// outputs take is lost.
+pub fn change_outputs(inputs: &[Input], to: Address) -> Vec<Output> {
+    let mut assets = HashSet::new();
+    inputs
+        .iter()
+        .filter_map(|input| match input {
+            Input::Coin { asset_id, .. } if assets.insert(*asset_id) => {
+                Some(Output::change(to, 0, *asset_id))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the id the node assigns to `tx` once it's submitted, e.g. to track it or to
+/// avoid submitting it twice. Witnesses aren't part of the id, so it's the same before
+/// and after the transaction is signed.
+pub fn transaction_id(tx: &Transaction) -> Bytes32 {
+    tx.id()
+}
+
+/// A problem found in a transaction before submitting it, see
+/// `validate_without_signature`.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    #[error("Coin {0:?} is spent by more than one input")]
+    DuplicateInput(UtxoId),
+    #[error("Coin outputs of asset {0} add up to {1}, more than the {2} spent by the inputs")]
+    OutputsExceedInputs(AssetId, u64, u64),
+    #[error("Input {0} is signed by witness {1}, which the transaction doesn't have")]
+    MissingWitness(usize, u8),
+    #[error("Rejected by fuel-tx: {0:?}")]
+    Tx(fuel_tx::ValidationError),
+}
+
+/// Checks `tx` for the mistakes the node would reject it for, without checking its
+/// signatures, so that they're reported before submitting it. Every problem the SDK
+/// looks for is reported; `fuel-tx`'s own validation, which stops at the first problem,
+/// only runs once these pass. The maturity of the transaction isn't checked, as it
+/// depends on the current block height.
+pub fn validate_without_signature(tx: &Transaction) -> Result<(), Vec<ValidationError>> {
+    let mut errors = vec![];
+    let mut spent = HashSet::new();
+    let mut input_amounts: HashMap<AssetId, u64> = HashMap::new();
+    let mut output_amounts: HashMap<AssetId, u64> = HashMap::new();
+
+    for (index, input) in tx.inputs().iter().enumerate() {
+        if let Input::Coin {
+            utxo_id,
+            amount,
+            asset_id,
+            witness_index,
+            predicate,
+            ..
+        } = input
+        {
+            if !spent.insert(*utxo_id) {
+                errors.push(ValidationError::DuplicateInput(*utxo_id));
+            }
+            if predicate.is_empty() && *witness_index as usize >= tx.witnesses().len() {
+                errors.push(ValidationError::MissingWitness(index, *witness_index));
+            }
+            let total = input_amounts.entry(*asset_id).or_default();
+            *total = total.saturating_add(*amount);
+        }
+    }
+
+    for output in tx.outputs() {
+        if let Output::Coin {
+            amount, asset_id, ..
+        } = output
+        {
+            let total = output_amounts.entry(*asset_id).or_default();
+            *total = total.saturating_add(*amount);
+        }
+    }
+
+    for (asset_id, total) in output_amounts {
+        let available = input_amounts.get(&asset_id).copied().unwrap_or_default();
+        if total > available {
+            errors.push(ValidationError::OutputsExceedInputs(
+                asset_id, total, available,
+            ));
+        }
+    }
+
+    if errors.is_empty() {
+        if let Err(e) = tx.validate_without_signature(tx.maturity()) {
+            errors.push(ValidationError::Tx(e));
+        }
+    }
+
+    match errors.is_empty() {
+        true => Ok(()),
+        false => Err(errors),
+    }
+}
+
 #[allow(missing_docs)]
 #[cfg(feature = "test-helpers")]
 /// Testing utilities
@@ -10,8 +178,7 @@ pub mod test_helpers {
         service::{Config, DbType, FuelService},
     };
     use fuel_crypto::Hasher;
-    use fuel_gql_client::client::FuelClient;
-    use fuel_tx::{Address, Bytes32, Bytes64, UtxoId};
+    use fuel_tx::{Address, AssetId, Bytes32, Bytes64, UtxoId};
     use fuels_core::constants::DEFAULT_INITIAL_BALANCE;
     use rand::{Fill, Rng};
     use secp256k1::{PublicKey, Secp256k1, SecretKey};
@@ -31,6 +198,26 @@ pub mod test_helpers {
     pub fn setup_address_and_coins(
         num_of_coins: usize,
         amount: u64,
+    ) -> (SecretKey, Vec<(UtxoId, Coin)>) {
+        setup_address_and_coins_with_asset(num_of_coins, amount, AssetId::default())
+    }
+
+    /// Same as `setup_address_and_coins`, with coins of `asset_id` rather than of the
+    /// base asset.
+    pub fn setup_address_and_coins_with_asset(
+        num_of_coins: usize,
+        amount: u64,
+        asset_id: AssetId,
+    ) -> (SecretKey, Vec<(UtxoId, Coin)>) {
+        setup_address_and_coins_with_assets(num_of_coins, amount, &[asset_id])
+    }
+
+    /// Same as `setup_address_and_coins`, with `num_of_coins` coins of each of `asset_ids`,
+    /// all owned by the same address.
+    pub fn setup_address_and_coins_with_assets(
+        num_of_coins: usize,
+        amount: u64,
+        asset_ids: &[AssetId],
     ) -> (SecretKey, Vec<(UtxoId, Coin)>) {
         let mut rng = rand::thread_rng();
 
@@ -45,12 +232,13 @@ pub mod test_helpers {
         let public = Bytes64::try_from(&public[1..]).unwrap();
         let hashed = Hasher::hash(public);
 
-        let coins: Vec<(UtxoId, Coin)> = (1..=num_of_coins)
-            .map(|_i| {
+        let mut coins: Vec<(UtxoId, Coin)> = Vec::with_capacity(num_of_coins * asset_ids.len());
+        for asset_id in asset_ids {
+            for _ in 0..num_of_coins {
                 let coin = Coin {
                     owner: Address::from(*hashed),
                     amount,
-                    asset_id: Default::default(),
+                    asset_id: *asset_id,
                     maturity: Default::default(),
                     status: CoinStatus::Unspent,
                     block_created: Default::default(),
@@ -59,9 +247,9 @@ pub mod test_helpers {
                 let mut r = Bytes32::zeroed();
                 r.try_fill(&mut rng).unwrap();
                 let utxo_id = UtxoId::new(r, 0);
-                (utxo_id, coin)
-            })
-            .collect();
+                coins.push((utxo_id, coin));
+            }
+        }
 
         (secret, coins)
     }
@@ -97,8 +285,8 @@ pub mod test_helpers {
         };
 
         let srv = FuelService::new_node(config).await.unwrap();
-        let client = FuelClient::from(srv.bound_address);
+        let provider = Provider::connect(srv.bound_address).await.unwrap();
 
-        (Provider::new(client), srv.bound_address)
+        (provider, srv.bound_address)
     }
 }
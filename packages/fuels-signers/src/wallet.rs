@@ -1,16 +1,35 @@
-use crate::provider::{Provider, ProviderError};
+use crate::derivation::{
+    derive_secret_key, mnemonic_to_seed, DerivationConfig, DerivationError, DerivationPath,
+};
+use crate::provider::{ConsensusParameters, Provider, ProviderError};
 use crate::signature::Signature;
+use crate::typed_data::TypedDataError;
+use crate::util::{address_from_public_key, change_outputs, estimated_byte_size};
 use crate::Signer;
 use async_trait::async_trait;
 use fuel_crypto::Hasher;
-use fuel_gql_client::client::schema::coin::Coin;
-use fuel_tx::{Address, AssetId, Bytes64, Input, Output, Receipt, Transaction, UtxoId, Witness};
+use fuel_gql_client::client::schema::coin::{Coin, CoinStatus};
+use fuel_tx::{Address, AssetId, Bytes32, Input, Output, Receipt, Transaction, UtxoId, Witness};
 use fuel_vm::crypto::secp256k1_sign_compact_recoverable;
+use fuels_core::constants::DEFAULT_GAS_LIMIT;
 use fuels_core::errors::Error;
+use fuels_core::parameters::TxParameters;
 use secp256k1::{PublicKey, Secp256k1, SecretKey};
-use std::{fmt, io};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use thiserror::Error;
 
+/// The outcome of a transfer sent by a [`Wallet`].
+#[derive(Debug, Clone)]
+pub struct TransferResult {
+    /// Id of the transaction, which can be used to query its status later on.
+    pub tx_id: Bytes32,
+    pub receipts: Vec<Receipt>,
+}
+
 /// A FuelVM-compatible wallet which can be used for signing, sending transactions, and more.
 ///
 /// # Examples
@@ -67,6 +86,41 @@ pub struct Wallet {
     pub(crate) address: Address,
 
     pub provider: Provider,
+
+    /// Coins selected by in-flight transfers. Shared between clones so that
+    /// concurrent transfers from the same wallet pick disjoint coins.
+    pub(crate) reserved_coins: Arc<Mutex<HashSet<UtxoId>>>,
+
+    /// Optional cache of this wallet's coins, see `with_coin_cache`.
+    pub(crate) coin_cache: Option<Arc<Mutex<CoinCache>>>,
+}
+
+/// Holds the last set of coins fetched from the provider until it expires.
+#[derive(Debug)]
+pub(crate) struct CoinCache {
+    ttl: Duration,
+    coins: Option<(Instant, Vec<Coin>)>,
+}
+
+impl CoinCache {
+    fn new(ttl: Duration) -> Self {
+        Self { ttl, coins: None }
+    }
+
+    fn get(&self) -> Option<Vec<Coin>> {
+        match &self.coins {
+            Some((fetched_at, coins)) if fetched_at.elapsed() < self.ttl => Some(coins.clone()),
+            _ => None,
+        }
+    }
+
+    fn set(&mut self, coins: Vec<Coin>) {
+        self.coins = Some((Instant::now(), coins));
+    }
+
+    fn invalidate(&mut self) {
+        self.coins = None;
+    }
 }
 
 #[derive(Error, Debug)]
@@ -85,11 +139,48 @@ pub enum WalletError {
     NoProvider,
     #[error("Provider error: {0}")]
     ProviderError(#[from] ProviderError),
+    #[error("Coin inputs owned by this wallet don't reference witness index {0}")]
+    WitnessIndexMismatch(u8),
+    #[error("This wallet is watch-only and can't sign")]
+    WatchOnly,
+    #[error("Derivation error: {0}")]
+    DerivationError(#[from] DerivationError),
+    #[error("Not enough unreserved coins to spend {0} of asset {1}")]
+    NotEnoughCoins(u64, AssetId),
+    #[error("Can't split a coin into {0} outputs, the maximum is {1}")]
+    InvalidSplit(usize, usize),
+    #[error("Gas limit {0} is higher than the maximum of {1} per transaction")]
+    GasLimitTooHigh(u64, u64),
+    #[error("Transfer needs {0} inputs, the maximum is {1}")]
+    TooManyInputs(usize, usize),
+    #[error("Transfer needs {0} outputs, the maximum is {1}")]
+    TooManyOutputs(usize, usize),
+    #[error(transparent)]
+    TypedDataError(#[from] TypedDataError),
+    #[error("Input {0:?} isn't a coin owned by this wallet")]
+    ForeignInput(UtxoId),
+    #[error(
+        "Input {0:?} isn't an unspent and unreserved coin of this wallet, as the node knows it"
+    )]
+    UnspendableInput(UtxoId),
+    #[error("Invalid private key: {0}")]
+    InvalidPrivateKey(String),
+    #[error("The transaction has no coin inputs owned by this wallet")]
+    NoOwnedInputs,
+    #[error("Transfer would leave {1} of asset {0} as change, below the minimum of {2}")]
+    DustChange(AssetId, u64, u64),
+    #[error("The amounts to transfer add up to more than {}", u64::MAX)]
+    AmountOverflow,
 }
 
 impl From<WalletError> for Error {
     fn from(e: WalletError) -> Self {
-        Error::WalletError(e.to_string())
+        match e {
+            WalletError::ProviderError(e) => e.into(),
+            WalletError::GasLimitTooHigh(limit, max) => Error::GasLimitTooHigh(limit, max),
+            WalletError::InvalidPrivateKey(reason) => Error::InvalidPrivateKey(reason),
+            _ => Error::WalletError(e.to_string()),
+        }
     }
 }
 
@@ -98,19 +189,88 @@ impl Wallet {
         private_key: SecretKey,
         provider: Provider,
     ) -> Result<Self, WalletError> {
-        let secp = Secp256k1::new();
-
-        let public = PublicKey::from_secret_key(&secp, &private_key).serialize_uncompressed();
-        let public = Bytes64::try_from(&public[1..])?;
-        let hashed = Hasher::hash(public);
+        let public_key = PublicKey::from_secret_key(&Secp256k1::new(), &private_key);
 
         Ok(Self {
             private_key,
-            address: Address::new(*hashed),
+            address: address_from_public_key(&public_key),
             provider,
+            reserved_coins: Default::default(),
+            coin_cache: None,
         })
     }
 
+    /// Creates a wallet from a private key in hex, with or without a `0x` prefix, e.g. as
+    /// read from an environment variable.
+    pub fn new_from_private_key_str(
+        private_key: &str,
+        provider: Provider,
+    ) -> Result<Self, WalletError> {
+        let hex_key = private_key.trim();
+        let hex_key = hex_key.strip_prefix("0x").unwrap_or(hex_key);
+        if hex_key.len() != 64 {
+            return Err(WalletError::InvalidPrivateKey(format!(
+                "expected 64 hex characters, got {}",
+                hex_key.len()
+            )));
+        }
+
+        // The key itself is left out of the errors, they may end up in logs
+        let bytes = hex::decode(hex_key)
+            .map_err(|_| WalletError::InvalidPrivateKey("invalid hex".to_string()))?;
+        let private_key = SecretKey::from_slice(&bytes).map_err(|_| {
+            WalletError::InvalidPrivateKey("not a valid secp256k1 secret key".to_string())
+        })?;
+
+        Self::new_from_private_key(private_key, provider)
+    }
+
+    /// Creates a wallet from the key derived from `seed` at the BIP-44 path described by
+    /// `config` and `index`. Use `DerivationConfig::default()` for Fuel's coin type.
+    pub fn new_from_seed(
+        seed: &[u8],
+        config: DerivationConfig,
+        index: u32,
+        provider: Provider,
+    ) -> Result<Self, WalletError> {
+        let private_key = derive_secret_key(seed, &config.path(index))?;
+        Self::new_from_private_key(private_key, provider)
+    }
+
+    /// Creates a wallet from a BIP-39 mnemonic phrase, with the key derived at `path`,
+    /// e.g. `m/44'/1179993420'/0'/0/0`, see `DerivationPath`. Fails if the phrase isn't a
+    /// valid BIP-39 mnemonic.
+    pub fn new_from_mnemonic_phrase_with_path(
+        phrase: &str,
+        path: &str,
+        provider: Provider,
+    ) -> Result<Self, WalletError> {
+        let path = DerivationPath::from_str(path)?;
+        let seed = mnemonic_to_seed(phrase, "")?;
+        let private_key = derive_secret_key(&seed, path.indices())?;
+        Self::new_from_private_key(private_key, provider)
+    }
+
+    /// Creates a wallet connected to the node at `url`. Unlike `new_from_private_key`,
+    /// this performs a handshake with the node and errors immediately if it can't be
+    /// reached or doesn't run the network named `network`, see `Provider::connect_url`.
+    pub async fn connect(
+        private_key: SecretKey,
+        url: &str,
+        network: &str,
+    ) -> Result<Self, WalletError> {
+        let provider = Provider::connect_url(url, network).await?;
+        Self::new_from_private_key(private_key, provider)
+    }
+
+    /// Caches the coins returned by `get_coins` for `ttl`, saving round trips to the
+    /// node for apps that query them repeatedly. The cache is shared between clones of
+    /// this wallet and dropped after every `transfer`.
+    pub fn with_coin_cache(mut self, ttl: Duration) -> Self {
+        self.coin_cache = Some(Arc::new(Mutex::new(CoinCache::new(ttl))));
+        self
+    }
+
     pub fn set_provider(&mut self, provider: Provider) {
         self.provider = provider
     }
@@ -144,11 +304,14 @@ impl Wallet {
     ///   let wallet_2 = LocalWallet::new_from_private_key(pk_2, provider).unwrap();
     ///
     ///   // Transfer 1 from wallet 1 to wallet 2
-    ///   let _receipts = wallet_1
+    ///   let result = wallet_1
     ///        .transfer(&wallet_2.address(), 1, Default::default())
     ///        .await
     ///        .unwrap();
     ///
+    ///   // The transaction id can be used to query its status later on
+    ///   let _status = wallet_1.provider.client.transaction_status(&result.tx_id.to_string()).await?;
+    ///
     ///   let wallet_2_final_coins = wallet_2.get_coins().await.unwrap();
     ///
     ///   // Check that wallet two now has two coins
@@ -161,22 +324,660 @@ impl Wallet {
         to: &Address,
         amount: u64,
         asset_id: AssetId,
-    ) -> Result<Vec<Receipt>, WalletError> {
+    ) -> Result<TransferResult, WalletError> {
+        self.transfer_with_change_address(to, amount, asset_id, None)
+            .await
+    }
+
+    /// Same as `transfer`, but sends the change to `change_to` instead of this wallet.
+    /// If `change_to` is `None`, the change goes back to this wallet.
+    pub async fn transfer_with_change_address(
+        &self,
+        to: &Address,
+        amount: u64,
+        asset_id: AssetId,
+        change_to: Option<Address>,
+    ) -> Result<TransferResult, WalletError> {
+        let params = TxParameters::default();
+        self.transfer_with_options(to, amount, asset_id, change_to, None, &params)
+            .await
+    }
+
+    /// Same as `transfer`, with the gas price, byte price, maturity and gas margin of
+    /// `params`. The gas limit is estimated, use `transfer_with_gas_limit` to set it
+    /// explicitly. The fee is paid in the base asset, so when transferring another asset
+    /// the wallet also spends base asset coins to cover it.
+    pub async fn transfer_with_params(
+        &self,
+        to: &Address,
+        amount: u64,
+        asset_id: AssetId,
+        params: &TxParameters,
+    ) -> Result<TransferResult, WalletError> {
+        self.transfer_with_options(to, amount, asset_id, None, None, params)
+            .await
+    }
+
+    /// Same as `transfer`, with a custom gas limit.
+    /// Fails with `WalletError::GasLimitTooHigh` before anything is submitted if
+    /// `gas_limit` is above the node's `max_gas_per_tx`.
+    pub async fn transfer_with_gas_limit(
+        &self,
+        to: &Address,
+        amount: u64,
+        asset_id: AssetId,
+        gas_limit: u64,
+    ) -> Result<TransferResult, WalletError> {
+        let params = TxParameters::default();
+        self.transfer_with_options(to, amount, asset_id, None, Some(gas_limit), &params)
+            .await
+    }
+
+    /// Builds and simulates the transfer `transfer_with_params` would send, without
+    /// broadcasting it. Returns the signed transaction along with the receipts of the
+    /// dry-run, so that what would be sent can be inspected. Nothing changes on the node
+    /// and the coins spent by the transaction remain available.
+    pub async fn transfer_dry_run(
+        &self,
+        to: &Address,
+        amount: u64,
+        asset_id: AssetId,
+        params: &TxParameters,
+    ) -> Result<(Transaction, Vec<Receipt>), WalletError> {
+        let (tx, inputs) = self
+            .build_transfer_tx(to, amount, asset_id, None, None, params)
+            .await?;
+        let receipts = self.provider.dry_run(&tx).await;
+        self.release_inputs(&inputs);
+
+        Ok((tx, receipts?))
+    }
+
+    /// Transfers `amount` to `to`, see `build_transfer_tx`.
+    async fn transfer_with_options(
+        &self,
+        to: &Address,
+        amount: u64,
+        asset_id: AssetId,
+        change_to: Option<Address>,
+        gas_limit: Option<u64>,
+        params: &TxParameters,
+    ) -> Result<TransferResult, WalletError> {
+        let (tx, inputs) = self
+            .build_transfer_tx(to, amount, asset_id, change_to, gas_limit, params)
+            .await?;
+
+        self.send_signed_transfer_tx(&tx, &inputs).await
+    }
+
+    /// Builds and signs a transfer of `amount` to `to`, with a gas limit of `gas_limit`,
+    /// or estimated with a dry-run and the gas margin of `params` if it's `None`. Base
+    /// asset coins are added to the inputs until they cover the fee, on top of `amount`
+    /// if it's the base asset. The inputs are returned reserved, callers must release
+    /// them if the transaction isn't sent.
+    async fn build_transfer_tx(
+        &self,
+        to: &Address,
+        amount: u64,
+        asset_id: AssetId,
+        change_to: Option<Address>,
+        gas_limit: Option<u64>,
+        params: &TxParameters,
+    ) -> Result<(Transaction, Vec<Input>), WalletError> {
+        let max_gas_per_tx = self.consensus_parameters().await?.max_gas_per_tx;
+        if let Some(gas_limit) = gas_limit {
+            if gas_limit > max_gas_per_tx {
+                return Err(WalletError::GasLimitTooHigh(gas_limit, max_gas_per_tx));
+            }
+        }
+
         let inputs = self
-            .get_asset_inputs_for_amount(asset_id, amount, 0)
+            .reserve_asset_inputs_for_amount(asset_id, amount, 0)
+            .await?;
+        let payments = [Output::coin(*to, amount, asset_id)];
+        let change_to = change_to.unwrap_or_else(|| self.address());
+
+        self.fund_transfer_tx(inputs, &payments, change_to, gas_limit, params)
+            .await
+    }
+
+    /// Builds and signs a transaction paying the coin outputs `payments` out of `inputs`,
+    /// which must have been reserved by this wallet, the change of each spent asset going
+    /// to `change_to`. The gas limit is `gas_limit`, or estimated with a dry-run and the
+    /// gas margin of `params` if it's `None`. Base asset coins are added to the inputs
+    /// until they cover the fee, on top of the base asset paid out. The inputs are
+    /// returned reserved, or released if it fails.
+    async fn fund_transfer_tx(
+        &self,
+        mut inputs: Vec<Input>,
+        payments: &[Output],
+        change_to: Address,
+        gas_limit: Option<u64>,
+        params: &TxParameters,
+    ) -> Result<(Transaction, Vec<Input>), WalletError> {
+        let base_asset = AssetId::default();
+        // Note that the change will be computed by the node.
+        // Here we only have to tell the node who will own the change of each asset.
+        let transfer_outputs = |inputs: &[Input]| {
+            let mut outputs = payments.to_vec();
+            outputs.extend(change_outputs(inputs, change_to));
+            outputs
+        };
+        let mut outputs = transfer_outputs(&inputs);
+
+        let gas_limit = match gas_limit {
+            Some(gas_limit) => gas_limit,
+            None => {
+                let estimated = self.estimate_transfer_gas_limit(&inputs, &outputs, params);
+                match estimated.await {
+                    Ok(gas_limit) => gas_limit,
+                    Err(e) => {
+                        self.release_inputs(&inputs);
+                        return Err(e);
+                    }
+                }
+            }
+        };
+
+        // Each coin added for the fee makes the transaction bigger, so the fee is
+        // computed again until the base asset inputs cover it.
+        let spent_base = payments
+            .iter()
+            .filter_map(|output| match output {
+                Output::Coin {
+                    amount, asset_id, ..
+                } if *asset_id == base_asset => Some(*amount),
+                _ => None,
+            })
+            .fold(0, u64::saturating_add);
+        loop {
+            let mut tx = self
+                .provider
+                .build_transfer_tx_with_gas_limit(&inputs, &outputs, gas_limit);
+            set_tx_prices(&mut tx, params);
+            if let Err(e) = self.sign_transaction(&mut tx).await {
+                self.release_inputs(&inputs);
+                return Err(e);
+            }
+
+            let required = spent_base.saturating_add(transfer_fee(&tx, gas_limit, params));
+            let available: u64 = inputs
+                .iter()
+                .filter_map(|input| match input {
+                    Input::Coin {
+                        amount, asset_id, ..
+                    } if *asset_id == base_asset => Some(*amount),
+                    _ => None,
+                })
+                .sum();
+            if available >= required {
+                if let Err(e) = check_dust_change(&tx, params) {
+                    self.release_inputs(&inputs);
+                    return Err(e);
+                }
+                return Ok((tx, inputs));
+            }
+
+            match self
+                .reserve_asset_inputs_for_amount(base_asset, required - available, 0)
+                .await
+            {
+                Ok(fee_inputs) => {
+                    inputs.extend(fee_inputs);
+                    outputs = transfer_outputs(&inputs);
+                }
+                Err(e) => {
+                    self.release_inputs(&inputs);
+                    return Err(match e {
+                        WalletError::NotEnoughCoins(..) => {
+                            WalletError::NotEnoughCoins(required, base_asset)
+                        }
+                        e => e,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Transfers `amount` of `asset_id` to `to`, spending exactly `inputs` rather than
+    /// selecting coins. The inputs must be coins owned by this wallet, covering `amount`
+    /// and the fee set by `params`, which is paid in the base asset. They're checked
+    /// against the unspent coins the node reports for this wallet, skipping the coin cache,
+    /// and can't be reserved by another transfer. The change goes back to this wallet.
+    pub async fn transfer_with_inputs(
+        &self,
+        inputs: Vec<Input>,
+        to: &Address,
+        amount: u64,
+        asset_id: AssetId,
+        params: &TxParameters,
+    ) -> Result<TransferResult, WalletError> {
+        for input in &inputs {
+            match input {
+                Input::Coin { utxo_id, owner, .. } if *owner != self.address => {
+                    return Err(WalletError::ForeignInput(*utxo_id))
+                }
+                Input::Coin { .. } => {}
+                Input::Contract { utxo_id, .. } => return Err(WalletError::ForeignInput(*utxo_id)),
+            }
+        }
+
+        let coins: HashMap<UtxoId, Coin> = self
+            .provider
+            .get_coins(&self.address())
+            .await?
+            .into_iter()
+            .filter(|coin| matches!(coin.status, CoinStatus::Unspent))
+            .map(|coin| (UtxoId::from(coin.utxo_id.clone()), coin))
+            .collect();
+
+        // The coins are reserved in the same critical section they're checked in, so that
+        // a concurrent transfer can't pick them in between
+        let mut available: HashMap<AssetId, u64> = HashMap::new();
+        {
+            let mut reserved = self.reserved_coins.lock().unwrap();
+            for input in &inputs {
+                if let Input::Coin {
+                    utxo_id,
+                    amount,
+                    asset_id,
+                    ..
+                } = input
+                {
+                    let known = coins.get(utxo_id).map_or(false, |coin| {
+                        coin.amount.0 == *amount
+                            && AssetId::from(coin.asset_id.clone()) == *asset_id
+                    });
+                    if !known || reserved.contains(utxo_id) {
+                        return Err(WalletError::UnspendableInput(*utxo_id));
+                    }
+                    *available.entry(*asset_id).or_default() += amount;
+                }
+            }
+            reserved.extend(inputs.iter().filter_map(coin_utxo_id));
+        }
+
+        let base_asset = AssetId::default();
+        let mut outputs = vec![Output::coin(*to, amount, asset_id)];
+        outputs.extend(change_outputs(&inputs, self.address()));
+
+        let mut tx =
+            self.provider
+                .build_transfer_tx_with_gas_limit(&inputs, &outputs, params.gas_limit);
+        set_tx_prices(&mut tx, params);
+        if let Err(e) = self.sign_transaction(&mut tx).await {
+            self.release_inputs(&inputs);
+            return Err(e);
+        }
+
+        // The fee is only known once the signature adds to the size of the transaction
+        let fee = transfer_fee(&tx, params.gas_limit, params);
+        let mut required = HashMap::from([(base_asset, fee)]);
+        *required.entry(asset_id).or_default() += amount;
+        for (asset_id, required) in required {
+            if available.get(&asset_id).copied().unwrap_or_default() < required {
+                self.release_inputs(&inputs);
+                return Err(WalletError::NotEnoughCoins(required, asset_id));
+            }
+        }
+        if let Err(e) = check_dust_change(&tx, params) {
+            self.release_inputs(&inputs);
+            return Err(e);
+        }
+
+        self.send_signed_transfer_tx(&tx, &inputs).await
+    }
+
+    /// Estimates the gas limit of a transfer of `inputs` to `outputs` by dry-running it,
+    /// applying the gas margin of `params` and capping it to the node's maximum.
+    pub async fn estimate_transfer_gas_limit(
+        &self,
+        inputs: &[Input],
+        outputs: &[Output],
+        params: &TxParameters,
+    ) -> Result<u64, WalletError> {
+        let max_gas_per_tx = self.consensus_parameters().await?.max_gas_per_tx;
+
+        let mut tx =
+            self.provider
+                .build_transfer_tx_with_gas_limit(inputs, outputs, max_gas_per_tx);
+        self.sign_transaction(&mut tx).await?;
+        let gas_used = self.provider.estimate_gas_used(&tx).await?;
+
+        Ok(params.apply_gas_margin(gas_used, max_gas_per_tx))
+    }
+
+    /// Pays each `(recipient, amount)` of `recipients` in `asset_id`, spreading them over
+    /// as many transactions as needed to stay within the node's maximum number of inputs
+    /// and outputs per transaction. Returns the id and receipts of each transaction.
+    /// The wallet's available balance is checked upfront against the total and the fee of
+    /// each transaction, so nothing is sent if it can't pay every recipient. That fee
+    /// only accounts for the outputs, as the inputs aren't selected yet: each transaction
+    /// is still checked to cover its own fee before it's sent.
+    pub async fn transfer_to_many(
+        &self,
+        recipients: &[(Address, u64)],
+        asset_id: AssetId,
+    ) -> Result<Vec<TransferResult>, WalletError> {
+        let params = self.consensus_parameters().await?;
+        self.transfer_to_many_with_limits(
+            recipients,
+            asset_id,
+            params.max_inputs as usize,
+            params.max_outputs as usize,
+        )
+        .await
+    }
+
+    /// Same as `transfer_to_many`, with custom maximum numbers of inputs and outputs
+    /// per transaction. These can't go above the node's own limits.
+    pub async fn transfer_to_many_with_limits(
+        &self,
+        recipients: &[(Address, u64)],
+        asset_id: AssetId,
+        max_inputs: usize,
+        max_outputs: usize,
+    ) -> Result<Vec<TransferResult>, WalletError> {
+        let params = self.consensus_parameters().await?;
+        let max_inputs = max_inputs.min(params.max_inputs as usize);
+        let max_outputs = max_outputs.min(params.max_outputs as usize);
+
+        // Each transaction needs an output for the change of `asset_id`, and another one
+        // for the change of the base asset if coins of it are added for the fee
+        let base_asset = AssetId::default();
+        let change_count = if asset_id == base_asset { 1 } else { 2 };
+        if max_outputs <= change_count {
+            return Err(WalletError::TooManyOutputs(change_count + 1, max_outputs));
+        }
+        let chunk_size = max_outputs - change_count;
+
+        let payments = |chunk: &[(Address, u64)]| -> Vec<Output> {
+            chunk
+                .iter()
+                .map(|(to, amount)| Output::coin(*to, *amount, asset_id))
+                .collect()
+        };
+        let tx_params = TxParameters::default();
+
+        let total = recipients
+            .iter()
+            .try_fold(0u64, |total, (_, amount)| total.checked_add(*amount))
+            .ok_or(WalletError::AmountOverflow)?;
+        let fees = recipients
+            .chunks(chunk_size)
+            .map(|chunk| {
+                let tx = self.provider.build_transfer_tx_with_gas_limit(
+                    &[],
+                    &payments(chunk),
+                    DEFAULT_GAS_LIMIT,
+                );
+                transfer_fee(&tx, DEFAULT_GAS_LIMIT, &tx_params)
+            })
+            .fold(0, u64::saturating_add);
+
+        let mut required = HashMap::from([(base_asset, fees)]);
+        let asset_required = required.entry(asset_id).or_default();
+        *asset_required = asset_required
+            .checked_add(total)
+            .ok_or(WalletError::AmountOverflow)?;
+        for (asset_id, required) in required {
+            if self.available_balance(asset_id).await? < required {
+                return Err(WalletError::NotEnoughCoins(required, asset_id));
+            }
+        }
+
+        let mut results = vec![];
+        for chunk in recipients.chunks(chunk_size) {
+            let amount = chunk.iter().map(|(_, amount)| amount).sum();
+
+            let inputs = self
+                .reserve_asset_inputs_for_amount(asset_id, amount, 0)
+                .await?;
+            let (tx, inputs) = self
+                .fund_transfer_tx(
+                    inputs,
+                    &payments(chunk),
+                    self.address(),
+                    Some(DEFAULT_GAS_LIMIT),
+                    &tx_params,
+                )
+                .await?;
+            if inputs.len() > max_inputs {
+                self.release_inputs(&inputs);
+                return Err(WalletError::TooManyInputs(inputs.len(), max_inputs));
+            }
+
+            results.push(self.send_signed_transfer_tx(&tx, &inputs).await?);
+        }
+
+        Ok(results)
+    }
+
+    /// The consensus parameters of the node, or those of `fuel-tx` it validates
+    /// transactions against if it doesn't expose them.
+    async fn consensus_parameters(&self) -> Result<ConsensusParameters, WalletError> {
+        match self.provider.consensus_parameters().await {
+            Err(ProviderError::Unsupported(_)) => Ok(ConsensusParameters::default()),
+            params => Ok(params?),
+        }
+    }
+
+    /// Sends `tx`, then releases its reserved `inputs`: once it's accepted, the node
+    /// reports them as spent so they aren't selected again, and if it fails they're
+    /// available to other transfers.
+    async fn send_signed_transfer_tx(
+        &self,
+        tx: &Transaction,
+        inputs: &[Input],
+    ) -> Result<TransferResult, WalletError> {
+        let result = self.provider.send_transaction(tx).await;
+        self.release_inputs(inputs);
+        if let Some(cache) = &self.coin_cache {
+            cache.lock().unwrap().invalidate();
+        }
+
+        Ok(TransferResult {
+            tx_id: tx.id(),
+            receipts: result?,
+        })
+    }
+
+    /// Spends a single coin of at least `coin_amount` and sends `coin_amount` back to this
+    /// wallet as `into` roughly-equal coins, the last one taking the remainder of the
+    /// division. Whatever the spent coin holds above `coin_amount` comes back as change.
+    /// Base asset coins are added for the fee if the spent coin doesn't cover it, as in
+    /// `transfer`, and the gas limit is estimated with a dry-run. Fails if `into` is zero
+    /// or if the coins and the change don't fit in the node's maximum number of outputs.
+    pub async fn split_coin(
+        &self,
+        coin_amount: u64,
+        into: usize,
+        asset_id: AssetId,
+    ) -> Result<TransferResult, WalletError> {
+        // The change takes one output, and another one for the base asset paying the fee
+        // when splitting another asset
+        let change_count = if asset_id == AssetId::default() { 1 } else { 2 };
+        let max_outputs = self.consensus_parameters().await?.max_outputs as usize;
+        let max_split = max_outputs.saturating_sub(change_count);
+        if into == 0 || into > max_split {
+            return Err(WalletError::InvalidSplit(into, max_split));
+        }
+
+        let coins = self.get_coins().await?;
+
+        let input = {
+            let mut reserved = self.reserved_coins.lock().unwrap();
+
+            let coin = coins
+                .into_iter()
+                .find(|coin| {
+                    matches!(coin.status, CoinStatus::Unspent)
+                        && AssetId::from(coin.asset_id.clone()) == asset_id
+                        && coin.amount.0 >= coin_amount
+                        && !reserved.contains(&UtxoId::from(coin.utxo_id.clone()))
+                })
+                .ok_or(WalletError::NotEnoughCoins(coin_amount, asset_id))?;
+
+            let utxo_id = UtxoId::from(coin.utxo_id);
+            reserved.insert(utxo_id);
+
+            Input::coin(
+                utxo_id,
+                coin.owner.into(),
+                coin.amount.0,
+                asset_id,
+                0,
+                0,
+                vec![],
+                vec![],
+            )
+        };
+
+        let share = coin_amount / into as u64;
+        let remainder = coin_amount % into as u64;
+        let payments: Vec<Output> = (0..into)
+            .map(|i| {
+                let amount = if i == into - 1 {
+                    share + remainder
+                } else {
+                    share
+                };
+                Output::coin(self.address(), amount, asset_id)
+            })
+            .collect();
+
+        let (tx, inputs) = self
+            .fund_transfer_tx(
+                vec![input],
+                &payments,
+                self.address(),
+                None,
+                &TxParameters::default(),
+            )
             .await?;
-        let outputs: Vec<Output> = vec![
-            Output::coin(*to, amount, asset_id),
-            // Note that the change will be computed by the node.
-            // Here we only have to tell the node who will own the change and its asset ID.
-            Output::change(self.address(), 0, asset_id),
-        ];
+        self.send_signed_transfer_tx(&tx, &inputs).await
+    }
 
-        // Build transaction and sign it
-        let mut tx = self.provider.build_transfer_tx(&inputs, &outputs);
-        let _sig = self.sign_transaction(&mut tx).await.unwrap();
+    /// Like `get_asset_inputs_for_amount`, but skips coins reserved by other in-flight
+    /// transfers from this wallet and reserves the selected ones. Call `release_inputs`
+    /// if the transaction using them doesn't go through.
+    pub async fn reserve_asset_inputs_for_amount(
+        &self,
+        asset_id: AssetId,
+        amount: u64,
+        witness_index: u8,
+    ) -> Result<Vec<Input>, WalletError> {
+        let coins = self.get_coins().await?;
+
+        // The lock is only taken once the coins are fetched so it's never held across
+        // an `.await`.
+        let mut reserved = self.reserved_coins.lock().unwrap();
 
-        Ok(self.provider.send_transaction(&tx).await?)
+        let mut inputs = vec![];
+        let mut total = 0;
+        for coin in coins {
+            if total >= amount {
+                break;
+            }
+
+            let utxo_id = UtxoId::from(coin.utxo_id);
+            let coin_asset_id: AssetId = coin.asset_id.into();
+            if !matches!(coin.status, CoinStatus::Unspent)
+                || coin_asset_id != asset_id
+                || reserved.contains(&utxo_id)
+            {
+                continue;
+            }
+
+            total += coin.amount.0;
+            inputs.push(Input::coin(
+                utxo_id,
+                coin.owner.into(),
+                coin.amount.0,
+                asset_id,
+                witness_index,
+                0,
+                vec![],
+                vec![],
+            ));
+        }
+
+        if total < amount {
+            return Err(WalletError::NotEnoughCoins(amount, asset_id));
+        }
+
+        reserved.extend(inputs.iter().filter_map(coin_utxo_id));
+
+        Ok(inputs)
+    }
+
+    /// Releases the coins of `inputs` reserved by `reserve_asset_inputs_for_amount`,
+    /// making them available to other transfers again.
+    pub fn release_inputs(&self, inputs: &[Input]) {
+        let mut reserved = self.reserved_coins.lock().unwrap();
+        for utxo_id in inputs.iter().filter_map(coin_utxo_id) {
+            reserved.remove(&utxo_id);
+        }
+    }
+
+    /// Signs the transaction and places the signature at `witness_index` in the
+    /// transaction's list of witnesses, padding it with empty witnesses if needed.
+    /// Unlike `sign_transaction`, which appends the signature, this allows several
+    /// wallets to sign the same transaction. Every coin input owned by this wallet
+    /// must reference `witness_index`, otherwise the node would check the wrong witness.
+    pub async fn sign_transaction_at(
+        &self,
+        tx: &mut Transaction,
+        witness_index: u8,
+    ) -> Result<Signature, WalletError> {
+        let mismatched = tx.inputs().iter().any(|input| {
+            matches!(
+                input,
+                Input::Coin { owner, witness_index: index, .. }
+                    if *owner == self.address && *index != witness_index
+            )
+        });
+        if mismatched {
+            return Err(WalletError::WitnessIndexMismatch(witness_index));
+        }
+
+        let id = tx.id();
+        let sig = secp256k1_sign_compact_recoverable(self.private_key.as_ref(), &*id).unwrap();
+        let sig = Signature { compact: sig };
+
+        let index = witness_index as usize;
+        let mut witnesses: Vec<Witness> = tx.witnesses().to_vec();
+        if witnesses.len() <= index {
+            witnesses.resize(index + 1, Witness::default());
+        }
+        witnesses[index] = Witness::from(sig.compact.as_ref());
+        tx.set_witnesses(witnesses);
+
+        Ok(sig)
+    }
+
+    /// Adds this wallet's signature to a transaction signed by several parties, at the
+    /// witness index its coin inputs reference, keeping the other parties' witnesses.
+    /// This allows passing a partially signed transaction around until every owner of
+    /// its inputs has signed it, in any order. See `sign_transaction_at`.
+    pub async fn add_signature_to_tx(
+        &self,
+        tx: &mut Transaction,
+    ) -> Result<Signature, WalletError> {
+        let witness_index = tx
+            .inputs()
+            .iter()
+            .find_map(|input| match input {
+                Input::Coin {
+                    owner,
+                    witness_index,
+                    ..
+                } if *owner == self.address => Some(*witness_index),
+                _ => None,
+            })
+            .ok_or(WalletError::NoOwnedInputs)?;
+
+        self.sign_transaction_at(tx, witness_index).await
     }
 
     /// Returns a proper vector of `Input::Coin`s for the given asset ID, amount, and witness index.
@@ -210,9 +1011,60 @@ impl Wallet {
     }
 
     /// Gets coins from this wallet
-    /// Note that this is a simple wrapper on provider's `get_coins`.
+    /// Note that this is a simple wrapper on provider's `get_coins`, unless a coin
+    /// cache was set with `with_coin_cache`.
     pub async fn get_coins(&self) -> Result<Vec<Coin>, WalletError> {
-        Ok(self.provider.get_coins(&self.address()).await?)
+        if let Some(cache) = &self.coin_cache {
+            if let Some(coins) = cache.lock().unwrap().get() {
+                return Ok(coins);
+            }
+        }
+
+        let coins = self.provider.get_coins(&self.address()).await?;
+
+        if let Some(cache) = &self.coin_cache {
+            cache.lock().unwrap().set(coins.clone());
+        }
+
+        Ok(coins)
+    }
+
+    /// Gets the coins of this wallet as of the block at `height`, bypassing the coin cache,
+    /// see provider's `get_coins_at_block`.
+    pub async fn get_coins_at_block(&self, height: u64) -> Result<Vec<Coin>, WalletError> {
+        Ok(self
+            .provider
+            .get_coins_at_block(&self.address(), height)
+            .await?)
+    }
+
+    /// Gets the balance of every asset owned by this wallet.
+    /// Note that this is a simple wrapper on provider's `get_balances`.
+    pub async fn get_balances(&self) -> Result<HashMap<AssetId, u64>, WalletError> {
+        Ok(self.provider.get_balances(&self.address()).await?)
+    }
+
+    /// Gets the amount of `asset_id` this wallet can spend right now, i.e. its unspent
+    /// coins minus the ones reserved by transfers that are still in flight. Their change
+    /// only counts once it's received.
+    pub async fn available_balance(&self, asset_id: AssetId) -> Result<u64, WalletError> {
+        let coins = self.get_coins().await?;
+        let reserved = self.reserved_coins.lock().unwrap();
+
+        Ok(coins
+            .into_iter()
+            .filter(|coin| {
+                matches!(coin.status, CoinStatus::Unspent)
+                    && AssetId::from(coin.asset_id.clone()) == asset_id
+                    && !reserved.contains(&UtxoId::from(coin.utxo_id.clone()))
+            })
+            .map(|coin| coin.amount.0)
+            .sum())
+    }
+
+    /// Returns a query-only handle to this wallet, holding its address but not its key.
+    pub fn to_watch_only(&self) -> WatchOnlyWallet {
+        WatchOnlyWallet::new(self.address, self.provider.clone())
     }
 
     /// Gets spendable coins from this wallet.
@@ -222,7 +1074,7 @@ impl Wallet {
         &self,
         asset_id: &AssetId,
         amount: u64,
-    ) -> io::Result<Vec<Coin>> {
+    ) -> Result<Vec<Coin>, WalletError> {
         Ok(self
             .provider
             .get_spendable_coins(&self.address(), *asset_id, amount)
@@ -271,6 +1123,137 @@ impl Signer for Wallet {
     }
 }
 
+fn coin_utxo_id(input: &Input) -> Option<UtxoId> {
+    match input {
+        Input::Coin { utxo_id, .. } => Some(*utxo_id),
+        _ => None,
+    }
+}
+
+/// Sets the gas price, byte price and maturity of `params` on a transfer built by
+/// `Provider::build_transfer_tx_with_gas_limit`.
+fn set_tx_prices(tx: &mut Transaction, params: &TxParameters) {
+    if let Transaction::Script {
+        gas_price,
+        byte_price,
+        maturity,
+        ..
+    } = tx
+    {
+        *gas_price = params.gas_price;
+        *byte_price = params.byte_price;
+        *maturity = params.maturity.into();
+    }
+}
+
+/// Fails with `WalletError::DustChange` if the change of any asset spent by `tx`, what its
+/// coin inputs hold above its coin outputs, is positive but below the `min_output_amount`
+/// of `params`. Assets are checked in the order `tx` first spends them.
+/// The change of the base asset also pays the fee, which is only known before execution
+/// when gas is free: it's then checked net of the byte fee, and left unchecked otherwise.
+fn check_dust_change(tx: &Transaction, params: &TxParameters) -> Result<(), WalletError> {
+    let mut assets = vec![];
+    let mut change: HashMap<AssetId, i128> = HashMap::new();
+    for input in tx.inputs() {
+        if let Input::Coin {
+            amount, asset_id, ..
+        } = input
+        {
+            if !change.contains_key(asset_id) {
+                assets.push(*asset_id);
+            }
+            *change.entry(*asset_id).or_default() += *amount as i128;
+        }
+    }
+    for output in tx.outputs() {
+        if let Output::Coin {
+            amount, asset_id, ..
+        } = output
+        {
+            *change.entry(*asset_id).or_default() -= *amount as i128;
+        }
+    }
+
+    let base_asset = AssetId::default();
+    if params.gas_price == 0 {
+        let byte_fee = params
+            .byte_price
+            .saturating_mul(estimated_byte_size(tx) as u64);
+        *change.entry(base_asset).or_default() -= byte_fee as i128;
+    } else {
+        change.remove(&base_asset);
+    }
+
+    let min = params.min_output_amount;
+    for asset_id in assets {
+        match change.get(&asset_id) {
+            Some(&change) if change > 0 && change < min as i128 => {
+                return Err(WalletError::DustChange(asset_id, change as u64, min));
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// The maximum fee of `tx` in the base asset, with the prices of `params`.
+fn transfer_fee(tx: &Transaction, gas_limit: u64, params: &TxParameters) -> u64 {
+    params.gas_price.saturating_mul(gas_limit).saturating_add(
+        params
+            .byte_price
+            .saturating_mul(estimated_byte_size(tx) as u64),
+    )
+}
+
+/// A wallet that only knows its address. It can query the node for coins and balances,
+/// e.g. for portfolio trackers, but all signing operations fail with
+/// [`WalletError::WatchOnly`].
+#[derive(Debug, Clone)]
+pub struct WatchOnlyWallet {
+    pub(crate) address: Address,
+
+    pub provider: Provider,
+}
+
+impl WatchOnlyWallet {
+    pub fn new(address: Address, provider: Provider) -> Self {
+        Self { address, provider }
+    }
+
+    /// Gets coins from this wallet
+    /// Note that this is a simple wrapper on provider's `get_coins`.
+    pub async fn get_coins(&self) -> Result<Vec<Coin>, WalletError> {
+        Ok(self.provider.get_coins(&self.address).await?)
+    }
+
+    /// Gets the balance of every asset owned by this wallet.
+    /// Note that this is a simple wrapper on provider's `get_balances`.
+    pub async fn get_balances(&self) -> Result<HashMap<AssetId, u64>, WalletError> {
+        Ok(self.provider.get_balances(&self.address).await?)
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl Signer for WatchOnlyWallet {
+    type Error = WalletError;
+
+    async fn sign_message<S: Send + Sync + AsRef<[u8]>>(
+        &self,
+        _message: S,
+    ) -> Result<Signature, Self::Error> {
+        Err(WalletError::WatchOnly)
+    }
+
+    async fn sign_transaction(&self, _tx: &mut Transaction) -> Result<Signature, Self::Error> {
+        Err(WalletError::WatchOnly)
+    }
+
+    fn address(&self) -> Address {
+        self.address
+    }
+}
+
 impl fmt::Debug for Wallet {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Wallet")
@@ -21,6 +21,9 @@ pub struct Function {
     pub inputs: Vec<Property>,
     pub name: String,
     pub outputs: Vec<Property>,
+    /// Annotations of the function, e.g. `storage(read)`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attributes: Option<Vec<String>>,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -31,3 +34,69 @@ pub struct Property {
     pub type_field: String,
     pub components: Option<Vec<Property>>, // Used for custom types
 }
+
+/// Program ABI in the format emitted by newer versions of forc, where every type is declared
+/// once in `types` and referenced by its id everywhere else.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProgramABI {
+    pub types: Vec<TypeDeclaration>,
+    pub functions: Vec<ABIFunction>,
+    pub logged_types: Option<Vec<LoggedType>>,
+    pub configurables: Option<Vec<ABIConfigurable>>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypeDeclaration {
+    pub type_id: usize,
+    /// E.g. `u64`, `struct MyStruct`, `[_; 2]` or `(_, _)`.
+    #[serde(rename = "type")]
+    pub type_field: String,
+    pub components: Option<Vec<TypeApplication>>,
+    pub type_parameters: Option<Vec<usize>>,
+}
+
+/// Use of a declared type, e.g. as a function argument or a struct field.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypeApplication {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub type_id: usize,
+    pub type_arguments: Option<Vec<TypeApplication>>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ABIFunction {
+    pub inputs: Vec<TypeApplication>,
+    pub name: String,
+    pub output: TypeApplication,
+    pub attributes: Option<Vec<Attribute>>,
+}
+
+/// Annotation of a function, e.g. `storage` with the arguments `read` and `write`.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Attribute {
+    pub name: String,
+    pub arguments: Vec<String>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoggedType {
+    pub log_id: u64,
+    pub logged_type: TypeApplication,
+}
+
+/// Constant of a program that can be set before deploying it, stored at `offset` in its
+/// bytecode.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ABIConfigurable {
+    pub name: String,
+    pub configurable_type: TypeApplication,
+    pub offset: u64,
+}